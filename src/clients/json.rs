@@ -18,6 +18,7 @@ use http::Request;
 use hyper::client::connect::HttpInfo;
 use hyper::{Body, Client as HyperClient};
 use hyper_alpn::AlpnConnector;
+use log::debug;
 use num_traits::FromPrimitive;
 use serde::{Deserialize, Serialize};
 use serde_json;
@@ -290,7 +291,7 @@ impl AsyncExchanger for Client {
             // Read the full body
             let body = hyper::body::to_bytes(resp.into_body()).await?;
 
-            println!("{:?}", body);
+            debug!("{:?}", body);
 
             let m: MessageJson = serde_json::from_slice(&body).map_err(ParseError::JsonError)?;
             let mut m: Message = m.try_into()?;
@@ -4,6 +4,7 @@ use crate::clients::Exchanger;
 use crate::types::*;
 use crate::Extension;
 use crate::Message;
+use log::debug;
 use std::collections::HashSet;
 use std::net::IpAddr;
 use std::net::SocketAddr;
@@ -84,7 +85,7 @@ where
 
             let response = self.client.exchange(&query)?; // TODO Better error message
 
-            println!(
+            debug!(
                 "{}: Trying {} and got {}",
                 name,
                 r#type,
@@ -2,10 +2,35 @@
 //! in `dig` style.
 // Refer to https://github.com/tigeli/bind-utils/blob/master/bin/dig/dig.c for reference.
 
+use crate::resource::AFSDB;
+use crate::resource::ATMA;
+use crate::resource::APLItem;
+use crate::resource::APL;
+use crate::resource::CERT;
+use crate::resource::CSYNC;
+use crate::resource::DOA;
+use crate::resource::DS;
+use crate::resource::TLSA;
+use crate::resource::GPOS;
+use crate::resource::ISDN;
+use crate::resource::HIP;
+use crate::resource::KEY;
+use crate::resource::L32;
+use crate::resource::L64;
+use crate::resource::LP;
+use crate::resource::SIG;
+use crate::resource::TKEY;
+use crate::resource::TSIG;
 use crate::resource::TXT;
 use crate::resource::MX;
+use crate::resource::NID;
+use crate::resource::RT;
 use crate::resource::SOA;
 use crate::resource::SRV;
+use crate::resource::SvcParam;
+use crate::resource::SVCB;
+use crate::resource::URI;
+use crate::resource::WKS;
 use crate::Message;
 use crate::Question;
 use crate::Record;
@@ -157,12 +182,16 @@ impl fmt::Display for Record {
             name = self.name,
             ttl = self.ttl.as_secs(),
             class = self.class,
-            r#type = self.r#type(),
+            r#type = self.resource.type_name(),
             resource = self.resource,
         )
     }
 }
 
+/// Displays the RDATA in the presentation format, as used in zone files,
+/// for example "10 mail.example.com." for a MX record. The alternate form
+/// (`{:#}`) spreads a SOA record over multiple lines within parentheses,
+/// with each value commented.
 impl fmt::Display for Resource {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -174,9 +203,44 @@ impl fmt::Display for Resource {
             Resource::PTR(name) => name.fmt(f),
 
             Resource::SOA(soa) => soa.fmt(f),
-            Resource::TXT(txts) | Resource::SPF(txts) => txts.fmt(f),
+            Resource::TXT(txts) | Resource::SPF(txts) | Resource::AVC(txts) => txts.fmt(f),
             Resource::MX(mx) => mx.fmt(f),
+            Resource::WKS(wks) => wks.fmt(f),
+            Resource::AFSDB(afsdb) => afsdb.fmt(f),
+            Resource::X25(address) => write!(f, "\"{}\"", address),
+            Resource::ISDN(isdn) => isdn.fmt(f),
+            Resource::RT(rt) => rt.fmt(f),
+            Resource::NSAP(nsap) => write!(f, "0x{}", hex::encode(nsap)),
+            Resource::NSAP_PTR(name) => name.fmt(f),
+            Resource::GPOS(gpos) => gpos.fmt(f),
             Resource::SRV(srv) => srv.fmt(f),
+            Resource::ATMA(atma) => atma.fmt(f),
+            Resource::URI(uri) => uri.fmt(f),
+            Resource::APL(apl) => apl.fmt(f),
+            Resource::CERT(cert) => cert.fmt(f),
+            Resource::DHCID(dhcid) => base64::encode(dhcid).fmt(f),
+            Resource::OPENPGPKEY(key) => base64::encode(key).fmt(f),
+            Resource::HIP(hip) => hip.fmt(f),
+            Resource::EUI48(eui) => fmt_eui(eui, f),
+            Resource::EUI64(eui) => fmt_eui(eui, f),
+            Resource::CSYNC(csync) => csync.fmt(f),
+            Resource::SVCB(svcb) | Resource::HTTPS(svcb) => svcb.fmt(f),
+            Resource::CDS(ds) => ds.fmt(f),
+            Resource::SMIMEA(tlsa) => tlsa.fmt(f),
+            Resource::CDNSKEY(key) => key.fmt(f),
+            Resource::NID(nid) => nid.fmt(f),
+            Resource::L32(l32) => l32.fmt(f),
+            Resource::L64(l64) => l64.fmt(f),
+            Resource::LP(lp) => lp.fmt(f),
+            Resource::KEY(key) => key.fmt(f),
+            Resource::SIG(sig) => sig.fmt(f),
+            Resource::TKEY(tkey) => tkey.fmt(f),
+            Resource::TSIG(tsig) => tsig.fmt(f),
+            Resource::DOA(doa) => doa.fmt(f),
+
+            // The generic form, for example "\# 2 abcd".
+            Resource::Unknown(_, rdata) if rdata.is_empty() => write!(f, "\\# 0"),
+            Resource::Unknown(_, rdata) => write!(f, "\\# {} {}", rdata.len(), hex::encode(rdata)),
 
             Resource::OPT => write!(f, "OPT (TODO)"),
             Resource::ANY => write!(f, "*"),
@@ -196,6 +260,96 @@ impl fmt::Display for MX {
     }
 }
 
+// Writes a EUI48 or EUI64 as hyphen separated pairs of hex digits, for
+// example "00-00-5e-00-53-2a".
+fn fmt_eui(eui: &[u8], f: &mut fmt::Formatter) -> fmt::Result {
+    for (i, b) in eui.iter().enumerate() {
+        if i > 0 {
+            write!(f, "-")?;
+        }
+        write!(f, "{:02x}", b)?;
+    }
+
+    Ok(())
+}
+
+impl fmt::Display for WKS {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "192.0.2.1 6 25 80"
+        write!(
+            f,
+            "{address} {protocol}",
+            address = self.address,
+            protocol = self.protocol,
+        )?;
+
+        for port in &self.ports {
+            write!(f, " {}", port)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for AFSDB {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "1 afsdb.example.com."
+        write!(
+            f,
+            "{subtype} {hostname}",
+            subtype = self.subtype,
+            hostname = self.hostname,
+        )
+    }
+}
+
+impl fmt::Display for RT {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "10 relay.example.com."
+        write!(
+            f,
+            "{preference} {host}",
+            preference = self.preference,
+            host = self.host,
+        )
+    }
+}
+
+impl fmt::Display for ISDN {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "\"150862028003217\" \"004\""
+        write!(f, "\"{}\"", self.address)?;
+        if let Some(subaddress) = &self.subaddress {
+            write!(f, " \"{}\"", subaddress)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ATMA {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            // "39246f00e7c9f0001f3c5c2e0000022bb3200000"
+            ATMA::AESA(address) => write!(f, "{}", hex::encode(address)),
+            // "+358400123456"
+            ATMA::E164(address) => write!(f, "+{}", address),
+        }
+    }
+}
+
+impl fmt::Display for GPOS {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "\"116.8652\" \"-32.6882\" \"10.0\""
+        write!(
+            f,
+            "\"{longitude}\" \"{latitude}\" \"{altitude}\"",
+            longitude = self.longitude,
+            latitude = self.latitude,
+            altitude = self.altitude,
+        )
+    }
+}
+
 impl fmt::Display for SOA {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         // "ns1.google.com. dns-admin.google.com. 376337657 900 900 1800 60"
@@ -207,6 +361,20 @@ impl fmt::Display for SOA {
             Err(_) => self.rname.to_owned(), // Ignore the error
         };
 
+        if f.alternate() {
+            return write!(
+                f,
+                "{mname} {rname} (\n    {serial} ; serial\n    {refresh} ; refresh\n    {retry} ; retry\n    {expire} ; expire\n    {minimum} ; minimum\n)",
+                mname = self.mname,
+                rname = rname,
+                serial = self.serial,
+                refresh = self.refresh.as_secs(),
+                retry = self.retry.as_secs(),
+                expire = self.expire.as_secs(),
+                minimum = self.minimum.as_secs(),
+            );
+        }
+
         write!(
             f,
             "{mname} {rname} {serial} {refresh} {retry} {expire} {minimum}",
@@ -235,19 +403,382 @@ impl fmt::Display for SRV {
     }
 }
 
+impl fmt::Display for URI {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "10 1 \"https://example.com/\""
+        // Any quotes or backslashes in the target are escaped, as in TXT.
+        write!(
+            f,
+            "{priority} {weight} \"{target}\"",
+            priority = self.priority,
+            weight = self.weight,
+            target = self.target.replace('\\', "\\\\").replace('"', "\\\""),
+        )
+    }
+}
+
+impl fmt::Display for CERT {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "PKIX 12345 8 MIIB..."
+        match Self::type_to_mnemonic(self.cert_type) {
+            Some(mnemonic) => write!(f, "{} ", mnemonic)?,
+            None => write!(f, "{} ", self.cert_type)?,
+        };
+
+        write!(
+            f,
+            "{key_tag} {algorithm} {certificate}",
+            key_tag = self.key_tag,
+            algorithm = self.algorithm,
+            certificate = base64::encode(&self.certificate),
+        )
+    }
+}
+
+impl fmt::Display for CSYNC {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "1278700841 3 A NS AAAA"
+        write!(
+            f,
+            "{serial} {flags}",
+            serial = self.serial,
+            flags = self.flags
+        )?;
+        for r#type in &self.types {
+            write!(f, " {}", r#type)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for NID {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "10 0014:4fff:ff20:ee64"
+        write!(
+            f,
+            "{preference} {node_id}",
+            preference = self.preference,
+            node_id = L64::locator_to_string(self.node_id),
+        )
+    }
+}
+
+impl fmt::Display for L32 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "10 10.1.2.0"
+        write!(
+            f,
+            "{preference} {locator}",
+            preference = self.preference,
+            locator = self.locator,
+        )
+    }
+}
+
+impl fmt::Display for L64 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "10 2001:0db8:1140:1000"
+        write!(
+            f,
+            "{preference} {locator}",
+            preference = self.preference,
+            locator = Self::locator_to_string(self.locator),
+        )
+    }
+}
+
+impl fmt::Display for LP {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "10 l64-subnet1.example.com."
+        write!(
+            f,
+            "{preference} {fqdn}",
+            preference = self.preference,
+            fqdn = self.fqdn,
+        )
+    }
+}
+
+impl fmt::Display for HIP {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "2 200100107B1A74DF365639CC39F1D578 AwEAAbdx... rvs.example.com."
+        write!(
+            f,
+            "{algorithm} {hit} {public_key}",
+            algorithm = self.algorithm,
+            hit = hex::encode_upper(&self.hit),
+            public_key = base64::encode(&self.public_key),
+        )?;
+
+        for server in &self.rendezvous_servers {
+            write!(f, " {}", server)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for DS {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "60485 5 1 2BB183AF5F22588179A53B0A98631FAD1A292118"
+        write!(
+            f,
+            "{key_tag} {algorithm} {digest_type} {digest}",
+            key_tag = self.key_tag,
+            algorithm = self.algorithm,
+            digest_type = self.digest_type,
+            digest = hex::encode_upper(&self.digest),
+        )
+    }
+}
+
+impl fmt::Display for TLSA {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "3 1 1 D2ABDE240D7CD3EE6B4B28C54DF034B9"
+        write!(
+            f,
+            "{usage} {selector} {matching_type} {data}",
+            usage = self.usage,
+            selector = self.selector,
+            matching_type = self.matching_type,
+            data = hex::encode_upper(&self.data),
+        )
+    }
+}
+
+impl fmt::Display for KEY {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "256 3 5 AQPSKmyn..."
+        write!(
+            f,
+            "{flags} {protocol} {algorithm} {public_key}",
+            flags = self.flags,
+            protocol = self.protocol,
+            algorithm = self.algorithm,
+            public_key = base64::encode(&self.public_key),
+        )
+    }
+}
+
+impl fmt::Display for SIG {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "A 5 3 86400 20030322173103 20030220173103 2642 example.com. oJB1W6WN..."
+        write!(
+            f,
+            "{type_covered} {algorithm} {labels} {original_ttl} {expiration} {inception} {key_tag} {signer_name} {signature}",
+            type_covered = self.type_covered,
+            algorithm = self.algorithm,
+            labels = self.labels,
+            original_ttl = self.original_ttl,
+            expiration = Self::time_to_string(self.expiration),
+            inception = Self::time_to_string(self.inception),
+            key_tag = self.key_tag,
+            signer_name = self.signer_name,
+            signature = base64::encode(&self.signature),
+        )
+    }
+}
+
+/// Writes the size of the data, followed by the data in base64 unless it is
+/// empty, as used by TKEY and TSIG.
+fn write_sized_data(f: &mut fmt::Formatter, data: &[u8]) -> fmt::Result {
+    write!(f, "{}", data.len())?;
+    if !data.is_empty() {
+        write!(f, " {}", base64::encode(data))?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for TKEY {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "gss-tsig. 20231114221320 20231114231320 3 NOERROR 4 AQIDBA== 0"
+        write!(
+            f,
+            "{algorithm} {inception} {expiration} {mode} {error} ",
+            algorithm = self.algorithm,
+            inception = SIG::time_to_string(self.inception),
+            expiration = SIG::time_to_string(self.expiration),
+            mode = self.mode,
+            error = TSIG::error_to_string(self.error),
+        )?;
+        write_sized_data(f, &self.key)?;
+        write!(f, " ")?;
+        write_sized_data(f, &self.other_data)
+    }
+}
+
+impl fmt::Display for TSIG {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "hmac-sha256. 1700000000 300 32 AAAA...= 12345 NOERROR 0"
+        write!(
+            f,
+            "{algorithm} {time_signed} {fudge} ",
+            algorithm = self.algorithm,
+            time_signed = self.time_signed,
+            fudge = self.fudge,
+        )?;
+        write_sized_data(f, &self.mac)?;
+        write!(
+            f,
+            " {original_id} {error} ",
+            original_id = self.original_id,
+            error = TSIG::error_to_string(self.error),
+        )?;
+        write_sized_data(f, &self.other_data)
+    }
+}
+
+impl fmt::Display for DOA {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "0 1 2 \"\" aHR0cHM6Ly93d3cuaXNjLm9yZy8="
+        write!(
+            f,
+            "{enterprise} {doa_type} {location} \"{media_type}\" ",
+            enterprise = self.enterprise,
+            doa_type = self.doa_type,
+            location = self.location,
+            media_type = self.media_type,
+        )?;
+
+        // Empty data is written as "-".
+        if self.data.is_empty() {
+            write!(f, "-")
+        } else {
+            write!(f, "{}", base64::encode(&self.data))
+        }
+    }
+}
+
+impl fmt::Display for SVCB {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "1 . alpn=h2,h3 port=443"
+        write!(
+            f,
+            "{priority} {target}",
+            priority = self.priority,
+            target = self.target
+        )?;
+        for param in &self.params {
+            write!(f, " {}", param)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for SvcParam {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "alpn=h2,h3"
+        fn join<T: ToString>(items: &[T]) -> String {
+            items
+                .iter()
+                .map(|item| item.to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+        }
+
+        // Escapes a value, so it can be written without quotes.
+        fn escape(value: &[u8], comma: bool) -> String {
+            let mut output = String::new();
+            for &b in value {
+                match b {
+                    b'"' | b'\\' | b';' | b'(' | b')' => {
+                        output.push_str(&format!("\\{}", b as char))
+                    }
+                    b',' if comma => output.push_str("\\,"),
+                    0x21..=0x7e => output.push(b as char),
+                    _ => output.push_str(&format!("\\{:03}", b)),
+                }
+            }
+            output
+        }
+
+        let name = Self::key_to_name(self.key());
+        match self {
+            SvcParam::Mandatory(keys) => {
+                let names: Vec<String> = keys.iter().map(|key| Self::key_to_name(*key)).collect();
+                write!(f, "{}={}", name, names.join(","))
+            }
+            SvcParam::Alpn(ids) => {
+                let ids: Vec<String> = ids.iter().map(|id| escape(id.as_bytes(), true)).collect();
+                write!(f, "{}={}", name, ids.join(","))
+            }
+            SvcParam::NoDefaultAlpn => write!(f, "{}", name),
+            SvcParam::Port(port) => write!(f, "{}={}", name, port),
+            SvcParam::Ipv4Hint(hints) => write!(f, "{}={}", name, join(hints)),
+            SvcParam::Ech(ech) => write!(f, "{}={}", name, base64::encode(ech)),
+            SvcParam::Ipv6Hint(hints) => write!(f, "{}={}", name, join(hints)),
+            SvcParam::Unknown(_, value) if value.is_empty() => write!(f, "{}", name),
+            SvcParam::Unknown(_, value) => write!(f, "{}={}", name, escape(value, false)),
+        }
+    }
+}
+
+impl fmt::Display for APL {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "1:192.0.2.0/24 !1:192.0.2.128/25"
+        let output = self
+            .0
+            .iter()
+            .map(|item| item.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        write!(f, "{}", output)
+    }
+}
+
+impl fmt::Display for APLItem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "!1:192.0.2.128/25"
+        write!(
+            f,
+            "{negation}{family}:{address}/{prefix}",
+            negation = if self.negation { "!" } else { "" },
+            family = self.family(),
+            address = self.address,
+            prefix = self.prefix,
+        )
+    }
+}
+
 impl fmt::Display for TXT {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Escapes a single byte, so it can be written within quotes.
+        fn escape(output: &mut String, b: u8) {
+            match b {
+                b'"' | b'\\' => output.push_str(&format!("\\{}", b as char)),
+                0x20..=0x7e => output.push(b as char),
+                _ => output.push_str(&format!("\\{:03}", b)),
+            }
+        }
+
         let output = self.0
             .iter()
             .map(|txt| {
+                let mut output = "\"".to_owned();
                 match std::str::from_utf8(txt) {
-                    // TODO Escape the " character (and maybe others)
-                    Ok(txt) => "\"".to_owned() + txt + "\"",
+                    // Valid UTF-8 is kept as is, except for any control characters.
+                    Ok(txt) => {
+                        for c in txt.chars() {
+                            if c.is_ascii() {
+                                escape(&mut output, c as u8);
+                            } else if c.is_control() {
+                                let mut buf = [0; 4];
+                                for b in c.encode_utf8(&mut buf).bytes() {
+                                    escape(&mut output, b);
+                                }
+                            } else {
+                                output.push(c);
+                            }
+                        }
+                    }
 
-                    // TODO Try our best to convert this to valid UTF, and use
-                    // https://doc.rust-lang.org/std/str/struct.Utf8Error.html to show what we can.
-                    Err(_e) => "invalid".to_string(),
+                    // Otherwise binary data, so escape anything not printable.
+                    Err(_e) => txt.iter().for_each(|b| escape(&mut output, *b)),
                 }
+                output + "\""
             })
             .collect::<Vec<String>>()
             .join(" ");
@@ -261,8 +792,34 @@ mod tests {
     use crate::TXT;
     use crate::Resource;
     use crate::MX;
+    use crate::AFSDB;
+    use crate::WKS;
+    use crate::GPOS;
+    use crate::ISDN;
+    use crate::ATMA;
+    use crate::RT;
     use crate::SOA;
     use crate::SRV;
+    use crate::URI;
+    use crate::APL;
+    use crate::APLItem;
+    use crate::CERT;
+    use crate::DOA;
+    use crate::CSYNC;
+    use crate::DS;
+    use crate::TLSA;
+    use crate::HIP;
+    use crate::KEY;
+    use crate::L32;
+    use crate::L64;
+    use crate::LP;
+    use crate::NID;
+    use crate::SIG;
+    use crate::TKEY;
+    use crate::TSIG;
+    use crate::SvcParam;
+    use crate::SVCB;
+    use crate::Type;
     use core::time::Duration;
     use pretty_assertions::assert_eq;
 
@@ -307,6 +864,67 @@ mod tests {
                     }),
                     "10 aspmx.l.google.com.",
                 ),
+                (
+                    Resource::WKS(WKS {
+                        address: "192.0.2.1".parse().unwrap(),
+                        protocol: 6,
+                        ports: vec![25, 80],
+                    }),
+                    "192.0.2.1 6 25 80",
+                ),
+                (
+                    Resource::AFSDB(AFSDB {
+                        subtype: 1,
+                        hostname: "afsdb.example.com.".to_string(),
+                    }),
+                    "1 afsdb.example.com.",
+                ),
+                (
+                    Resource::RT(RT {
+                        preference: 10,
+                        host: "relay.example.com.".to_string(),
+                    }),
+                    "10 relay.example.com.",
+                ),
+                (Resource::X25("311061700956".to_string()), "\"311061700956\""),
+                (
+                    Resource::ISDN(ISDN {
+                        address: "150862028003217".to_string(),
+                        subaddress: Some("004".to_string()),
+                    }),
+                    "\"150862028003217\" \"004\"",
+                ),
+                (
+                    Resource::ISDN(ISDN {
+                        address: "150862028003217".to_string(),
+                        subaddress: None,
+                    }),
+                    "\"150862028003217\"",
+                ),
+                (
+                    Resource::ATMA(ATMA::AESA(vec![0x39, 0x24, 0x6f, 0x00])),
+                    "39246f00",
+                ),
+                (
+                    Resource::ATMA(ATMA::E164("3584001234567".to_string())),
+                    "+3584001234567",
+                ),
+                (
+                    Resource::NSAP(vec![0x47, 0x00, 0x05, 0x80, 0x00, 0x5a, 0x00]),
+                    "0x47000580005a00",
+                ),
+                (
+                    Resource::NSAP_PTR("host.example.com.".to_string()),
+                    "host.example.com.",
+                ),
+                (
+                    Resource::GPOS(GPOS {
+                        longitude: "116.8652".to_string(),
+                        latitude: "-32.6882".to_string(),
+                        altitude: "10.0".to_string(),
+                    }),
+                    "\"116.8652\" \"-32.6882\" \"10.0\"",
+                ),
                 (
                     Resource::SRV(SRV {
                         priority: 5,
@@ -316,6 +934,277 @@ mod tests {
                     }),
                     "5 0 389 ldap.google.com.",
                 ),
+                (
+                    Resource::URI(URI {
+                        priority: 10,
+                        weight: 1,
+                        target: "ftp://ftp1.example.com/public".to_string(),
+                    }),
+                    "10 1 \"ftp://ftp1.example.com/public\"",
+                ),
+                (
+                    Resource::URI(URI {
+                        priority: 1,
+                        weight: 0,
+                        target: "https://example.com/a \"path\"\\".to_string(),
+                    }),
+                    "1 0 \"https://example.com/a \\\"path\\\"\\\\\"",
+                ),
+                (
+                    Resource::APL(APL(vec![
+                        APLItem {
+                            negation: false,
+                            address: "192.0.2.0".parse().unwrap(),
+                            prefix: 24,
+                        },
+                        APLItem {
+                            negation: true,
+                            address: "2001:db8::".parse().unwrap(),
+                            prefix: 32,
+                        },
+                    ])),
+                    "1:192.0.2.0/24 !2:2001:db8::/32",
+                ),
+                (
+                    Resource::CERT(CERT {
+                        cert_type: 1,
+                        key_tag: 12345,
+                        algorithm: 8,
+                        certificate: b"certificate".to_vec(),
+                    }),
+                    "PKIX 12345 8 Y2VydGlmaWNhdGU=",
+                ),
+                (
+                    Resource::CERT(CERT {
+                        cert_type: 100,
+                        key_tag: 0,
+                        algorithm: 0,
+                        certificate: vec![0, 1, 2],
+                    }),
+                    "100 0 0 AAEC",
+                ),
+                (
+                    // Example from https://datatracker.ietf.org/doc/html/rfc4701#section-3.6
+                    Resource::DHCID(base64::decode("AAIBY2/AuCccgoJbsaxcQc9TUapptP69lOjxfNuVAA2kjEA=").unwrap()),
+                    "AAIBY2/AuCccgoJbsaxcQc9TUapptP69lOjxfNuVAA2kjEA=",
+                ),
+                (
+                    Resource::OPENPGPKEY(base64::decode("mQINBFVHm5sBEACZ3nUbh6nW3Jv1").unwrap()),
+                    "mQINBFVHm5sBEACZ3nUbh6nW3Jv1",
+                ),
+                (
+                    // Examples from https://datatracker.ietf.org/doc/html/rfc7043#section-3.2
+                    Resource::EUI48([0x00, 0x00, 0x5e, 0x00, 0x53, 0x2a]),
+                    "00-00-5e-00-53-2a",
+                ),
+                (
+                    Resource::EUI64([0x00, 0x00, 0x5e, 0xef, 0x10, 0x00, 0x00, 0x2a]),
+                    "00-00-5e-ef-10-00-00-2a",
+                ),
+                (
+                    Resource::CSYNC(CSYNC {
+                        serial: 1278700841,
+                        flags: 3,
+                        types: vec![Type::A, Type::NS, Type::AAAA],
+                    }),
+                    "1278700841 3 A NS AAAA",
+                ),
+                (
+                    // Example from https://datatracker.ietf.org/doc/html/rfc8005#section-6
+                    Resource::HIP(HIP {
+                        algorithm: 2,
+                        hit: hex::decode("200100107B1A74DF365639CC39F1D578").unwrap(),
+                        public_key: base64::decode("AwEAAbdxyhNuSutc5EMzxTs9LBPCIkOFH8cIvM4p9+LrV4e19WzK00+CI6zBCQTdtWsuxKbWIy87UOoJTwkUs7lBu+Upr1gsNrut79ryra+bSRGQb1slImA8YVJyuIDsj7kwzG7jnERNqnWxZ48AWkskmdHaVDP4BcelrTI3rMXdXF5D").unwrap(),
+                        rendezvous_servers: vec!["rvs1.example.com.".to_string(), "rvs2.example.com.".to_string()],
+                    }),
+                    "2 200100107B1A74DF365639CC39F1D578 AwEAAbdxyhNuSutc5EMzxTs9LBPCIkOFH8cIvM4p9+LrV4e19WzK00+CI6zBCQTdtWsuxKbWIy87UOoJTwkUs7lBu+Upr1gsNrut79ryra+bSRGQb1slImA8YVJyuIDsj7kwzG7jnERNqnWxZ48AWkskmdHaVDP4BcelrTI3rMXdXF5D rvs1.example.com. rvs2.example.com.",
+                ),
+                (
+                    Resource::HIP(HIP {
+                        algorithm: 2,
+                        hit: vec![0x20, 0x01],
+                        public_key: vec![0, 1, 2],
+                        rendezvous_servers: vec![],
+                    }),
+                    "2 2001 AAEC",
+                ),
+                (
+                    Resource::CDS(DS {
+                        key_tag: 60485,
+                        algorithm: 5,
+                        digest_type: 1,
+                        digest: vec![0x2b, 0xb1, 0x83, 0xaf, 0x5f, 0x22, 0x58, 0x81],
+                    }),
+                    "60485 5 1 2BB183AF5F225881",
+                ),
+                (
+                    Resource::SMIMEA(TLSA {
+                        usage: 3,
+                        selector: 1,
+                        matching_type: 1,
+                        data: vec![0xd2, 0xab, 0xde, 0x24, 0x0d, 0x7c, 0xd3, 0xee],
+                    }),
+                    "3 1 1 D2ABDE240D7CD3EE",
+                ),
+                (
+                    // The special "delete" records from rfc8078.
+                    Resource::CDS(DS {
+                        key_tag: 0,
+                        algorithm: 0,
+                        digest_type: 0,
+                        digest: vec![0],
+                    }),
+                    "0 0 0 00",
+                ),
+                (
+                    Resource::CDNSKEY(KEY {
+                        flags: 0,
+                        protocol: 3,
+                        algorithm: 0,
+                        public_key: vec![0],
+                    }),
+                    "0 3 0 AA==",
+                ),
+                (
+                    // Examples from https://datatracker.ietf.org/doc/html/rfc6742#section-3
+                    Resource::NID(NID {
+                        preference: 10,
+                        node_id: 0x0014_4fff_ff20_ee64,
+                    }),
+                    "10 0014:4fff:ff20:ee64",
+                ),
+                (
+                    Resource::L32(L32 {
+                        preference: 10,
+                        locator: "10.1.2.0".parse().unwrap(),
+                    }),
+                    "10 10.1.2.0",
+                ),
+                (
+                    Resource::L64(L64 {
+                        preference: 10,
+                        locator: 0x2001_0db8_1140_1000,
+                    }),
+                    "10 2001:0db8:1140:1000",
+                ),
+                (
+                    Resource::LP(LP {
+                        preference: 10,
+                        fqdn: "l64-subnet1.example.com.".to_string(),
+                    }),
+                    "10 l64-subnet1.example.com.",
+                ),
+                (
+                    Resource::KEY(KEY {
+                        flags: 256,
+                        protocol: 3,
+                        algorithm: 1,
+                        public_key: b"public key".to_vec(),
+                    }),
+                    "256 3 1 cHVibGljIGtleQ==",
+                ),
+                (
+                    Resource::SIG(SIG {
+                        type_covered: Type::A,
+                        algorithm: 1,
+                        labels: 3,
+                        original_ttl: 86400,
+                        expiration: 1048354263,
+                        inception: 1045762263,
+                        key_tag: 2642,
+                        signer_name: "example.com.".to_string(),
+                        signature: b"signature".to_vec(),
+                    }),
+                    "A 1 3 86400 20030322173103 20030220173103 2642 example.com. c2lnbmF0dXJl",
+                ),
+                (
+                    Resource::DOA(DOA {
+                        enterprise: 0,
+                        doa_type: 1,
+                        location: 2,
+                        media_type: "".to_string(),
+                        data: b"https://www.iana.org/".to_vec(),
+                    }),
+                    "0 1 2 \"\" aHR0cHM6Ly93d3cuaWFuYS5vcmcv",
+                ),
+                (
+                    Resource::DOA(DOA {
+                        enterprise: 1234,
+                        doa_type: 5,
+                        location: 1,
+                        media_type: "image/png".to_string(),
+                        data: vec![],
+                    }),
+                    "1234 5 1 \"image/png\" -",
+                ),
+                (
+                    Resource::TKEY(TKEY {
+                        algorithm: "gss-tsig.".to_string(),
+                        inception: 1700000000,
+                        expiration: 1700003600,
+                        mode: 3,
+                        error: 0,
+                        key: vec![1, 2, 3, 4],
+                        other_data: vec![],
+                    }),
+                    "gss-tsig. 20231114221320 20231114231320 3 NOERROR 4 AQIDBA== 0",
+                ),
+                (
+                    Resource::TSIG(TSIG {
+                        algorithm: "hmac-sha256.".to_string(),
+                        time_signed: 1700000000,
+                        fudge: 300,
+                        mac: (0..32).collect(),
+                        original_id: 12345,
+                        error: 0,
+                        other_data: vec![],
+                    }),
+                    "hmac-sha256. 1700000000 300 32 AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8= 12345 NOERROR 0",
+                ),
+                (
+                    // A BADTIME error carries the server's time as the other data.
+                    Resource::TSIG(TSIG {
+                        algorithm: "hmac-sha256.".to_string(),
+                        time_signed: 1700000000,
+                        fudge: 300,
+                        mac: vec![],
+                        original_id: 1,
+                        error: 18,
+                        other_data: vec![0, 0, 0x65, 0x53, 0xf1, 0x00],
+                    }),
+                    "hmac-sha256. 1700000000 300 0 1 BADTIME 6 AABlU/EA",
+                ),
+                (
+                    Resource::AVC(TXT::from("app-name:WOLFGANG|app-class:OAM|business=yes")),
+                    "\"app-name:WOLFGANG|app-class:OAM|business=yes\"",
+                ),
+                (
+                    Resource::HTTPS(SVCB {
+                        priority: 1,
+                        target: ".".to_string(),
+                        params: vec![
+                            SvcParam::Alpn(vec!["h2".to_string(), "h3".to_string()]),
+                            SvcParam::Port(8443),
+                            SvcParam::Ipv4Hint(vec!["192.0.2.1".parse().unwrap(), "192.0.2.2".parse().unwrap()]),
+                            SvcParam::Ipv6Hint(vec!["2001:db8::1".parse().unwrap()]),
+                        ],
+                    }),
+                    "1 . alpn=h2,h3 port=8443 ipv4hint=192.0.2.1,192.0.2.2 ipv6hint=2001:db8::1",
+                ),
+                (
+                    Resource::SVCB(SVCB {
+                        priority: 16,
+                        target: "foo.example.org.".to_string(),
+                        params: vec![
+                            SvcParam::Mandatory(vec![1, 4]),
+                            SvcParam::Alpn(vec!["h2".to_string(), "h3,19".to_string()]),
+                            SvcParam::NoDefaultAlpn,
+                            SvcParam::Ech(b"ech".to_vec()),
+                            SvcParam::Unknown(667, b"hello world".to_vec()),
+                        ],
+                    }),
+                    "16 foo.example.org. mandatory=alpn,ipv4hint alpn=h2,h3\\,19 no-default-alpn ech=ZWNo key667=hello\\032world",
+                ),
                 (
                     Resource::TXT(TXT::from("v=spf1 include:_spf.google.com ~all")),
                     "\"v=spf1 include:_spf.google.com ~all\"",
@@ -328,6 +1217,15 @@ mod tests {
                     ][..])),
                     "\"k=rsa;  p=MIGfMA0GCSqGSIb3DQEBAQUAA4GNADCBiQKBgQDrEee0Ri4Juz+QfiWYui/E9UGSXau/2P8LjnTD8V4Unn+2FAZVGE3kL23bzeoULYv4PeleB3gfm\" \"JiDJOKU3Ns5L4KJAUUHjFwDebt0NP+sBK0VKeTATL2Yr/S3bT/xhy+1xtj4RkdV7fVxTn56Lb4udUnwuxK4V5b5PdOKj/+XcwIDAQAB; n=A 1024 bit key;\"",
                 ),
+                (
+                    // Quotes, and any binary data, are escaped.
+                    Resource::TXT(TXT(vec![
+                        "a\u{e9}b".as_bytes().to_vec(),
+                        b"say \"hi\"".to_vec(),
+                        vec![0xff, 0x00],
+                    ])),
+                    "\"a\u{e9}b\" \"say \\\"hi\\\"\" \"\\255\\000\"",
+                ),
             ]
         };
     }
@@ -337,6 +1235,45 @@ mod tests {
         for (resource, display) in (*DISPLAY_TESTS).iter() {
             assert_eq!(format!("{}", resource), *display);
         }
+
+        // Unknown types are written in the generic form, see rfc3597 section 5.
+        assert_eq!(
+            format!("{}", Resource::Unknown(65280, vec![0xab, 0xcd])),
+            "\\# 2 abcd"
+        );
+        assert_eq!(format!("{}", Resource::Unknown(65280, vec![])), "\\# 0");
+    }
+
+    #[test]
+    fn test_display_alternate() {
+        let soa = Resource::SOA(SOA {
+            mname: "ns1.google.com.".to_string(),
+            rname: "dns-admin@google.com.".to_string(),
+            serial: 379031418,
+            refresh: Duration::from_secs(900),
+            retry: Duration::from_secs(900),
+            expire: Duration::from_secs(1800),
+            minimum: Duration::from_secs(60),
+        });
+
+        assert_eq!(
+            format!("{:#}", soa),
+            "ns1.google.com. dns-admin.google.com. (
+    379031418 ; serial
+    900 ; refresh
+    900 ; retry
+    1800 ; expire
+    60 ; minimum
+)"
+        );
+
+        // The other types are the same in either form.
+        let mx = Resource::MX(MX {
+            preference: 10,
+            exchange: "mail.example.com.".to_string(),
+        });
+        assert_eq!(format!("{:#}", mx), "10 mail.example.com.");
+        assert_eq!(format!("{:#}", Resource::A("192.0.2.1".parse().unwrap())), "192.0.2.1");
     }
 
     #[test]
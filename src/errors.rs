@@ -73,3 +73,42 @@ pub enum ParseError {
     #[error("invalid rname email address: '{0}'")]
     InvalidRname(String),
 }
+
+/// Problems with the timers in a SOA record, as returned by [`SOA::validate`].
+///
+/// [`SOA::validate`]: crate::SOA::validate
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum SOAError {
+    #[error("refresh ({refresh}s) must be greater than retry ({retry}s)")]
+    RetryTooLarge { refresh: u64, retry: u64 },
+
+    #[error("expire ({expire}s) must be greater than refresh + retry ({refresh_retry}s)")]
+    ExpireTooSmall { expire: u64, refresh_retry: u64 },
+}
+
+/// Problems with a domain name, as returned by [`validate_domain`].
+///
+/// [`validate_domain`]: crate::util::validate_domain
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum NameError {
+    #[error("domain name is empty")]
+    Empty,
+
+    #[error("domain name is {0} bytes long, the limit is 255")]
+    TooLong(usize),
+
+    #[error("domain name contains an empty label")]
+    EmptyLabel,
+
+    #[error("label '{0}' is {} bytes long, the limit is 63", .0.len())]
+    LabelTooLong(String),
+
+    #[error("label '{label}' contains the invalid character '{ch}'")]
+    InvalidCharacter { label: String, ch: char },
+
+    #[error("label '{0}' may not start or end with a hyphen")]
+    HyphenPlacement(String),
+
+    #[error("wildcard '*' is only allowed as the whole left most label")]
+    Wildcard,
+}
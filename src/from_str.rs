@@ -5,12 +5,40 @@ use crate::TXT;
 use crate::Resource;
 use crate::Type;
 use crate::MX;
+use crate::AFSDB;
+use crate::WKS;
+use crate::RT;
 use crate::SOA;
+use crate::APLItem;
+use crate::APL;
+use crate::ATMA;
+use crate::CERT;
+use crate::CSYNC;
+use crate::DOA;
+use crate::DS;
+use crate::TLSA;
+use crate::GPOS;
+use crate::ISDN;
+use crate::HIP;
+use crate::KEY;
+use crate::L32;
+use crate::L64;
+use crate::LP;
+use crate::NID;
+use crate::NSAP;
+use crate::SIG;
+use crate::TKEY;
+use crate::TSIG;
+use crate::X25;
 use crate::SRV;
+use crate::SvcParam;
+use crate::SVCB;
+use crate::URI;
 use core::num::ParseIntError;
 use core::str::FromStr;
 use regex::Regex;
 use std::net::AddrParseError;
+use std::net::IpAddr;
 use std::time::Duration;
 use thiserror::Error;
 
@@ -27,6 +55,12 @@ pub enum FromStrError {
 
     #[error(transparent)]
     AddrParseError(#[from] AddrParseError),
+
+    #[error(transparent)]
+    Base64Error(#[from] base64::DecodeError),
+
+    #[error(transparent)]
+    HexError(#[from] hex::FromHexError),
 }
 
 impl Resource {
@@ -44,10 +78,51 @@ impl Resource {
 
             // Complex types
             Type::MX => Resource::MX(s.parse()?),
+            Type::WKS => Resource::WKS(s.parse()?),
+            Type::AFSDB => Resource::AFSDB(s.parse()?),
+            Type::X25 => Resource::X25(parse_x25(s)?),
+            Type::ISDN => Resource::ISDN(s.parse()?),
+            Type::RT => Resource::RT(s.parse()?),
+            Type::NSAP => Resource::NSAP(parse_nsap(s)?),
+            Type::NSAP_PTR => Resource::NSAP_PTR(s.to_string()),
+            Type::GPOS => Resource::GPOS(s.parse()?),
             Type::SRV => Resource::SRV(s.parse()?),
+            Type::ATMA => Resource::ATMA(s.parse()?),
             Type::SOA => Resource::SOA(s.parse()?),
             Type::SPF => Resource::SPF(s.parse()?),
+            Type::AVC => Resource::AVC(s.parse()?),
             Type::TXT => Resource::TXT(s.parse()?),
+            Type::URI => Resource::URI(s.parse()?),
+            Type::APL => Resource::APL(s.parse()?),
+            Type::CERT => Resource::CERT(s.parse()?),
+            Type::CSYNC => Resource::CSYNC(s.parse()?),
+            Type::SVCB => Resource::SVCB(s.parse()?),
+            Type::HTTPS => Resource::HTTPS(s.parse()?),
+            Type::CDS => Resource::CDS(s.parse()?),
+            Type::SMIMEA => Resource::SMIMEA(s.parse()?),
+            Type::CDNSKEY => Resource::CDNSKEY(s.parse()?),
+            Type::NID => Resource::NID(s.parse()?),
+            Type::L32 => Resource::L32(s.parse()?),
+            Type::L64 => Resource::L64(s.parse()?),
+            Type::LP => Resource::LP(s.parse()?),
+            Type::KEY => Resource::KEY(s.parse()?),
+            Type::SIG => Resource::SIG(s.parse()?),
+            Type::DOA => Resource::DOA(s.parse()?),
+            Type::TKEY => Resource::TKEY(s.parse()?),
+            Type::TSIG => Resource::TSIG(s.parse()?),
+            Type::EUI48 => Resource::EUI48(parse_eui(s)?),
+            Type::EUI64 => Resource::EUI64(parse_eui(s)?),
+            Type::DHCID => {
+                // The digest may be split by whitespace.
+                let dhcid: String = s.split_whitespace().collect();
+                Resource::DHCID(base64::decode(dhcid)?)
+            }
+            Type::HIP => Resource::HIP(s.parse()?),
+            Type::OPENPGPKEY => {
+                // The key may be split by whitespace.
+                let key: String = s.split_whitespace().collect();
+                Resource::OPENPGPKEY(base64::decode(key)?)
+            }
 
             // This should never appear in a answer record unless we have invalid data.
             Type::Reserved | Type::OPT | Type::ANY => return Err(FromStrError::UnsupportedType),
@@ -107,6 +182,156 @@ impl FromStr for MX {
     }
 }
 
+impl FromStr for WKS {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "192.0.2.1 6 25 80"
+            // "{address} {protocol} {ports}",
+            static ref RE: Regex = Regex::new(r"^(\S+) (\w+)((?: \w+)*)$").unwrap();
+        }
+        if let Some(caps) = RE.captures(s) {
+            let protocol = match Self::protocol_from_name(&caps[2]) {
+                Some(protocol) => protocol,
+                None => return Err(FromStrError::InvalidFormat),
+            };
+
+            let mut ports = Vec::new();
+            for service in caps[3].split_whitespace() {
+                match Self::port_from_name(service) {
+                    Some(port) => ports.push(port),
+                    None => return Err(FromStrError::InvalidFormat),
+                }
+            }
+            ports.sort_unstable();
+            ports.dedup();
+
+            Ok(WKS {
+                address: caps[1].parse()?,
+                protocol,
+                ports,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for AFSDB {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "1 afsdb.example.com."
+            // "{subtype} {hostname}",
+            static ref RE: Regex = Regex::new(r"^(\d+) (.+)$").unwrap();
+        }
+        if let Some(caps) = RE.captures(s) {
+            Ok(AFSDB {
+                subtype: caps[1].parse()?,
+                hostname: caps[2].to_string(),
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for RT {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "10 relay.example.com."
+            // "{preference} {host}",
+            static ref RE: Regex = Regex::new(r"^(\d+) (.+)$").unwrap();
+        }
+        if let Some(caps) = RE.captures(s) {
+            Ok(RT {
+                preference: caps[1].parse()?,
+                host: caps[2].to_string(),
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+/// Parses a X25 address, written as a quoted string, for example "\"311061700956\"".
+pub(crate) fn parse_x25(s: &str) -> Result<X25, FromStrError> {
+    match s.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+        Some(address) if !address.contains('"') => Ok(address.to_string()),
+        _ => Err(FromStrError::InvalidFormat),
+    }
+}
+
+impl FromStr for ISDN {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "\"150862028003217\" \"004\""
+            // "\"{address}\" \"{subaddress}\"",
+            static ref RE: Regex = Regex::new(r#"^"([^"]*)"(?: "([^"]*)")?$"#).unwrap();
+        }
+        if let Some(caps) = RE.captures(s) {
+            Ok(ISDN {
+                address: caps[1].to_string(),
+                subaddress: caps.get(2).map(|m| m.as_str().to_string()),
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for ATMA {
+    type Err = FromStrError;
+
+    /// Parses a E.164 number, such as "+358.400.123456", or a AESA in hex,
+    /// such as "39.246f.000e7c9c0312.0001.0001.000012345678.00". In both the
+    /// dots are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(digits) = s.strip_prefix('+') {
+            let digits = digits.replace('.', "");
+            if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(FromStrError::InvalidFormat);
+            }
+
+            return Ok(ATMA::E164(digits));
+        }
+
+        let digits = s.replace('.', "");
+        if digits.is_empty() {
+            return Err(FromStrError::InvalidFormat);
+        }
+
+        Ok(ATMA::AESA(hex::decode(digits)?))
+    }
+}
+
+impl FromStr for GPOS {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "\"116.8652\" \"-32.6882\" \"10.0\""
+            // "\"{longitude}\" \"{latitude}\" \"{altitude}\"",
+            static ref RE: Regex = Regex::new(r#"^"([^"]*)" "([^"]*)" "([^"]*)"$"#).unwrap();
+        }
+        if let Some(caps) = RE.captures(s) {
+            Ok(GPOS {
+                longitude: caps[1].to_string(),
+                latitude: caps[2].to_string(),
+                altitude: caps[3].to_string(),
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
 impl FromStr for SRV {
     type Err = FromStrError;
 
@@ -129,32 +354,698 @@ impl FromStr for SRV {
     }
 }
 
+impl FromStr for URI {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "10 1 \"ftp://ftp1.example.com/public\""
+            // "{priority} {weight} \"{target}\"",
+            static ref RE: Regex = Regex::new(r#"^(\d+) (\d+) "((?:[^"\\]|\\.)*)"$"#).unwrap();
+        }
+        if let Some(caps) = RE.captures(s) {
+            let target = String::from_utf8(unescape(&caps[3])?)
+                .map_err(|_| FromStrError::InvalidFormat)?;
+
+            Ok(URI {
+                priority: caps[1].parse()?,
+                weight: caps[2].parse()?,
+                target,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for CSYNC {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "1278700841 3 A NS AAAA"
+            // "{serial} {flags} {types}",
+            static ref RE: Regex = Regex::new(r"^(\d+) (\d+)((?: \S+)*)$").unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            let types = caps[3]
+                .split_whitespace()
+                .map(|t| Type::from_str(t).map_err(|_| FromStrError::InvalidFormat))
+                .collect::<Result<Vec<Type>, _>>()?;
+
+            Ok(CSYNC {
+                serial: caps[1].parse()?,
+                flags: caps[2].parse()?,
+                types,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for NID {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "10 0014:4fff:ff20:ee64"
+            // "{preference} {node_id}",
+            static ref RE: Regex = Regex::new(r"^(\d+) (\S+)$").unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            Ok(NID {
+                preference: caps[1].parse()?,
+                node_id: L64::locator_from_str(&caps[2]).ok_or(FromStrError::InvalidFormat)?,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for L32 {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "10 10.1.2.0"
+            // "{preference} {locator}",
+            static ref RE: Regex = Regex::new(r"^(\d+) (\S+)$").unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            Ok(L32 {
+                preference: caps[1].parse()?,
+                locator: caps[2].parse()?,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for L64 {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "10 2001:0db8:1140:1000"
+            // "{preference} {locator}",
+            static ref RE: Regex = Regex::new(r"^(\d+) (\S+)$").unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            Ok(L64 {
+                preference: caps[1].parse()?,
+                locator: L64::locator_from_str(&caps[2]).ok_or(FromStrError::InvalidFormat)?,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for LP {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "10 l64-subnet1.example.com."
+            // "{preference} {fqdn}",
+            static ref RE: Regex = Regex::new(r"^(\d+) (.+)$").unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            Ok(LP {
+                preference: caps[1].parse()?,
+                fqdn: caps[2].to_string(),
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for HIP {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "2 200100107B1A74DF365639CC39F1D578 AwEAAbdx... rvs.example.com."
+            // "{algorithm} {hit} {public_key} {rendezvous_servers}",
+            static ref RE: Regex = Regex::new(r"^(\d+) ([[:xdigit:]]+) (\S+)((?: \S+)*)$").unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            Ok(HIP {
+                algorithm: caps[1].parse()?,
+                hit: hex::decode(&caps[2])?,
+                public_key: base64::decode(&caps[3])?,
+                rendezvous_servers: caps[4].split_whitespace().map(str::to_string).collect(),
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for DS {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "60485 5 1 2BB183AF5F22588179A53B0A98631FAD1A292118"
+            // "{key_tag} {algorithm} {digest_type} {digest}",
+            static ref RE: Regex = Regex::new(r"^(\d+) (\d+) (\d+) (.+)$").unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            // The digest may be split by whitespace.
+            let digest: String = caps[4].split_whitespace().collect();
+
+            Ok(DS {
+                key_tag: caps[1].parse()?,
+                algorithm: caps[2].parse()?,
+                digest_type: caps[3].parse()?,
+                digest: hex::decode(digest)?,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for TLSA {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "3 1 1 D2ABDE240D7CD3EE6B4B28C54DF034B9"
+            // "{usage} {selector} {matching_type} {data}",
+            static ref RE: Regex = Regex::new(r"^(\d+) (\d+) (\d+) (.+)$").unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            // The data may be split by whitespace.
+            let data: String = caps[4].split_whitespace().collect();
+
+            Ok(TLSA {
+                usage: caps[1].parse()?,
+                selector: caps[2].parse()?,
+                matching_type: caps[3].parse()?,
+                data: hex::decode(data)?,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for KEY {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "256 3 5 AQPSKmyn..."
+            // "{flags} {protocol} {algorithm} {public_key}",
+            static ref RE: Regex = Regex::new(r"^(\d+) (\d+) (\d+) (.+)$").unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            // The key may be split by whitespace.
+            let public_key: String = caps[4].split_whitespace().collect();
+
+            Ok(KEY {
+                flags: caps[1].parse()?,
+                protocol: caps[2].parse()?,
+                algorithm: caps[3].parse()?,
+                public_key: base64::decode(public_key)?,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for SIG {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "A 5 3 86400 20030322173103 20030220173103 2642 example.com. oJB1W6WN..."
+            // "{type_covered} {algorithm} {labels} {original_ttl} {expiration} {inception} {key_tag} {signer_name} {signature}",
+            static ref RE: Regex = Regex::new(r"^(\S+) (\d+) (\d+) (\d+) (\d+) (\d+) (\d+) (\S+) (.+)$").unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            let type_covered = match Type::from_str(&caps[1]) {
+                Ok(t) => t,
+                Err(_) => return Err(FromStrError::InvalidFormat),
+            };
+
+            let time = |s: &str| SIG::time_from_str(s).ok_or(FromStrError::InvalidFormat);
+
+            // The signature may be split by whitespace.
+            let signature: String = caps[9].split_whitespace().collect();
+
+            Ok(SIG {
+                type_covered,
+                algorithm: caps[2].parse()?,
+                labels: caps[3].parse()?,
+                original_ttl: caps[4].parse()?,
+                expiration: time(&caps[5])?,
+                inception: time(&caps[6])?,
+                key_tag: caps[7].parse()?,
+                signer_name: caps[8].to_string(),
+                signature: base64::decode(signature)?,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+/// Parses the size of some data, followed by the data in base64 unless the
+/// size is zero, as used by TKEY and TSIG. Returns the data, and the rest of
+/// the fields.
+fn parse_sized_data(s: &str) -> Result<(Vec<u8>, &str), FromStrError> {
+    let (size, rest) = s.split_once(' ').unwrap_or((s, ""));
+    let size: usize = size.parse()?;
+    if size == 0 {
+        return Ok((Vec::new(), rest));
+    }
+
+    let (data, rest) = rest.split_once(' ').unwrap_or((rest, ""));
+    let data = base64::decode(data)?;
+    if data.len() != size {
+        return Err(FromStrError::InvalidFormat);
+    }
+
+    Ok((data, rest))
+}
+
+impl FromStr for TKEY {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "gss-tsig. 20231114221320 20231114231320 3 NOERROR 4 AQIDBA== 0"
+            // "{algorithm} {inception} {expiration} {mode} {error} {key size} {key} {other size} {other data}",
+            static ref RE: Regex = Regex::new(r"^(\S+) (\d+) (\d+) (\d+) (\w+) (.+)$").unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            let time = |s: &str| SIG::time_from_str(s).ok_or(FromStrError::InvalidFormat);
+
+            let (key, rest) = parse_sized_data(&caps[6])?;
+            let (other_data, rest) = parse_sized_data(rest)?;
+            if !rest.is_empty() {
+                return Err(FromStrError::InvalidFormat);
+            }
+
+            Ok(TKEY {
+                algorithm: caps[1].to_string(),
+                inception: time(&caps[2])?,
+                expiration: time(&caps[3])?,
+                mode: caps[4].parse()?,
+                error: TSIG::error_from_str(&caps[5]).ok_or(FromStrError::InvalidFormat)?,
+                key,
+                other_data,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for TSIG {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "hmac-sha256. 1700000000 300 32 AAECAwQF...= 12345 NOERROR 0"
+            // "{algorithm} {time signed} {fudge} {mac size} {mac} {original id} {error} {other size} {other data}",
+            static ref RE: Regex = Regex::new(r"^(\S+) (\d+) (\d+) (.+)$").unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            let time_signed: u64 = caps[2].parse()?;
+            if time_signed > 0xffff_ffff_ffff {
+                return Err(FromStrError::InvalidFormat);
+            }
+
+            let (mac, rest) = parse_sized_data(&caps[4])?;
+            let (original_id, rest) = rest.split_once(' ').ok_or(FromStrError::InvalidFormat)?;
+            let (error, rest) = rest.split_once(' ').ok_or(FromStrError::InvalidFormat)?;
+            let (other_data, rest) = parse_sized_data(rest)?;
+            if !rest.is_empty() {
+                return Err(FromStrError::InvalidFormat);
+            }
+
+            Ok(TSIG {
+                algorithm: caps[1].to_string(),
+                time_signed,
+                fudge: caps[3].parse()?,
+                mac,
+                original_id: original_id.parse()?,
+                error: TSIG::error_from_str(error).ok_or(FromStrError::InvalidFormat)?,
+                other_data,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for DOA {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "0 1 2 \"\" aHR0cHM6Ly93d3cuaXNjLm9yZy8="
+            // "{enterprise} {doa_type} {location} \"{media_type}\" {data}",
+            static ref RE: Regex = Regex::new(r#"^(\d+) (\d+) (\d+) "([^"]*)" (.+)$"#).unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            // Empty data is written as "-", otherwise it may be split by whitespace.
+            let data = match &caps[5] {
+                "-" => Vec::new(),
+                data => base64::decode(data.split_whitespace().collect::<String>())?,
+            };
+
+            Ok(DOA {
+                enterprise: caps[1].parse()?,
+                doa_type: caps[2].parse()?,
+                location: caps[3].parse()?,
+                media_type: caps[4].to_string(),
+                data,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for SVCB {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "1 . alpn=h2,h3 port=443"
+            // "{priority} {target} {params}",
+            static ref RE: Regex = Regex::new(r"^(\d+) (\S+)(.*)$").unwrap();
+
+            // " {key}={value}" where the value is optional, and may be quoted.
+            static ref PARAM_RE: Regex = Regex::new(
+                r#"\s+([^\s=]+)(?:=(?:"((?:[^"\\]|\\.)*)"|((?:[^\s"\\]|\\.)*)))?"#
+            ).unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            let mut params = Vec::new();
+            let mut end = 0;
+            for param in PARAM_RE.captures_iter(&caps[3]) {
+                // Ensure nothing was skipped between the params.
+                let m = param.get(0).unwrap();
+                if m.start() != end {
+                    return Err(FromStrError::InvalidFormat);
+                }
+                end = m.end();
+
+                let value = param.get(2).or_else(|| param.get(3)).map(|m| m.as_str());
+                params.push(SvcParam::from_key_value(&param[1], value)?);
+            }
+
+            if end != caps[3].len() {
+                return Err(FromStrError::InvalidFormat);
+            }
+
+            Ok(SVCB {
+                priority: caps[1].parse()?,
+                target: caps[2].to_string(),
+                params,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl SvcParam {
+    /// Parses a parameter from its key and value (with any quotes removed),
+    /// for example "alpn" and "h2,h3".
+    pub(crate) fn from_key_value(key: &str, value: Option<&str>) -> Result<SvcParam, FromStrError> {
+        let key = match Self::key_from_name(key) {
+            Some(key) => key,
+            None => return Err(FromStrError::InvalidFormat),
+        };
+
+        // All the parameters, except no-default-alpn, and unknown keys, require a value.
+        let list = match value {
+            Some(value) => value,
+            None if key == 2 || key > 6 => "",
+            None => return Err(FromStrError::InvalidFormat),
+        };
+
+        Ok(match key {
+            0 => SvcParam::Mandatory(
+                unescape_list(list, true)?
+                    .iter()
+                    .map(|name| {
+                        std::str::from_utf8(name)
+                            .ok()
+                            .and_then(Self::key_from_name)
+                            .ok_or(FromStrError::InvalidFormat)
+                    })
+                    .collect::<Result<Vec<u16>, _>>()?,
+            ),
+            1 => SvcParam::Alpn(
+                unescape_list(list, true)?
+                    .into_iter()
+                    .map(|id| String::from_utf8(id).map_err(|_| FromStrError::InvalidFormat))
+                    .collect::<Result<Vec<String>, _>>()?,
+            ),
+            2 if value.is_none() => SvcParam::NoDefaultAlpn,
+            3 => SvcParam::Port(list.parse()?),
+            4 => SvcParam::Ipv4Hint(
+                list.split(',')
+                    .map(|ip| ip.parse())
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            5 => SvcParam::Ech(base64::decode(list)?),
+            6 => SvcParam::Ipv6Hint(
+                list.split(',')
+                    .map(|ip| ip.parse())
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            key if key > 6 => {
+                let mut value = unescape_list(list, false)?;
+                SvcParam::Unknown(key, value.pop().unwrap_or_default())
+            }
+            _ => return Err(FromStrError::InvalidFormat),
+        })
+    }
+}
+
+/// Parses a EUI48 or EUI64, written as hyphen separated pairs of hex digits,
+/// for example "00-00-5e-00-53-2a". See rfc7043 section 3.2.
+pub(crate) fn parse_eui<const N: usize>(s: &str) -> Result<[u8; N], FromStrError> {
+    let mut eui = [0_u8; N];
+
+    let mut groups = s.split('-');
+    for b in eui.iter_mut() {
+        match groups.next() {
+            Some(group) if group.len() == 2 && group.bytes().all(|c| c.is_ascii_hexdigit()) => {
+                *b = u8::from_str_radix(group, 16)?
+            }
+            _ => return Err(FromStrError::InvalidFormat),
+        }
+    }
+
+    if groups.next().is_some() {
+        return Err(FromStrError::InvalidFormat);
+    }
+
+    Ok(eui)
+}
+
+/// Parses a NSAP address, written as "0x" followed by hex digits, which may
+/// be separated by dots, for example "0x47.0005.80.005a00". See rfc1706
+/// section 5.
+pub(crate) fn parse_nsap(s: &str) -> Result<NSAP, FromStrError> {
+    let digits = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(digits) => digits.replace('.', ""),
+        None => return Err(FromStrError::InvalidFormat),
+    };
+
+    if digits.is_empty() {
+        return Err(FromStrError::InvalidFormat);
+    }
+
+    Ok(hex::decode(digits)?)
+}
+
+/// Unescapes a single character-string, where "\\x" is the character x,
+/// and "\\DDD" is the byte with decimal value DDD.
+pub(crate) fn unescape(s: &str) -> Result<Vec<u8>, FromStrError> {
+    let mut items = unescape_list(s, false)?;
+    Ok(items.remove(0))
+}
+
+/// Unescapes a value, where "\\x" is the character x, and "\\DDD" is the
+/// byte with decimal value DDD. If `comma` is true the value is split into
+/// a list on any unescaped commas.
+fn unescape_list(s: &str, comma: bool) -> Result<Vec<Vec<u8>>, FromStrError> {
+    let mut items = Vec::new();
+    let mut item = Vec::new();
+
+    let mut bytes = s.bytes();
+    while let Some(b) = bytes.next() {
+        match b {
+            b'\\' => match bytes.next() {
+                Some(d) if d.is_ascii_digit() => {
+                    let mut value: u16 = 0;
+                    for digit in [Some(d), bytes.next(), bytes.next()] {
+                        match digit {
+                            Some(d) if d.is_ascii_digit() => {
+                                value = value * 10 + u16::from(d - b'0')
+                            }
+                            _ => return Err(FromStrError::InvalidFormat),
+                        }
+                    }
+
+                    if value > 255 {
+                        return Err(FromStrError::InvalidFormat);
+                    }
+                    item.push(value as u8);
+                }
+                Some(c) => item.push(c),
+                None => return Err(FromStrError::InvalidFormat),
+            },
+            b',' if comma => items.push(std::mem::take(&mut item)),
+            _ => item.push(b),
+        }
+    }
+    items.push(item);
+
+    Ok(items)
+}
+
+impl FromStr for CERT {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "PKIX 12345 8 MIIB..."
+            // "{cert_type} {key_tag} {algorithm} {certificate}",
+            static ref RE: Regex = Regex::new(r"^(\S+) (\d+) (\d+) (.+)$").unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            let cert_type = match Self::type_from_mnemonic(&caps[1]) {
+                Some(cert_type) => cert_type,
+                None => return Err(FromStrError::InvalidFormat),
+            };
+
+            // The certificate may be split by whitespace.
+            let certificate: String = caps[4].split_whitespace().collect();
+
+            Ok(CERT {
+                cert_type,
+                key_tag: caps[2].parse()?,
+                algorithm: caps[3].parse()?,
+                certificate: base64::decode(certificate)?,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
+impl FromStr for APL {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // "1:192.0.2.0/24 !1:192.0.2.128/25"
+        let items = s
+            .split_whitespace()
+            .map(|item| item.parse())
+            .collect::<Result<Vec<APLItem>, _>>()?;
+
+        Ok(APL(items))
+    }
+}
+
+impl FromStr for APLItem {
+    type Err = FromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        lazy_static! {
+            // "!1:192.0.2.128/25"
+            // "{negation}{family}:{address}/{prefix}",
+            static ref RE: Regex = Regex::new(r"^(!?)(\d+):(\S+)/(\d+)$").unwrap();
+        }
+
+        if let Some(caps) = RE.captures(s) {
+            let address: IpAddr = match &caps[2] {
+                "1" => IpAddr::V4(caps[3].parse()?),
+                "2" => IpAddr::V6(caps[3].parse()?),
+                _ => return Err(FromStrError::InvalidFormat),
+            };
+
+            let prefix: u8 = caps[4].parse()?;
+            let max = if address.is_ipv4() { 32 } else { 128 };
+            if prefix > max {
+                return Err(FromStrError::InvalidFormat);
+            }
+
+            Ok(APLItem {
+                negation: &caps[1] == "!",
+                address,
+                prefix,
+            })
+        } else {
+            Err(FromStrError::InvalidFormat)
+        }
+    }
+}
+
 impl FromStr for TXT {
     type Err = FromStrError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         lazy_static! {
-            // TODO Handle escaped quotes
-            static ref RE: Regex = Regex::new(r#""(.*?)""#).unwrap();
+            // A quoted string, which may contain escaped characters, such as \" or \195.
+            static ref RE: Regex = Regex::new(r#""((?:[^"\\]|\\.)*)""#).unwrap();
         }
 
         if !s.starts_with('"') && !s.ends_with('"') {
             // Assume a single unquoted string
-            return Ok(TXT::from(s))
+            return Ok(TXT(vec![unescape(s)?]))
         }
 
         // Otherparse parse multiple "..." strings
         let mut txts = Vec::new();
+        let mut end = 0;
         for caps in RE.captures_iter(s) {
-            txts.push(caps[1].as_bytes().to_vec());
+            // Only whitespace is allowed between the strings.
+            let m = caps.get(0).unwrap();
+            if !s[end..m.start()].trim().is_empty() {
+                return Err(FromStrError::InvalidFormat);
+            }
+            end = m.end();
+
+            txts.push(unescape(&caps[1])?);
         };
 
-        if txts.is_empty() {
+        if txts.is_empty() || !s[end..].trim().is_empty() {
             return Err(FromStrError::InvalidFormat);
         }
 
-        // TODO Also check we parsed the full record
-
         Ok(TXT(txts))
     }
 }
@@ -155,7 +155,7 @@ pub trait DNSReadExt: io::Read + io::Seek {
     /// Reads a DNS Type.
     fn read_type(&mut self) -> io::Result<Type> {
         let r#type = self.read_u16::<BE>()?;
-        let r#type = match FromPrimitive::from_u16(r#type) {
+        let r#type = match Type::from_u16(r#type) {
             Some(t) => t,
             None => bail!(InvalidData, "invalid Type({})", r#type),
         };
@@ -163,6 +163,47 @@ pub trait DNSReadExt: io::Read + io::Seek {
         Ok(r#type)
     }
 
+    /// Reads a type bitmap, as used by CSYNC and NSEC records, until the end
+    /// of the input. See [rfc4034 section 4.1.2].
+    ///
+    /// [rfc4034 section 4.1.2]: https://datatracker.ietf.org/doc/html/rfc4034#section-4.1.2
+    fn read_type_bitmap(&mut self) -> io::Result<Vec<Type>> {
+        let mut types = Vec::new();
+
+        loop {
+            // Keep reading windows until EOF is reached.
+            let window = match self.read_u8() {
+                Ok(window) => window as u16,
+                Err(e) => match e.kind() {
+                    io::ErrorKind::UnexpectedEof => break,
+                    _ => return Err(e),
+                },
+            };
+
+            let len = self.read_u8()?;
+            if len == 0 || len > 32 {
+                bail!(InvalidData, "invalid type bitmap length {}", len);
+            }
+
+            let mut bitmap = vec![0; len.into()];
+            self.read_exact(&mut bitmap)?;
+
+            for (i, byte) in bitmap.iter().enumerate() {
+                for bit in 0..8 {
+                    if byte & (0x80 >> bit) != 0 {
+                        let r#type = window * 256 + (i as u16) * 8 + bit;
+                        match Type::from_u16(r#type) {
+                            Some(t) => types.push(t),
+                            None => bail!(InvalidData, "invalid Type({})", r#type),
+                        };
+                    }
+                }
+            }
+        }
+
+        Ok(types)
+    }
+
     /// Reads a DNS Class.
     fn read_class(&mut self) -> io::Result<Class> {
         let class = self.read_u16::<BE>()?;
@@ -11,11 +11,34 @@
 //!   * AAAA,
 //!   * CNAME,
 //!   * MX,
+//!   * WKS,
+//!   * AFSDB,
+//!   * X25 and ISDN,
+//!   * RT,
+//!   * NSAP and NSAP-PTR,
+//!   * GPOS,
+//!   * ATMA,
 //!   * NS,
 //!   * SOA,
 //!   * PTR,
-//!   * TXT, and
-//!   * SRV
+//!   * TXT,
+//!   * SRV,
+//!   * URI,
+//!   * APL,
+//!   * CERT,
+//!   * DHCID,
+//!   * OPENPGPKEY,
+//!   * HIP,
+//!   * SMIMEA,
+//!   * CDS and CDNSKEY,
+//!   * CSYNC,
+//!   * SVCB,
+//!   * HTTPS,
+//!   * NID, L32, L64 and LP,
+//!   * EUI48 and EUI64,
+//!   * AVC and DOA,
+//!   * KEY and SIG (deprecated, but parsed for older zones), and
+//!   * TKEY and TSIG
 //! * Extension Mechanisms for DNS ([EDNS(0)]).
 //! * Support [International Domain Names (IDNA)](https://en.wikipedia.org/wiki/Internationalized_domain_name) - Different scripts, alphabets, anhd even emojis!
 //! * Sample `dig` style [command line](#usage-cli).
@@ -88,6 +111,11 @@
 //!   - `tcp`: Enables the DNS over TCP client
 //!   - `udp`: Enables the DNS over UDP client
 //! - `zones`: Enable a Zone File Parser
+//!   - `gzip`: Allow the Zone File Parser to read gzip compressed files
+//!
+//! `std` is required. A `no_std` (alloc only) build is not supported, as the
+//! Zone File Parser depends on crates that need `std` (such as pest, regex and
+//! chrono), and the A and AAAA records use `std::net` addresses.
 //!
 //! # Usage (cli)
 //!
@@ -135,6 +163,7 @@
 //!
 //! ```shell
 //! $ cargo fuzz run from_slice
+//! $ cargo fuzz run parse_zone
 //! ```
 //!
 //! ## Test Data
@@ -237,3 +266,5 @@ pub use crate::clients::Resolver;
 
 pub use crate::errors::Error;
 pub use crate::errors::ParseError;
+pub use crate::errors::NameError;
+pub use crate::errors::SOAError;
@@ -1,13 +1,21 @@
 use crate::bail;
 use crate::io::{CursorExt, DNSReadExt, SeekExt};
 use crate::types::*;
+use crate::util::name_labels;
 use crate::ParseError;
+use crate::SOAError;
+use chrono::Datelike;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use num_traits::FromPrimitive;
+use std::convert::TryFrom;
 use byteorder::{ReadBytesExt, BE};
 use std::io;
 use std::io::Cursor;
 use std::io::Read;
-use std::net::{Ipv4Addr, Ipv6Addr};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::time::Duration;
+use std::time::SystemTime;
 
 /// IPv4 Address (A) record.
 pub type A = Ipv4Addr;
@@ -29,6 +37,48 @@ pub type CNAME = String;
 #[allow(clippy::upper_case_acronyms)]
 pub type PTR = String;
 
+/// DHCP Information (DHCID) record, used to associate a DHCP client with a
+/// domain name. The contents is a opaque digest. See [rfc4701].
+///
+/// [rfc4701]: https://datatracker.ietf.org/doc/html/rfc4701
+#[allow(clippy::upper_case_acronyms)]
+pub type DHCID = Vec<u8>;
+
+/// OpenPGP Public Key (OPENPGPKEY) record, publishing a OpenPGP key for the
+/// email address whose hashed local part is the owner name. The contents is
+/// a OpenPGP Transferable Public Key. See [rfc7929].
+///
+/// [rfc7929]: https://datatracker.ietf.org/doc/html/rfc7929
+#[allow(clippy::upper_case_acronyms)]
+pub type OPENPGPKEY = Vec<u8>;
+
+/// X.25 (X25) record, holding a PSDN (Public Switched Data Network) address,
+/// a string of at least 4 decimal digits. See [rfc1183 section 3.1].
+///
+/// [rfc1183 section 3.1]: https://datatracker.ietf.org/doc/html/rfc1183#section-3.1
+#[allow(clippy::upper_case_acronyms)]
+pub type X25 = String;
+
+/// Network Service Access Point (NSAP) record, holding a OSI NSAP address of
+/// up to 20 bytes. See [rfc1706].
+///
+/// [rfc1706]: https://datatracker.ietf.org/doc/html/rfc1706
+#[allow(clippy::upper_case_acronyms)]
+pub type NSAP = Vec<u8>;
+
+/// 48-bit Extended Unique Identifier (EUI48) record, such as a MAC address.
+/// See [rfc7043].
+///
+/// [rfc7043]: https://datatracker.ietf.org/doc/html/rfc7043
+#[allow(clippy::upper_case_acronyms)]
+pub type EUI48 = [u8; 6];
+
+/// 64-bit Extended Unique Identifier (EUI64) record. See [rfc7043].
+///
+/// [rfc7043]: https://datatracker.ietf.org/doc/html/rfc7043
+#[allow(clippy::upper_case_acronyms)]
+pub type EUI64 = [u8; 8];
+
 /// Text (TXT) record for arbitrary human-readable text in a DNS record.
 #[allow(clippy::upper_case_acronyms)]
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -63,46 +113,397 @@ impl Record {
         // If parsing fails for this record, (and the length seems correct),
         // we could turn this into a warning instead of a full error.
 
+        let resource = Resource::parse(&mut record, r#type, class)?;
+
+        if record.remaining()? > 0 {
+            bail!(
+                Other,
+                "finished '{}' parsing record with {} bytes left over",
+                r#type,
+                record.remaining()?
+            );
+        }
+
+        // Now catch up (this is safe since record.len() < cur.len())
+        cur.set_position(record.position());
+
+        Ok(Record {
+            name,
+            class,
+            ttl: Duration::from_secs(ttl.into()),
+            resource,
+        })
+    }
+}
+
+impl Resource {
+    /// Parses the RDATA of a record of the given type, from the current
+    /// position of the cursor.
+    pub(crate) fn parse(
+        record: &mut Cursor<&[u8]>,
+        r#type: Type,
+        class: Class,
+    ) -> io::Result<Resource> {
         // TODO Consider changing these parse methods to some kind of common function
         // that accepts Cursor and Class.
-        let resource = match r#type {
-            Type::A => Resource::A(parse_a(&mut record, class)?),
-            Type::AAAA => Resource::AAAA(parse_aaaa(&mut record, class)?),
+        Ok(match r#type {
+            Type::A => Resource::A(parse_a(record, class)?),
+            Type::AAAA => Resource::AAAA(parse_aaaa(record, class)?),
 
             Type::NS => Resource::NS(record.read_qname()?),
-            Type::SOA => Resource::SOA(SOA::parse(&mut record)?),
+            Type::SOA => Resource::SOA(SOA::parse(record)?),
             Type::CNAME => Resource::CNAME(record.read_qname()?),
             Type::PTR => Resource::PTR(record.read_qname()?),
-            Type::MX => Resource::MX(MX::parse(&mut record)?),
-            Type::TXT => Resource::TXT(parse_txt(&mut record)?),
-            Type::SPF => Resource::SPF(parse_txt(&mut record)?),
-            Type::SRV => Resource::SRV(SRV::parse(&mut record)?),
+            Type::MX => Resource::MX(MX::parse(record)?),
+            Type::WKS => Resource::WKS(WKS::parse(record)?),
+            Type::AFSDB => Resource::AFSDB(AFSDB::parse(record)?),
+            Type::X25 => Resource::X25(read_string(record, "X25 address")?),
+            Type::ISDN => Resource::ISDN(ISDN::parse(record)?),
+            Type::RT => Resource::RT(RT::parse(record)?),
+            Type::NSAP => Resource::NSAP(parse_nsap(record)?),
+            Type::NSAP_PTR => Resource::NSAP_PTR(record.read_qname()?),
+            Type::GPOS => Resource::GPOS(GPOS::parse(record)?),
+            Type::TXT => Resource::TXT(parse_txt(record)?),
+            Type::SPF => Resource::SPF(parse_txt(record)?),
+            Type::AVC => Resource::AVC(parse_txt(record)?),
+            Type::SRV => Resource::SRV(SRV::parse(record)?),
+            Type::ATMA => Resource::ATMA(ATMA::parse(record)?),
+            Type::URI => Resource::URI(URI::parse(record)?),
+            Type::APL => Resource::APL(APL::parse(record)?),
+            Type::CERT => Resource::CERT(CERT::parse(record)?),
+            Type::DHCID => Resource::DHCID(parse_dhcid(record)?),
+            Type::SMIMEA => Resource::SMIMEA(TLSA::parse(record)?),
+            Type::HIP => Resource::HIP(HIP::parse(record)?),
+            Type::CDS => Resource::CDS(DS::parse(record)?),
+            Type::CDNSKEY => Resource::CDNSKEY(KEY::parse(record)?),
+            Type::OPENPGPKEY => Resource::OPENPGPKEY(parse_openpgpkey(record)?),
+            Type::CSYNC => Resource::CSYNC(CSYNC::parse(record)?),
+            Type::SVCB => Resource::SVCB(SVCB::parse(record)?),
+            Type::HTTPS => Resource::HTTPS(SVCB::parse(record)?),
+            Type::NID => Resource::NID(NID::parse(record)?),
+            Type::L32 => Resource::L32(L32::parse(record)?),
+            Type::L64 => Resource::L64(L64::parse(record)?),
+            Type::LP => Resource::LP(LP::parse(record)?),
+            Type::EUI48 => Resource::EUI48(parse_eui48(record)?),
+            Type::EUI64 => Resource::EUI64(parse_eui64(record)?),
+            Type::KEY => Resource::KEY(KEY::parse(record)?),
+            Type::SIG => Resource::SIG(SIG::parse(record)?),
+            Type::DOA => Resource::DOA(DOA::parse(record)?),
+            Type::TKEY => Resource::TKEY(TKEY::parse(record)?),
+            Type::TSIG => Resource::TSIG(TSIG::parse(record)?),
 
             // This should never appear in a answer record unless we have invalid data.
             Type::Reserved | Type::OPT | Type::ANY => {
                 // TODO This could be a warning, instead of a full error.
                 bail!(InvalidData, "invalid record type '{}'", r#type);
             }
-        };
+        })
+    }
+
+    /// Parses the uncompressed RDATA of a record of the given type, for
+    /// example as found in the generic `\# len hex` form ([rfc3597]).
+    ///
+    /// [rfc3597]: https://datatracker.ietf.org/doc/html/rfc3597
+    #[cfg(feature = "zones")]
+    pub(crate) fn from_rdata(r#type: Type, class: Class, rdata: &[u8]) -> io::Result<Resource> {
+        let mut record = Cursor::new(rdata);
+        let resource = Resource::parse(&mut record, r#type, class)?;
 
         if record.remaining()? > 0 {
             bail!(
                 Other,
-                "finished '{}' parsing record with {} bytes left over",
+                "finished '{}' parsing RDATA with {} bytes left over",
                 r#type,
                 record.remaining()?
             );
         }
 
-        // Now catch up (this is safe since record.len() < cur.len())
-        cur.set_position(record.position());
+        Ok(resource)
+    }
 
-        Ok(Record {
-            name,
-            class,
-            ttl: Duration::from_secs(ttl.into()),
-            resource,
-        })
+    /// Returns the RDATA in the canonical wire format, as defined by
+    /// [rfc4034 section 6.2]. That is uncompressed, with the names of the
+    /// older record types (such as NS, MX and SOA) lowercased.
+    ///
+    /// [rfc4034 section 6.2]: https://datatracker.ietf.org/doc/html/rfc4034#section-6.2
+    pub fn canonical_rdata(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        match self {
+            Resource::A(ip) => buf.extend(ip.octets()),
+            Resource::AAAA(ip) => buf.extend(ip.octets()),
+
+            Resource::CNAME(name) | Resource::NS(name) | Resource::PTR(name) => {
+                write_name(&mut buf, name, true)
+            }
+
+            Resource::TXT(txt) | Resource::SPF(txt) | Resource::AVC(txt) => {
+                for s in &txt.0 {
+                    buf.push(s.len() as u8);
+                    buf.extend(s);
+                }
+            }
+
+            Resource::MX(mx) => {
+                buf.extend_from_slice(&mx.preference.to_be_bytes());
+                write_name(&mut buf, &mx.exchange, true);
+            }
+            Resource::WKS(wks) => {
+                buf.extend(wks.address.octets());
+                buf.push(wks.protocol);
+
+                // A bitmap where bit N is set for port N.
+                for port in &wks.ports {
+                    let i = *port as usize / 8;
+                    if buf.len() <= 5 + i {
+                        buf.resize(5 + i + 1, 0);
+                    }
+                    buf[5 + i] |= 0x80 >> (port % 8);
+                }
+            }
+            Resource::AFSDB(afsdb) => {
+                buf.extend_from_slice(&afsdb.subtype.to_be_bytes());
+                write_name(&mut buf, &afsdb.hostname, true);
+            }
+            Resource::RT(rt) => {
+                buf.extend_from_slice(&rt.preference.to_be_bytes());
+                write_name(&mut buf, &rt.host, true);
+            }
+            Resource::X25(address) => {
+                buf.push(address.len() as u8);
+                buf.extend(address.as_bytes());
+            }
+            Resource::ISDN(isdn) => {
+                for value in std::iter::once(&isdn.address).chain(&isdn.subaddress) {
+                    buf.push(value.len() as u8);
+                    buf.extend(value.as_bytes());
+                }
+            }
+            Resource::NSAP(nsap) => buf.extend(nsap),
+            Resource::ATMA(ATMA::AESA(address)) => {
+                buf.push(0);
+                buf.extend(address);
+            }
+            Resource::ATMA(ATMA::E164(address)) => {
+                buf.push(1);
+                buf.extend(address.as_bytes());
+            }
+            Resource::NSAP_PTR(name) => write_name(&mut buf, name, false),
+            Resource::GPOS(gpos) => {
+                for value in [&gpos.longitude, &gpos.latitude, &gpos.altitude] {
+                    buf.push(value.len() as u8);
+                    buf.extend(value.as_bytes());
+                }
+            }
+            Resource::SOA(soa) => {
+                // The rname is stored as a email address, so convert back.
+                let rname = SOA::email_to_rname(&soa.rname).unwrap_or_else(|_| soa.rname.clone());
+
+                write_name(&mut buf, &soa.mname, true);
+                write_name(&mut buf, &rname, true);
+                buf.extend_from_slice(&soa.serial.to_be_bytes());
+                for d in [soa.refresh, soa.retry, soa.expire, soa.minimum] {
+                    buf.extend_from_slice(&(d.as_secs() as u32).to_be_bytes());
+                }
+            }
+            Resource::SRV(srv) => {
+                buf.extend_from_slice(&srv.priority.to_be_bytes());
+                buf.extend_from_slice(&srv.weight.to_be_bytes());
+                buf.extend_from_slice(&srv.port.to_be_bytes());
+                write_name(&mut buf, &srv.name, true);
+            }
+            Resource::URI(uri) => {
+                buf.extend_from_slice(&uri.priority.to_be_bytes());
+                buf.extend_from_slice(&uri.weight.to_be_bytes());
+                buf.extend(uri.target.as_bytes());
+            }
+            Resource::APL(apl) => {
+                for item in &apl.0 {
+                    let mut address = match item.address {
+                        IpAddr::V4(ip) => ip.octets().to_vec(),
+                        IpAddr::V6(ip) => ip.octets().to_vec(),
+                    };
+                    // Trailing zero bytes are removed.
+                    while address.last() == Some(&0) {
+                        address.pop();
+                    }
+
+                    buf.extend_from_slice(&item.family().to_be_bytes());
+                    buf.push(item.prefix);
+                    buf.push(address.len() as u8 | if item.negation { 0x80 } else { 0 });
+                    buf.extend(address);
+                }
+            }
+            Resource::CERT(cert) => {
+                buf.extend_from_slice(&cert.cert_type.to_be_bytes());
+                buf.extend_from_slice(&cert.key_tag.to_be_bytes());
+                buf.push(cert.algorithm);
+                buf.extend(&cert.certificate);
+            }
+            Resource::DHCID(dhcid) => buf.extend(dhcid),
+            Resource::OPENPGPKEY(key) => buf.extend(key),
+            Resource::HIP(hip) => {
+                buf.push(hip.hit.len() as u8);
+                buf.push(hip.algorithm);
+                buf.extend_from_slice(&(hip.public_key.len() as u16).to_be_bytes());
+                buf.extend(&hip.hit);
+                buf.extend(&hip.public_key);
+                for server in &hip.rendezvous_servers {
+                    write_name(&mut buf, server, false);
+                }
+            }
+            Resource::EUI48(eui) => buf.extend(eui),
+            Resource::EUI64(eui) => buf.extend(eui),
+            Resource::CDS(ds) => {
+                buf.extend_from_slice(&ds.key_tag.to_be_bytes());
+                buf.push(ds.algorithm);
+                buf.push(ds.digest_type);
+                buf.extend(&ds.digest);
+            }
+            Resource::SMIMEA(tlsa) => {
+                buf.push(tlsa.usage);
+                buf.push(tlsa.selector);
+                buf.push(tlsa.matching_type);
+                buf.extend(&tlsa.data);
+            }
+            Resource::CSYNC(csync) => {
+                buf.extend_from_slice(&csync.serial.to_be_bytes());
+                buf.extend_from_slice(&csync.flags.to_be_bytes());
+                write_type_bitmap(&mut buf, &csync.types);
+            }
+            Resource::SVCB(svcb) | Resource::HTTPS(svcb) => {
+                buf.extend_from_slice(&svcb.priority.to_be_bytes());
+                write_name(&mut buf, &svcb.target, false);
+
+                // The parameters must be in increasing key order.
+                let mut params: Vec<&SvcParam> = svcb.params.iter().collect();
+                params.sort_by_key(|param| param.key());
+
+                for param in params {
+                    let value = param.canonical_value();
+                    buf.extend_from_slice(&param.key().to_be_bytes());
+                    buf.extend_from_slice(&(value.len() as u16).to_be_bytes());
+                    buf.extend(value);
+                }
+            }
+            Resource::NID(nid) => {
+                buf.extend_from_slice(&nid.preference.to_be_bytes());
+                buf.extend_from_slice(&nid.node_id.to_be_bytes());
+            }
+            Resource::L32(l32) => {
+                buf.extend_from_slice(&l32.preference.to_be_bytes());
+                buf.extend(l32.locator.octets());
+            }
+            Resource::L64(l64) => {
+                buf.extend_from_slice(&l64.preference.to_be_bytes());
+                buf.extend_from_slice(&l64.locator.to_be_bytes());
+            }
+            Resource::LP(lp) => {
+                buf.extend_from_slice(&lp.preference.to_be_bytes());
+                write_name(&mut buf, &lp.fqdn, false);
+            }
+            Resource::KEY(key) | Resource::CDNSKEY(key) => {
+                buf.extend_from_slice(&key.flags.to_be_bytes());
+                buf.push(key.protocol);
+                buf.push(key.algorithm);
+                buf.extend(&key.public_key);
+            }
+            Resource::SIG(sig) => {
+                buf.extend_from_slice(&(sig.type_covered as u16).to_be_bytes());
+                buf.push(sig.algorithm);
+                buf.push(sig.labels);
+                buf.extend_from_slice(&sig.original_ttl.to_be_bytes());
+                buf.extend_from_slice(&sig.expiration.to_be_bytes());
+                buf.extend_from_slice(&sig.inception.to_be_bytes());
+                buf.extend_from_slice(&sig.key_tag.to_be_bytes());
+                write_name(&mut buf, &sig.signer_name, true);
+                buf.extend(&sig.signature);
+            }
+            Resource::DOA(doa) => {
+                buf.extend_from_slice(&doa.enterprise.to_be_bytes());
+                buf.extend_from_slice(&doa.doa_type.to_be_bytes());
+                buf.push(doa.location);
+                buf.push(doa.media_type.len() as u8);
+                buf.extend(doa.media_type.as_bytes());
+                buf.extend(&doa.data);
+            }
+            Resource::TKEY(tkey) => {
+                write_name(&mut buf, &tkey.algorithm, true);
+                buf.extend_from_slice(&tkey.inception.to_be_bytes());
+                buf.extend_from_slice(&tkey.expiration.to_be_bytes());
+                buf.extend_from_slice(&tkey.mode.to_be_bytes());
+                buf.extend_from_slice(&tkey.error.to_be_bytes());
+                buf.extend_from_slice(&(tkey.key.len() as u16).to_be_bytes());
+                buf.extend(&tkey.key);
+                buf.extend_from_slice(&(tkey.other_data.len() as u16).to_be_bytes());
+                buf.extend(&tkey.other_data);
+            }
+            Resource::TSIG(tsig) => {
+                write_name(&mut buf, &tsig.algorithm, true);
+                // The time signed is a 48 bit number.
+                buf.extend_from_slice(&tsig.time_signed.to_be_bytes()[2..]);
+                buf.extend_from_slice(&tsig.fudge.to_be_bytes());
+                buf.extend_from_slice(&(tsig.mac.len() as u16).to_be_bytes());
+                buf.extend(&tsig.mac);
+                buf.extend_from_slice(&tsig.original_id.to_be_bytes());
+                buf.extend_from_slice(&tsig.error.to_be_bytes());
+                buf.extend_from_slice(&(tsig.other_data.len() as u16).to_be_bytes());
+                buf.extend(&tsig.other_data);
+            }
+
+            Resource::Unknown(_, rdata) => buf.extend(rdata),
+
+            Resource::OPT | Resource::ANY => (),
+        }
+
+        buf
+    }
+}
+
+/// Writes a uncompressed domain name, optionally lowercased.
+pub(crate) fn write_name(buf: &mut Vec<u8>, name: &str, lowercase: bool) {
+    // Names are stored as unicode, but the wire format is always ASCII.
+    let ascii;
+    let name = if name.is_ascii() {
+        name
+    } else {
+        ascii = idna::domain_to_ascii(name).unwrap_or_else(|_| name.to_string());
+        &ascii
+    };
+
+    for mut label in name_labels(name) {
+        if lowercase {
+            label.make_ascii_lowercase();
+        }
+        buf.push(label.len() as u8);
+        buf.extend(label);
+    }
+    buf.push(0);
+}
+
+/// Writes the types as a type bitmap, as defined by [rfc4034 section 4.1.2].
+///
+/// [rfc4034 section 4.1.2]: https://datatracker.ietf.org/doc/html/rfc4034#section-4.1.2
+fn write_type_bitmap(buf: &mut Vec<u8>, types: &[Type]) {
+    let mut types: Vec<u16> = types.iter().map(Type::to_u16).collect();
+    types.sort_unstable();
+    types.dedup();
+
+    for window in 0..=255_u8 {
+        let mut bitmap = Vec::new();
+        for t in types.iter().filter(|t| (*t >> 8) as u8 == window) {
+            let i = (t & 0xff) as usize;
+            if bitmap.len() <= i / 8 {
+                bitmap.resize(i / 8 + 1, 0);
+            }
+            bitmap[i / 8] |= 0x80 >> (i % 8);
+        }
+
+        if !bitmap.is_empty() {
+            buf.push(window);
+            buf.push(bitmap.len() as u8);
+            buf.extend(bitmap);
+        }
     }
 }
 
@@ -118,6 +519,49 @@ pub struct MX {
     pub exchange: String,
 }
 
+/// Well Known Services (WKS) record, listing the services supported by a
+/// host on a particular protocol. See [rfc1035].
+///
+/// [rfc1035]: https://datatracker.ietf.org/doc/html/rfc1035
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct WKS {
+    pub address: Ipv4Addr,
+
+    /// The IP protocol number, for example 6 for TCP, or 17 for UDP.
+    pub protocol: u8,
+
+    /// The supported ports, in increasing order.
+    pub ports: Vec<u16>,
+}
+
+/// AFS Data Base location (AFSDB) record, for finding a AFS cell database
+/// server, or a DCE authenticated name server. See [rfc1183].
+///
+/// [rfc1183]: https://datatracker.ietf.org/doc/html/rfc1183
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct AFSDB {
+    /// The type of server, 1 for a AFS database server, or 2 for a DCE
+    /// authenticated name server.
+    pub subtype: u16,
+    pub hostname: String,
+}
+
+/// Route Through (RT) record, specifying a intermediate host to route
+/// through to reach the owner. See [rfc1183].
+///
+/// [rfc1183]: https://datatracker.ietf.org/doc/html/rfc1183
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct RT {
+    /// The preference given to this RR among others at the same owner.
+    /// Lower values are preferred.
+    pub preference: u16,
+
+    /// The intermediate host to route through.
+    pub host: String,
+}
+
 /// Start of Authority (SOA) record containing administrative information
 /// about the zone. See [rfc1035].
 ///
@@ -155,143 +599,1574 @@ pub struct SRV {
     pub name: String,
 }
 
-fn parse_a(cur: &mut Cursor<&[u8]>, class: Class) -> io::Result<A> {
-    let mut buf = [0_u8; 4];
-    cur.read_exact(&mut buf)?;
+/// Uniform Resource Identifier (URI) record, for mapping a hostname to a URI. See [rfc7553].
+///
+/// [rfc7553]: https://datatracker.ietf.org/doc/html/rfc7553
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct URI {
+    pub priority: u16,
+    pub weight: u16,
+    pub target: String,
+}
 
-    match class {
-        Class::Internet => Ok(A::new(buf[0], buf[1], buf[2], buf[3])),
+/// Address Prefix List (APL) record, containing a list of address ranges. See [rfc3123].
+///
+/// [rfc3123]: https://datatracker.ietf.org/doc/html/rfc3123
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct APL(pub Vec<APLItem>);
 
-        _ => bail!(InvalidData, "unsupported A record class '{}'", class),
-    }
-}
+/// A single address prefix within a [`APL`] record, for example "1:192.0.2.0/24".
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct APLItem {
+    /// If true the prefix is excluded from the list, written as "!" in text.
+    pub negation: bool,
 
-fn parse_aaaa(cur: &mut Cursor<&[u8]>, class: Class) -> io::Result<AAAA> {
-    let mut buf = [0_u8; 16];
-    cur.read_exact(&mut buf)?;
+    /// The network address. The address family (1 for IPv4, 2 for IPv6) is
+    /// implied by the type of address.
+    pub address: IpAddr,
 
-    match class {
-        Class::Internet => Ok(AAAA::from(buf)),
+    /// The number of leading bits of the address making up the prefix.
+    pub prefix: u8,
+}
 
-        _ => bail!(InvalidData, "unsupported AAAA record class '{}'", class),
-    }
+/// Certificate (CERT) record, for storing certificates and related revocation
+/// lists. See [rfc4398].
+///
+/// [rfc4398]: https://datatracker.ietf.org/doc/html/rfc4398
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct CERT {
+    /// The type of certificate, for example 1 for X.509 (PKIX). See
+    /// [`CERT::type_to_mnemonic`] for the known types.
+    pub cert_type: u16,
+    pub key_tag: u16,
+    pub algorithm: u8,
+
+    /// The certificate, in the format defined by the `cert_type`.
+    pub certificate: Vec<u8>,
 }
 
-fn parse_txt(cur: &mut Cursor<&[u8]>) -> io::Result<TXT> {
-    let mut txts = Vec::new();
+/// Host Identity Protocol (HIP) record, publishing a Host Identity and the
+/// rendezvous servers it can be reached via. See [rfc8005].
+///
+/// [rfc8005]: https://datatracker.ietf.org/doc/html/rfc8005
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct HIP {
+    /// The algorithm of the public key, for example 2 for RSA.
+    pub algorithm: u8,
 
-    loop {
-        // Keep reading until EOF is reached.
-        let len = match cur.read_u8() {
-            Ok(len) => len,
-            Err(e) => match e.kind() {
-                io::ErrorKind::UnexpectedEof => break,
-                _ => return Err(e),
-            },
-        };
+    /// The Host Identity Tag, a hash of the public key.
+    pub hit: Vec<u8>,
+    pub public_key: Vec<u8>,
 
-        let mut txt = vec![0; len.into()];
-        cur.read_exact(&mut txt)?;
-        txts.push(txt)
-    }
+    /// The domain names of any rendezvous servers, in order of preference.
+    pub rendezvous_servers: Vec<String>,
+}
 
-    Ok(TXT(txts))
+/// Integrated Services Digital Network (ISDN) record. See [rfc1183 section 3.2].
+///
+/// [rfc1183 section 3.2]: https://datatracker.ietf.org/doc/html/rfc1183#section-3.2
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct ISDN {
+    /// The ISDN number, such as "150862028003217".
+    pub address: String,
+
+    /// The optional subaddress, such as "004".
+    pub subaddress: Option<String>,
 }
 
-impl SOA {
-    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<SOA> {
-        let mname = cur.read_qname()?;
-        let rname = Self::rname_to_email(&cur.read_qname()?).unwrap(); // TODO error handling
+/// ATM Address (ATMA) record, holding the address of a ATM end system. See
+/// the ATM Forum's "ATM Name System Specification Version 1.0"
+/// (af-dans-0152.000).
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum ATMA {
+    /// A ATM End System Address (format 0), usually 20 bytes, written in
+    /// hex, such as "39246f00e7c9f0001f3c5c2e0000022bb3200000".
+    AESA(Vec<u8>),
 
-        let serial = cur.read_u32::<BE>()?;
-        let refresh = cur.read_u32::<BE>()?;
-        let retry = cur.read_u32::<BE>()?;
-        let expire = cur.read_u32::<BE>()?;
-        let minimum = cur.read_u32::<BE>()?;
+    /// A E.164 number (format 1), written as "+" followed by the decimal
+    /// digits, such as "+358400123456".
+    E164(String),
+}
 
-        Ok(SOA {
-            mname,
-            rname,
+/// Geographical Position (GPOS) record, the predecessor to LOC. See [rfc1712].
+///
+/// Each value is kept as the decimal string found in the record, so it is
+/// not changed by converting to, and from, a floating point number.
+///
+/// [rfc1712]: https://datatracker.ietf.org/doc/html/rfc1712
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct GPOS {
+    /// Degrees east (positive) or west (negative) of the Prime Meridian,
+    /// between -180 and 180.
+    pub longitude: String,
 
-            serial,
-            refresh: Duration::from_secs(refresh.into()),
-            retry: Duration::from_secs(retry.into()),
-            expire: Duration::from_secs(expire.into()),
-            minimum: Duration::from_secs(minimum.into()),
-        })
-    }
+    /// Degrees north (positive) or south (negative) of the Equator, between
+    /// -90 and 90.
+    pub latitude: String,
 
-    /// Converts rnames to email address, for example, "admin.example.com" is
-    /// converted to "admin@example.com", per the rules in
-    /// https://datatracker.ietf.org/doc/html/rfc1035#section-8
-    pub fn rname_to_email(domain: &str) -> Result<String, ParseError> {
-        // The logic is simple.
-        // Find first unescaped dot and replace with a @
+    /// Meters above (positive) or below (negative) sea level.
+    pub altitude: String,
+}
 
-        // Handle the escaping. Replace the first . which isn't escapated with a \
-        let mut result = String::with_capacity(domain.len());
-        let mut last_char = ' ';
-        let mut done = false;
-        for c in domain.chars() {
-            if last_char == '\\' {
-                // Last character was escape, so always append this one.
-                result.push(c);
-            } else if c == '.' && !done {
-                result.push('@');
-                done = true;
-            } else if c != '\\' {
-                // Otherwise append if not an escape.
-                result.push(c);
-            }
+/// Delegation Signer (DS) data, identifying a DNSKEY by its digest. See
+/// [rfc4034 section 5]. This is used by the [`Resource::CDS`] record.
+///
+/// [rfc4034 section 5]: https://datatracker.ietf.org/doc/html/rfc4034#section-5
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct DS {
+    pub key_tag: u16,
+    pub algorithm: u8,
 
-            last_char = c;
-        }
+    /// The algorithm used to create the digest, for example 2 for SHA-256.
+    pub digest_type: u8,
+    pub digest: Vec<u8>,
+}
 
-        if !done {
-            return Err(ParseError::InvalidRname(domain.to_string()));
-        }
+/// Certificate association data, in the format of a TLSA record, matching a
+/// certificate or its public key. See [rfc6698 section 2]. This is used by
+/// the [`Resource::SMIMEA`] record.
+///
+/// [rfc6698 section 2]: https://datatracker.ietf.org/doc/html/rfc6698#section-2
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct TLSA {
+    /// How the certificate is used, for example 3 for the end entity's
+    /// certificate (DANE-EE).
+    pub usage: u8,
 
-        Ok(result)
-    }
+    /// Which part of the certificate is matched, 0 for the full certificate,
+    /// or 1 for its public key.
+    pub selector: u8,
 
-    pub fn email_to_rname(email: &str) -> Result<String, ParseError> {
-        match email.split_once('@') {
+    /// How the data is matched, 0 for the exact data, 1 for a SHA-256 hash,
+    /// or 2 for a SHA-512 hash.
+    pub matching_type: u8,
+    pub data: Vec<u8>,
+}
+
+/// Child-To-Parent Synchronization (CSYNC) record, indicating which records
+/// the parent zone should copy from the child. See [rfc7477].
+///
+/// [rfc7477]: https://datatracker.ietf.org/doc/html/rfc7477
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct CSYNC {
+    /// The SOA serial of the child zone.
+    pub serial: u32,
+    pub flags: u16,
+
+    /// The types that should be synchronised.
+    pub types: Vec<Type>,
+}
+
+/// Node Identifier (NID) record, for the Identifier-Locator Network Protocol
+/// (ILNP). See [rfc6742].
+///
+/// [rfc6742]: https://datatracker.ietf.org/doc/html/rfc6742
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct NID {
+    /// The preference given to this RR among others at the same owner.
+    /// Lower values are preferred.
+    pub preference: u16,
+
+    /// The 64-bit node identifier, written as "0014:4fff:ff20:ee64".
+    pub node_id: u64,
+}
+
+/// 32-bit Locator (L32) record, for ILNPv4. See [rfc6742].
+///
+/// [rfc6742]: https://datatracker.ietf.org/doc/html/rfc6742
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct L32 {
+    /// The preference given to this RR among others at the same owner.
+    /// Lower values are preferred.
+    pub preference: u16,
+
+    /// The locator, written the same as a IPv4 address.
+    pub locator: Ipv4Addr,
+}
+
+/// 64-bit Locator (L64) record, for ILNPv6. See [rfc6742].
+///
+/// [rfc6742]: https://datatracker.ietf.org/doc/html/rfc6742
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct L64 {
+    /// The preference given to this RR among others at the same owner.
+    /// Lower values are preferred.
+    pub preference: u16,
+
+    /// The 64-bit locator, written as "2001:0db8:1140:1000".
+    pub locator: u64,
+}
+
+/// Locator FQDN (LP) record, naming the subnetwork a node is on, which in
+/// turn has the L32 or L64 records. See [rfc6742].
+///
+/// [rfc6742]: https://datatracker.ietf.org/doc/html/rfc6742
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct LP {
+    /// The preference given to this RR among others at the same owner.
+    /// Lower values are preferred.
+    pub preference: u16,
+    pub fqdn: String,
+}
+
+/// Key (KEY) record, containing a public key. See [rfc2535].
+///
+/// This is deprecated, and replaced by DNSKEY in [rfc3755], but is parsed
+/// for compatibility with older zones.
+///
+/// [rfc2535]: https://datatracker.ietf.org/doc/html/rfc2535
+/// [rfc3755]: https://datatracker.ietf.org/doc/html/rfc3755
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct KEY {
+    pub flags: u16,
+    pub protocol: u8,
+    pub algorithm: u8,
+    pub public_key: Vec<u8>,
+}
+
+/// Signature (SIG) record, containing a signature over a set of records. See [rfc2535].
+///
+/// This is deprecated, and replaced by RRSIG in [rfc3755], but is parsed
+/// for compatibility with older zones.
+///
+/// [rfc2535]: https://datatracker.ietf.org/doc/html/rfc2535
+/// [rfc3755]: https://datatracker.ietf.org/doc/html/rfc3755
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct SIG {
+    /// The type of records covered by this signature.
+    pub type_covered: Type,
+    pub algorithm: u8,
+
+    /// The number of labels in the original owner name.
+    pub labels: u8,
+    pub original_ttl: u32,
+
+    /// The end of the signature's validity, in seconds since the UNIX epoch.
+    pub expiration: u32,
+
+    /// The start of the signature's validity, in seconds since the UNIX epoch.
+    pub inception: u32,
+    pub key_tag: u16,
+    pub signer_name: String,
+    pub signature: Vec<u8>,
+}
+
+/// Digital Object Architecture (DOA) record, locating a digital object, or
+/// holding it directly. See [draft-durand-doa-over-dns].
+///
+/// [draft-durand-doa-over-dns]: https://datatracker.ietf.org/doc/html/draft-durand-doa-over-dns
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct DOA {
+    /// The IANA Private Enterprise Number of the organisation defining the
+    /// `doa_type`, or 0 for types defined by the DOA specification.
+    pub enterprise: u32,
+    pub doa_type: u32,
+
+    /// How the `data` should be interpreted, for example 1 for the object
+    /// itself, or 2 for a URI where it can be found.
+    pub location: u8,
+
+    /// The media type of the object, for example "image/png", which may be
+    /// empty.
+    pub media_type: String,
+    pub data: Vec<u8>,
+}
+
+/// Transaction Key (TKEY) record, used to agree a shared secret for TSIG.
+/// This is a meta record, only found in the additional section of a
+/// message, so is usually built with [`TKEY::new`]. See [rfc2930].
+///
+/// [rfc2930]: https://datatracker.ietf.org/doc/html/rfc2930
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct TKEY {
+    /// The name of the algorithm the key is for, such as "hmac-sha256.".
+    pub algorithm: String,
+
+    /// The start of the key's validity, in seconds since the UNIX epoch.
+    pub inception: u32,
+
+    /// The end of the key's validity, in seconds since the UNIX epoch.
+    pub expiration: u32,
+
+    /// How the key is agreed, for example 3 for GSS-API or 5 for deletion.
+    pub mode: u16,
+
+    /// A extended RCODE, such as 19 (BADMODE), or 0 for no error.
+    pub error: u16,
+    pub key: Vec<u8>,
+    pub other_data: Vec<u8>,
+}
+
+/// Transaction Signature (TSIG) record, authenticating a message with a
+/// shared secret. This is a meta record, only found in the additional
+/// section of a message, so is usually built with [`TSIG::new`]. See
+/// [rfc8945].
+///
+/// [rfc8945]: https://datatracker.ietf.org/doc/html/rfc8945
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct TSIG {
+    /// The name of the MAC algorithm, such as "hmac-sha256.".
+    pub algorithm: String,
+
+    /// When the message was signed, in seconds since the UNIX epoch. Only
+    /// the lower 48 bits are used.
+    pub time_signed: u64,
+
+    /// The number of seconds of clock skew allowed from `time_signed`.
+    pub fudge: u16,
+    pub mac: Vec<u8>,
+
+    /// The ID of the message before it was signed.
+    pub original_id: u16,
+
+    /// A extended RCODE, such as 16 (BADSIG), or 0 for no error.
+    pub error: u16,
+
+    /// Extra data, which holds the server's time for a BADTIME error.
+    pub other_data: Vec<u8>,
+}
+
+/// The names of the extended RCODEs used in the TKEY and TSIG error fields,
+/// as written in the text format. See [rfc8945] and [rfc2930].
+///
+/// [rfc8945]: https://datatracker.ietf.org/doc/html/rfc8945
+/// [rfc2930]: https://datatracker.ietf.org/doc/html/rfc2930
+pub(crate) const TSIG_ERRORS: [(u16, &str); 8] = [
+    (0, "NOERROR"),
+    (16, "BADSIG"),
+    (17, "BADKEY"),
+    (18, "BADTIME"),
+    (19, "BADMODE"),
+    (20, "BADNAME"),
+    (21, "BADALG"),
+    (22, "BADTRUNC"),
+];
+
+/// Service Binding (SVCB) record, describing the endpoints and parameters
+/// for a service. The same format is used for HTTPS records. See [rfc9460].
+///
+/// [rfc9460]: https://datatracker.ietf.org/doc/html/rfc9460
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub struct SVCB {
+    /// The priority of this record, or 0 for AliasMode.
+    pub priority: u16,
+
+    /// The name of the alternative endpoint, or "." for the owner name.
+    pub target: String,
+
+    /// The service parameters, in the order they appear.
+    pub params: Vec<SvcParam>,
+}
+
+/// A single service parameter within a [`SVCB`] record, for example "alpn=h2,h3".
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum SvcParam {
+    /// The keys of parameters that must be understood to use this record.
+    Mandatory(Vec<u16>),
+
+    /// The Application-Layer Protocol Negotiation (ALPN) protocol ids.
+    Alpn(Vec<String>),
+
+    /// The default ALPN protocols are not supported.
+    NoDefaultAlpn,
+
+    /// The TCP or UDP port to connect to.
+    Port(u16),
+
+    /// IPv4 addresses that may be used to reach the service.
+    Ipv4Hint(Vec<Ipv4Addr>),
+
+    /// A Encrypted ClientHello config list.
+    Ech(Vec<u8>),
+
+    /// IPv6 addresses that may be used to reach the service.
+    Ipv6Hint(Vec<Ipv6Addr>),
+
+    /// Any other parameter, as a key and its opaque value.
+    Unknown(u16, Vec<u8>),
+}
+
+/// Service parameter keys and their names, from rfc9460 section 14.3.2.
+static SVC_PARAM_KEYS: &[(u16, &str)] = &[
+    (0, "mandatory"),
+    (1, "alpn"),
+    (2, "no-default-alpn"),
+    (3, "port"),
+    (4, "ipv4hint"),
+    (5, "ech"),
+    (6, "ipv6hint"),
+];
+
+/// IP protocols, and well known services, that may be named in WKS records.
+static WKS_PROTOCOLS: &[(u8, &str)] = &[(6, "tcp"), (17, "udp")];
+static WKS_SERVICES: &[(u16, &str)] = &[
+    (21, "ftp"),
+    (22, "ssh"),
+    (23, "telnet"),
+    (25, "smtp"),
+    (53, "domain"),
+    (69, "tftp"),
+    (79, "finger"),
+    (80, "http"),
+    (110, "pop3"),
+    (111, "sunrpc"),
+    (119, "nntp"),
+    (123, "ntp"),
+    (143, "imap"),
+    (161, "snmp"),
+    (389, "ldap"),
+    (443, "https"),
+];
+
+/// Certificate types and their mnemonics, from rfc4398 section 2.1.
+static CERT_TYPES: &[(u16, &str)] = &[
+    (1, "PKIX"),
+    (2, "SPKI"),
+    (3, "PGP"),
+    (4, "IPKIX"),
+    (5, "ISPKI"),
+    (6, "IPGP"),
+    (7, "ACPKIX"),
+    (8, "IACPKIX"),
+    (253, "URI"),
+    (254, "OID"),
+];
+
+fn parse_a(cur: &mut Cursor<&[u8]>, class: Class) -> io::Result<A> {
+    let mut buf = [0_u8; 4];
+    cur.read_exact(&mut buf)?;
+
+    match class {
+        Class::Internet => Ok(A::new(buf[0], buf[1], buf[2], buf[3])),
+
+        _ => bail!(InvalidData, "unsupported A record class '{}'", class),
+    }
+}
+
+fn parse_aaaa(cur: &mut Cursor<&[u8]>, class: Class) -> io::Result<AAAA> {
+    let mut buf = [0_u8; 16];
+    cur.read_exact(&mut buf)?;
+
+    match class {
+        Class::Internet => Ok(AAAA::from(buf)),
+
+        _ => bail!(InvalidData, "unsupported AAAA record class '{}'", class),
+    }
+}
+
+fn parse_dhcid(cur: &mut Cursor<&[u8]>) -> io::Result<DHCID> {
+    // The digest is the full record.
+    let mut dhcid = Vec::new();
+    cur.read_to_end(&mut dhcid)?;
+
+    Ok(dhcid)
+}
+
+/// Reads a single UTF-8 <character-string>, a length followed by the text.
+fn read_string(cur: &mut Cursor<&[u8]>, field: &str) -> io::Result<String> {
+    let mut value = vec![0; cur.read_u8()?.into()];
+    cur.read_exact(&mut value)?;
+
+    match String::from_utf8(value) {
+        Ok(value) => Ok(value),
+        Err(e) => bail!(InvalidData, "invalid {}: {}", field, e),
+    }
+}
+
+fn parse_nsap(cur: &mut Cursor<&[u8]>) -> io::Result<NSAP> {
+    // The address is the full record.
+    let mut nsap = Vec::new();
+    cur.read_to_end(&mut nsap)?;
+
+    Ok(nsap)
+}
+
+fn parse_openpgpkey(cur: &mut Cursor<&[u8]>) -> io::Result<OPENPGPKEY> {
+    // The key is the full record.
+    let mut key = Vec::new();
+    cur.read_to_end(&mut key)?;
+
+    Ok(key)
+}
+
+fn parse_eui48(cur: &mut Cursor<&[u8]>) -> io::Result<EUI48> {
+    let mut buf = [0_u8; 6];
+    cur.read_exact(&mut buf)?;
+
+    Ok(buf)
+}
+
+fn parse_eui64(cur: &mut Cursor<&[u8]>) -> io::Result<EUI64> {
+    let mut buf = [0_u8; 8];
+    cur.read_exact(&mut buf)?;
+
+    Ok(buf)
+}
+
+fn parse_txt(cur: &mut Cursor<&[u8]>) -> io::Result<TXT> {
+    let mut txts = Vec::new();
+
+    loop {
+        // Keep reading until EOF is reached.
+        let len = match cur.read_u8() {
+            Ok(len) => len,
+            Err(e) => match e.kind() {
+                io::ErrorKind::UnexpectedEof => break,
+                _ => return Err(e),
+            },
+        };
+
+        let mut txt = vec![0; len.into()];
+        cur.read_exact(&mut txt)?;
+        txts.push(txt)
+    }
+
+    Ok(TXT(txts))
+}
+
+impl SOA {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<SOA> {
+        let mname = cur.read_qname()?;
+        let rname = Self::rname_to_email(&cur.read_qname()?).unwrap(); // TODO error handling
+
+        let serial = cur.read_u32::<BE>()?;
+        let refresh = cur.read_u32::<BE>()?;
+        let retry = cur.read_u32::<BE>()?;
+        let expire = cur.read_u32::<BE>()?;
+        let minimum = cur.read_u32::<BE>()?;
+
+        Ok(SOA {
+            mname,
+            rname,
+
+            serial,
+            refresh: Duration::from_secs(refresh.into()),
+            retry: Duration::from_secs(retry.into()),
+            expire: Duration::from_secs(expire.into()),
+            minimum: Duration::from_secs(minimum.into()),
+        })
+    }
+
+    /// Converts rnames to email address, for example, "admin.example.com" is
+    /// converted to "admin@example.com", per the rules in
+    /// https://datatracker.ietf.org/doc/html/rfc1035#section-8
+    pub fn rname_to_email(domain: &str) -> Result<String, ParseError> {
+        // The logic is simple.
+        // Find first unescaped dot and replace with a @
+
+        // Handle the escaping. Replace the first . which isn't escapated with a \
+        let mut result = String::with_capacity(domain.len());
+        let mut last_char = ' ';
+        let mut done = false;
+        for c in domain.chars() {
+            if last_char == '\\' {
+                // Last character was escape, so always append this one.
+                result.push(c);
+            } else if c == '.' && !done {
+                result.push('@');
+                done = true;
+            } else if c != '\\' {
+                // Otherwise append if not an escape.
+                result.push(c);
+            }
+
+            last_char = c;
+        }
+
+        if !done {
+            return Err(ParseError::InvalidRname(domain.to_string()));
+        }
+
+        Ok(result)
+    }
+
+    /// Returns true if a secondary that last refreshed the zone at `last_refresh`
+    /// should now consider the zone expired, as it has been longer than `expire`.
+    pub fn is_expired(&self, now: SystemTime, last_refresh: SystemTime) -> bool {
+        match now.duration_since(last_refresh) {
+            Ok(elapsed) => elapsed >= self.expire,
+            Err(_) => false, // last_refresh is in the future
+        }
+    }
+
+    /// Returns the next serial, following the common "YYYYMMDDnn" convention,
+    /// where nn is a counter for changes made that day. The result is always
+    /// greater than the current serial, so if the serial is not already using
+    /// this convention, or more than 100 changes are made in a day, it's just
+    /// incremented.
+    ///
+    /// Serials are compared using serial number arithmetic ([rfc1982]), as
+    /// secondaries do, so the serial may wrap around. For example, the next
+    /// serial after `u32::MAX` is today's date.
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// # use rustdns::SOA;
+    /// # use std::time::Duration;
+    /// # let soa = SOA {
+    /// #     mname: "ns.example.com".to_string(),
+    /// #     rname: "admin@example.com".to_string(),
+    /// #     serial: 2021123101,
+    /// #     refresh: Duration::new(7200, 0),
+    /// #     retry: Duration::new(3600, 0),
+    /// #     expire: Duration::new(1209600, 0),
+    /// #     minimum: Duration::new(3600, 0),
+    /// # };
+    ///
+    /// let today = NaiveDate::from_ymd_opt(2022, 1, 5).unwrap();
+    /// assert_eq!(soa.serial, 2021123101);
+    /// assert_eq!(soa.next_serial_date(today), 2022010500);
+    /// ```
+    ///
+    /// [rfc1982]: https://datatracker.ietf.org/doc/html/rfc1982
+    pub fn next_serial_date(&self, today: NaiveDate) -> u32 {
+        let date = today.year() as u32 * 10000 + today.month() * 100 + today.day();
+        let serial = date.saturating_mul(100);
+
+        if Self::serial_greater(serial, self.serial) {
+            serial
+        } else {
+            self.serial.wrapping_add(1)
+        }
+    }
+
+    /// Returns true if serial `a` is greater than `b`, using serial number
+    /// arithmetic ([rfc1982]), where the serials wrap around.
+    ///
+    /// [rfc1982]: https://datatracker.ietf.org/doc/html/rfc1982#section-3.2
+    pub fn serial_greater(a: u32, b: u32) -> bool {
+        a != b && a.wrapping_sub(b) < 1 << 31
+    }
+
+    /// Checks the timers are sensible, that is, the refresh is greater than
+    /// the retry, and the expire is greater than the refresh and retry
+    /// combined. See [rfc1912] for recommended values.
+    ///
+    /// [rfc1912]: https://datatracker.ietf.org/doc/html/rfc1912#section-2.2
+    pub fn validate(&self) -> Result<(), SOAError> {
+        let refresh = self.refresh.as_secs();
+        let retry = self.retry.as_secs();
+        let expire = self.expire.as_secs();
+
+        if refresh <= retry {
+            return Err(SOAError::RetryTooLarge { refresh, retry });
+        }
+
+        if expire <= refresh + retry {
+            return Err(SOAError::ExpireTooSmall {
+                expire,
+                refresh_retry: refresh + retry,
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn email_to_rname(email: &str) -> Result<String, ParseError> {
+        match email.split_once('@') {
             None => Err(ParseError::InvalidRname(email.to_string())),
 
-            // Escape all the dots to the left of the '@',
-            // replace the '@' with a '.', and leave everything after the '@' alone.
-            Some((left, right)) => Ok(left.replace('.', "\\.") + "." + right),
-        }
+            // Escape all the dots to the left of the '@',
+            // replace the '@' with a '.', and leave everything after the '@' alone.
+            Some((left, right)) => Ok(left.replace('.', "\\.") + "." + right),
+        }
+    }
+}
+
+impl MX {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<MX> {
+        let preference = cur.read_u16::<BE>()?;
+        let exchange = cur.read_qname()?;
+
+        Ok(MX {
+            preference,
+            exchange,
+        })
+    }
+}
+
+impl WKS {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<WKS> {
+        let address = Ipv4Addr::from(cur.read_u32::<BE>()?);
+        let protocol = cur.read_u8()?;
+
+        // The remainder is a bitmap, where bit N is set for port N.
+        let mut bitmap = Vec::new();
+        cur.read_to_end(&mut bitmap)?;
+
+        let mut ports = Vec::new();
+        for (i, byte) in bitmap.iter().enumerate() {
+            for bit in 0..8 {
+                if byte & (0x80 >> bit) != 0 {
+                    match u16::try_from(i * 8 + bit) {
+                        Ok(port) => ports.push(port),
+                        Err(_) => bail!(InvalidData, "invalid WKS bitmap length {}", bitmap.len()),
+                    }
+                }
+            }
+        }
+
+        Ok(WKS {
+            address,
+            protocol,
+            ports,
+        })
+    }
+
+    /// Returns the protocol for a name (or number), for example 6 for "tcp", or "6".
+    pub fn protocol_from_name(name: &str) -> Option<u8> {
+        if let Ok(protocol) = name.parse() {
+            return Some(protocol);
+        }
+
+        WKS_PROTOCOLS
+            .iter()
+            .find(|(_, n)| n.eq_ignore_ascii_case(name))
+            .map(|(value, _)| *value)
+    }
+
+    /// Returns the port for a service name (or number), for example 25 for
+    /// "smtp", or "25". Only a small set of well known services are named.
+    pub fn port_from_name(name: &str) -> Option<u16> {
+        if let Ok(port) = name.parse() {
+            return Some(port);
+        }
+
+        WKS_SERVICES
+            .iter()
+            .find(|(_, n)| n.eq_ignore_ascii_case(name))
+            .map(|(value, _)| *value)
+    }
+}
+
+impl AFSDB {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<AFSDB> {
+        let subtype = cur.read_u16::<BE>()?;
+        let hostname = cur.read_qname()?;
+
+        Ok(AFSDB { subtype, hostname })
+    }
+}
+
+impl RT {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<RT> {
+        let preference = cur.read_u16::<BE>()?;
+        let host = cur.read_qname()?;
+
+        Ok(RT { preference, host })
+    }
+}
+
+impl ISDN {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<ISDN> {
+        let address = read_string(cur, "ISDN address")?;
+
+        // The subaddress is optional, so only present if there is more data.
+        let subaddress = if cur.remaining()? > 0 {
+            Some(read_string(cur, "ISDN subaddress")?)
+        } else {
+            None
+        };
+
+        Ok(ISDN {
+            address,
+            subaddress,
+        })
+    }
+}
+
+impl ATMA {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<ATMA> {
+        let format = cur.read_u8()?;
+
+        // The address is the rest of the record.
+        let mut address = Vec::new();
+        cur.read_to_end(&mut address)?;
+
+        match format {
+            0 => Ok(ATMA::AESA(address)),
+            1 => match String::from_utf8(address) {
+                Ok(address) if address.bytes().all(|b| b.is_ascii_digit()) => {
+                    Ok(ATMA::E164(address))
+                }
+                _ => bail!(
+                    InvalidData,
+                    "invalid ATMA E.164 address: expected decimal digits"
+                ),
+            },
+            _ => bail!(InvalidData, "invalid ATMA format '{}'", format),
+        }
+    }
+}
+
+impl GPOS {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<GPOS> {
+        // Each value is a <character-string>, a length followed by the text.
+        let mut read_string = || -> io::Result<String> {
+            let mut value = vec![0; cur.read_u8()?.into()];
+            cur.read_exact(&mut value)?;
+
+            match String::from_utf8(value) {
+                Ok(value) => Ok(value),
+                Err(e) => bail!(InvalidData, "invalid GPOS value: {}", e),
+            }
+        };
+
+        Ok(GPOS {
+            longitude: read_string()?,
+            latitude: read_string()?,
+            altitude: read_string()?,
+        })
+    }
+}
+
+impl SRV {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<SRV> {
+        let priority = cur.read_u16::<BE>()?;
+        let weight = cur.read_u16::<BE>()?;
+        let port = cur.read_u16::<BE>()?;
+
+        let name = cur.read_qname()?;
+
+        Ok(SRV {
+            priority,
+            weight,
+            port,
+            name,
+        })
+    }
+}
+
+impl URI {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<URI> {
+        let priority = cur.read_u16::<BE>()?;
+        let weight = cur.read_u16::<BE>()?;
+
+        // The target is the remainder of the record.
+        let mut target = Vec::new();
+        cur.read_to_end(&mut target)?;
+
+        let target = match String::from_utf8(target) {
+            Err(e) => bail!(InvalidData, "invalid URI target: {}", e),
+            Ok(s) => s,
+        };
+
+        Ok(URI {
+            priority,
+            weight,
+            target,
+        })
+    }
+}
+
+impl APL {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<APL> {
+        let mut items = Vec::new();
+
+        while cur.remaining()? > 0 {
+            let family = cur.read_u16::<BE>()?;
+            let prefix = cur.read_u8()?;
+            let n = cur.read_u8()?;
+
+            // The top bit is the negation flag, the rest is the length of
+            // the address, which has any trailing zero bytes removed.
+            let negation = n & 0x80 != 0;
+            let len = (n & 0x7f) as usize;
+
+            let address = match family {
+                1 => {
+                    let mut buf = [0_u8; 4];
+                    if len > buf.len() {
+                        bail!(InvalidData, "invalid APL IPv4 address length {}", len);
+                    }
+                    cur.read_exact(&mut buf[..len])?;
+                    IpAddr::from(buf)
+                }
+                2 => {
+                    let mut buf = [0_u8; 16];
+                    if len > buf.len() {
+                        bail!(InvalidData, "invalid APL IPv6 address length {}", len);
+                    }
+                    cur.read_exact(&mut buf[..len])?;
+                    IpAddr::from(buf)
+                }
+                _ => bail!(InvalidData, "unsupported APL address family {}", family),
+            };
+
+            items.push(APLItem {
+                negation,
+                address,
+                prefix,
+            });
+        }
+
+        Ok(APL(items))
+    }
+}
+
+impl CSYNC {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<CSYNC> {
+        let serial = cur.read_u32::<BE>()?;
+        let flags = cur.read_u16::<BE>()?;
+        let types = cur.read_type_bitmap()?;
+
+        Ok(CSYNC {
+            serial,
+            flags,
+            types,
+        })
+    }
+}
+
+impl NID {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<NID> {
+        let preference = cur.read_u16::<BE>()?;
+        let node_id = cur.read_u64::<BE>()?;
+
+        Ok(NID {
+            preference,
+            node_id,
+        })
+    }
+}
+
+impl L32 {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<L32> {
+        let preference = cur.read_u16::<BE>()?;
+        let locator = Ipv4Addr::from(cur.read_u32::<BE>()?);
+
+        Ok(L32 {
+            preference,
+            locator,
+        })
     }
 }
 
-impl MX {
-    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<MX> {
+impl L64 {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<L64> {
         let preference = cur.read_u16::<BE>()?;
-        let exchange = cur.read_qname()?;
+        let locator = cur.read_u64::<BE>()?;
 
-        Ok(MX {
+        Ok(L64 {
             preference,
-            exchange,
+            locator,
         })
     }
+
+    /// Formats a 64-bit locator, or node identifier, as four groups of hex
+    /// digits, for example "2001:0db8:1140:1000".
+    pub fn locator_to_string(locator: u64) -> String {
+        format!(
+            "{:04x}:{:04x}:{:04x}:{:04x}",
+            locator >> 48,
+            (locator >> 32) & 0xffff,
+            (locator >> 16) & 0xffff,
+            locator & 0xffff,
+        )
+    }
+
+    /// Parses a 64-bit locator, or node identifier, written as four groups
+    /// of hex digits, for example "2001:db8:1140:1000".
+    pub fn locator_from_str(s: &str) -> Option<u64> {
+        let groups: Vec<&str> = s.split(':').collect();
+        if groups.len() != 4 {
+            return None;
+        }
+
+        let mut locator = 0;
+        for group in groups {
+            if group.is_empty() || group.len() > 4 {
+                return None;
+            }
+            locator = locator << 16 | u64::from(u16::from_str_radix(group, 16).ok()?);
+        }
+
+        Some(locator)
+    }
 }
 
-impl SRV {
-    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<SRV> {
+impl LP {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<LP> {
+        let preference = cur.read_u16::<BE>()?;
+        let fqdn = cur.read_qname()?;
+
+        Ok(LP { preference, fqdn })
+    }
+}
+
+impl HIP {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<HIP> {
+        let hit_len = cur.read_u8()?;
+        let algorithm = cur.read_u8()?;
+        let public_key_len = cur.read_u16::<BE>()?;
+
+        let mut hit = vec![0; hit_len.into()];
+        cur.read_exact(&mut hit)?;
+
+        let mut public_key = vec![0; public_key_len.into()];
+        cur.read_exact(&mut public_key)?;
+
+        // The rendezvous servers fill the remainder of the record.
+        let mut rendezvous_servers = Vec::new();
+        while cur.remaining()? > 0 {
+            rendezvous_servers.push(cur.read_qname()?);
+        }
+
+        Ok(HIP {
+            algorithm,
+            hit,
+            public_key,
+            rendezvous_servers,
+        })
+    }
+}
+
+impl DS {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<DS> {
+        let key_tag = cur.read_u16::<BE>()?;
+        let algorithm = cur.read_u8()?;
+        let digest_type = cur.read_u8()?;
+
+        // The digest is the remainder of the record.
+        let mut digest = Vec::new();
+        cur.read_to_end(&mut digest)?;
+
+        Ok(DS {
+            key_tag,
+            algorithm,
+            digest_type,
+            digest,
+        })
+    }
+
+    /// Returns true if this is the special CDS record "0 0 0 00", which asks
+    /// the parent to remove all DS records for the child. See [rfc8078 section 4].
+    ///
+    /// [rfc8078 section 4]: https://datatracker.ietf.org/doc/html/rfc8078#section-4
+    pub fn is_delete(&self) -> bool {
+        self.key_tag == 0 && self.algorithm == 0 && self.digest_type == 0 && self.digest == [0]
+    }
+}
+
+impl TLSA {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<TLSA> {
+        let usage = cur.read_u8()?;
+        let selector = cur.read_u8()?;
+        let matching_type = cur.read_u8()?;
+
+        // The data is the remainder of the record.
+        let mut data = Vec::new();
+        cur.read_to_end(&mut data)?;
+
+        Ok(TLSA {
+            usage,
+            selector,
+            matching_type,
+            data,
+        })
+    }
+}
+
+impl KEY {
+    /// Returns true if this is the special CDNSKEY record "0 3 0 AA==", which
+    /// asks the parent to remove all DS records for the child. See [rfc8078 section 4].
+    ///
+    /// [rfc8078 section 4]: https://datatracker.ietf.org/doc/html/rfc8078#section-4
+    pub fn is_delete(&self) -> bool {
+        self.flags == 0 && self.protocol == 3 && self.algorithm == 0 && self.public_key == [0]
+    }
+
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<KEY> {
+        let flags = cur.read_u16::<BE>()?;
+        let protocol = cur.read_u8()?;
+        let algorithm = cur.read_u8()?;
+
+        // The key is the remainder of the record.
+        let mut public_key = Vec::new();
+        cur.read_to_end(&mut public_key)?;
+
+        Ok(KEY {
+            flags,
+            protocol,
+            algorithm,
+            public_key,
+        })
+    }
+}
+
+impl SIG {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<SIG> {
+        let r#type = cur.read_u16::<BE>()?;
+        let type_covered = match FromPrimitive::from_u16(r#type) {
+            Some(t) => t,
+            None => bail!(InvalidData, "invalid SIG type covered '{}'", r#type),
+        };
+
+        let algorithm = cur.read_u8()?;
+        let labels = cur.read_u8()?;
+        let original_ttl = cur.read_u32::<BE>()?;
+        let expiration = cur.read_u32::<BE>()?;
+        let inception = cur.read_u32::<BE>()?;
+        let key_tag = cur.read_u16::<BE>()?;
+        let signer_name = cur.read_qname()?;
+
+        // The signature is the remainder of the record.
+        let mut signature = Vec::new();
+        cur.read_to_end(&mut signature)?;
+
+        Ok(SIG {
+            type_covered,
+            algorithm,
+            labels,
+            original_ttl,
+            expiration,
+            inception,
+            key_tag,
+            signer_name,
+            signature,
+        })
+    }
+
+    /// Formats a expiration or inception time as "YYYYMMDDHHmmSS" in UTC, as
+    /// used in the text format.
+    pub fn time_to_string(time: u32) -> String {
+        NaiveDateTime::from_timestamp_opt(time.into(), 0)
+            .expect("a u32 is always a valid timestamp")
+            .format("%Y%m%d%H%M%S")
+            .to_string()
+    }
+
+    /// Parses a expiration or inception time, either as "YYYYMMDDHHmmSS" in
+    /// UTC, or as a number of seconds since the UNIX epoch.
+    pub fn time_from_str(s: &str) -> Option<u32> {
+        if s.len() == 14 {
+            let time = NaiveDateTime::parse_from_str(s, "%Y%m%d%H%M%S").ok()?;
+            return u32::try_from(time.timestamp()).ok();
+        }
+
+        s.parse().ok()
+    }
+}
+
+impl DOA {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<DOA> {
+        let enterprise = cur.read_u32::<BE>()?;
+        let doa_type = cur.read_u32::<BE>()?;
+        let location = cur.read_u8()?;
+
+        // The media type is a <character-string>, a length followed by the text.
+        let mut media_type = vec![0; cur.read_u8()?.into()];
+        cur.read_exact(&mut media_type)?;
+        let media_type = match String::from_utf8(media_type) {
+            Ok(media_type) => media_type,
+            Err(e) => bail!(InvalidData, "invalid DOA media type: {}", e),
+        };
+
+        // The data is the remainder of the record.
+        let mut data = Vec::new();
+        cur.read_to_end(&mut data)?;
+
+        Ok(DOA {
+            enterprise,
+            doa_type,
+            location,
+            media_type,
+            data,
+        })
+    }
+}
+
+impl TKEY {
+    /// Returns a TKEY record for the `algorithm`, valid between the
+    /// `inception` and `expiration` times, with no error, key or other data.
+    /// The rest of the fields can be set with the `with_` methods.
+    ///
+    /// ```rust
+    /// use rustdns::TKEY;
+    ///
+    /// let tkey = TKEY::new("gss-tsig.", 1700000000, 1700003600)
+    ///     .with_mode(3)
+    ///     .with_key(vec![1, 2, 3, 4]);
+    /// assert_eq!(tkey.mode, 3);
+    /// ```
+    pub fn new(algorithm: &str, inception: u32, expiration: u32) -> TKEY {
+        TKEY {
+            algorithm: algorithm.to_string(),
+            inception,
+            expiration,
+            mode: 0,
+            error: 0,
+            key: Vec::new(),
+            other_data: Vec::new(),
+        }
+    }
+
+    pub fn with_mode(mut self, mode: u16) -> TKEY {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_error(mut self, error: u16) -> TKEY {
+        self.error = error;
+        self
+    }
+
+    pub fn with_key(mut self, key: Vec<u8>) -> TKEY {
+        self.key = key;
+        self
+    }
+
+    pub fn with_other_data(mut self, other_data: Vec<u8>) -> TKEY {
+        self.other_data = other_data;
+        self
+    }
+
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<TKEY> {
+        let algorithm = cur.read_qname()?;
+        let inception = cur.read_u32::<BE>()?;
+        let expiration = cur.read_u32::<BE>()?;
+        let mode = cur.read_u16::<BE>()?;
+        let error = cur.read_u16::<BE>()?;
+        let key = read_sized_data(cur)?;
+        let other_data = read_sized_data(cur)?;
+
+        Ok(TKEY {
+            algorithm,
+            inception,
+            expiration,
+            mode,
+            error,
+            key,
+            other_data,
+        })
+    }
+}
+
+impl TSIG {
+    /// Returns a TSIG record for the `algorithm`, signed at `time_signed`,
+    /// allowing `fudge` seconds of clock skew, with no error, MAC or other
+    /// data. The rest of the fields can be set with the `with_` methods.
+    ///
+    /// ```rust
+    /// use rustdns::TSIG;
+    ///
+    /// let tsig = TSIG::new("hmac-sha256.", 1700000000, 300)
+    ///     .with_mac(vec![0; 32])
+    ///     .with_original_id(12345);
+    /// assert_eq!(tsig.mac.len(), 32);
+    /// ```
+    pub fn new(algorithm: &str, time_signed: u64, fudge: u16) -> TSIG {
+        TSIG {
+            algorithm: algorithm.to_string(),
+            time_signed,
+            fudge,
+            mac: Vec::new(),
+            original_id: 0,
+            error: 0,
+            other_data: Vec::new(),
+        }
+    }
+
+    pub fn with_mac(mut self, mac: Vec<u8>) -> TSIG {
+        self.mac = mac;
+        self
+    }
+
+    pub fn with_original_id(mut self, original_id: u16) -> TSIG {
+        self.original_id = original_id;
+        self
+    }
+
+    pub fn with_error(mut self, error: u16) -> TSIG {
+        self.error = error;
+        self
+    }
+
+    pub fn with_other_data(mut self, other_data: Vec<u8>) -> TSIG {
+        self.other_data = other_data;
+        self
+    }
+
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<TSIG> {
+        let algorithm = cur.read_qname()?;
+        let time_signed = cur.read_u48::<BE>()?;
+        let fudge = cur.read_u16::<BE>()?;
+        let mac = read_sized_data(cur)?;
+        let original_id = cur.read_u16::<BE>()?;
+        let error = cur.read_u16::<BE>()?;
+        let other_data = read_sized_data(cur)?;
+
+        Ok(TSIG {
+            algorithm,
+            time_signed,
+            fudge,
+            mac,
+            original_id,
+            error,
+            other_data,
+        })
+    }
+
+    /// Returns the name of a TKEY or TSIG error, such as "BADSIG", or the
+    /// number if it has no name.
+    pub fn error_to_string(error: u16) -> String {
+        match TSIG_ERRORS.iter().find(|(code, _)| *code == error) {
+            Some((_, name)) => name.to_string(),
+            None => error.to_string(),
+        }
+    }
+
+    /// Parses a TKEY or TSIG error, either by name, such as "BADSIG", or as
+    /// a number.
+    pub fn error_from_str(s: &str) -> Option<u16> {
+        match TSIG_ERRORS
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(s))
+        {
+            Some((code, _)) => Some(*code),
+            None => s.parse().ok(),
+        }
+    }
+}
+
+/// Reads data prefixed by its 16 bit length, as used by TKEY and TSIG.
+fn read_sized_data(cur: &mut Cursor<&[u8]>) -> io::Result<Vec<u8>> {
+    let mut data = vec![0; cur.read_u16::<BE>()?.into()];
+    cur.read_exact(&mut data)?;
+    Ok(data)
+}
+
+impl SVCB {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<SVCB> {
         let priority = cur.read_u16::<BE>()?;
-        let weight = cur.read_u16::<BE>()?;
-        let port = cur.read_u16::<BE>()?;
+        let target = cur.read_qname()?;
 
-        let name = cur.read_qname()?;
+        let mut params = Vec::new();
+        while cur.remaining()? > 0 {
+            params.push(SvcParam::parse(cur)?);
+        }
 
-        Ok(SRV {
+        Ok(SVCB {
             priority,
-            weight,
-            port,
-            name,
+            target,
+            params,
+        })
+    }
+}
+
+impl SvcParam {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<SvcParam> {
+        let key = cur.read_u16::<BE>()?;
+        let len = cur.read_u16::<BE>()?;
+
+        let mut value = vec![0; len.into()];
+        cur.read_exact(&mut value)?;
+
+        // Reject values that are not a multiple of the item length.
+        let check_len = |size: usize| -> io::Result<()> {
+            if value.len() % size != 0 {
+                bail!(
+                    InvalidData,
+                    "invalid SVCB '{}' length {}",
+                    Self::key_to_name(key),
+                    len
+                );
+            }
+            Ok(())
+        };
+
+        Ok(match key {
+            0 => {
+                check_len(2)?;
+                SvcParam::Mandatory(
+                    value
+                        .chunks(2)
+                        .map(|b| u16::from_be_bytes([b[0], b[1]]))
+                        .collect(),
+                )
+            }
+            1 => {
+                let mut alpn = Vec::new();
+                let mut value = Cursor::new(&value[..]);
+                while value.remaining()? > 0 {
+                    let len = value.read_u8()?;
+                    let mut id = vec![0; len.into()];
+                    value.read_exact(&mut id)?;
+
+                    match String::from_utf8(id) {
+                        Err(e) => bail!(InvalidData, "invalid SVCB alpn: {}", e),
+                        Ok(id) => alpn.push(id),
+                    }
+                }
+                SvcParam::Alpn(alpn)
+            }
+            2 => {
+                if !value.is_empty() {
+                    bail!(InvalidData, "invalid SVCB no-default-alpn length {}", len);
+                }
+                SvcParam::NoDefaultAlpn
+            }
+            3 => {
+                if value.len() != 2 {
+                    bail!(InvalidData, "invalid SVCB port length {}", len);
+                }
+                SvcParam::Port(u16::from_be_bytes([value[0], value[1]]))
+            }
+            4 => {
+                check_len(4)?;
+                SvcParam::Ipv4Hint(
+                    value
+                        .chunks(4)
+                        .map(|b| Ipv4Addr::new(b[0], b[1], b[2], b[3]))
+                        .collect(),
+                )
+            }
+            5 => SvcParam::Ech(value),
+            6 => {
+                check_len(16)?;
+                let mut hints = Vec::new();
+                for chunk in value.chunks(16) {
+                    let mut buf = [0_u8; 16];
+                    buf.copy_from_slice(chunk);
+                    hints.push(Ipv6Addr::from(buf));
+                }
+                SvcParam::Ipv6Hint(hints)
+            }
+            _ => SvcParam::Unknown(key, value),
+        })
+    }
+
+    /// Returns the key of this parameter, for example 3 for [`SvcParam::Port`].
+    /// Returns the value of this parameter in the wire format.
+    fn canonical_value(&self) -> Vec<u8> {
+        match self {
+            SvcParam::Mandatory(keys) => keys.iter().flat_map(|k| k.to_be_bytes()).collect(),
+            SvcParam::Alpn(ids) => {
+                let mut buf = Vec::new();
+                for id in ids {
+                    buf.push(id.len() as u8);
+                    buf.extend(id.as_bytes());
+                }
+                buf
+            }
+            SvcParam::NoDefaultAlpn => Vec::new(),
+            SvcParam::Port(port) => port.to_be_bytes().to_vec(),
+            SvcParam::Ipv4Hint(ips) => ips.iter().flat_map(|ip| ip.octets()).collect(),
+            SvcParam::Ech(config) => config.clone(),
+            SvcParam::Ipv6Hint(ips) => ips.iter().flat_map(|ip| ip.octets()).collect(),
+            SvcParam::Unknown(_, value) => value.clone(),
+        }
+    }
+
+    pub fn key(&self) -> u16 {
+        match self {
+            SvcParam::Mandatory(_) => 0,
+            SvcParam::Alpn(_) => 1,
+            SvcParam::NoDefaultAlpn => 2,
+            SvcParam::Port(_) => 3,
+            SvcParam::Ipv4Hint(_) => 4,
+            SvcParam::Ech(_) => 5,
+            SvcParam::Ipv6Hint(_) => 6,
+            SvcParam::Unknown(key, _) => *key,
+        }
+    }
+
+    /// Returns the name of the key, for example "port" for 3, or "key65000"
+    /// for keys without a name.
+    pub fn key_to_name(key: u16) -> String {
+        match SVC_PARAM_KEYS.iter().find(|(value, _)| *value == key) {
+            Some((_, name)) => name.to_string(),
+            None => format!("key{}", key),
+        }
+    }
+
+    /// Returns the key for a name, for example 3 for "port", or 65000 for "key65000".
+    pub fn key_from_name(name: &str) -> Option<u16> {
+        if let Some(key) = name.strip_prefix("key") {
+            return key.parse().ok();
+        }
+
+        SVC_PARAM_KEYS
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(value, _)| *value)
+    }
+}
+
+impl CERT {
+    pub(crate) fn parse(cur: &mut Cursor<&[u8]>) -> io::Result<CERT> {
+        let cert_type = cur.read_u16::<BE>()?;
+        let key_tag = cur.read_u16::<BE>()?;
+        let algorithm = cur.read_u8()?;
+
+        // The certificate is the remainder of the record.
+        let mut certificate = Vec::new();
+        cur.read_to_end(&mut certificate)?;
+
+        Ok(CERT {
+            cert_type,
+            key_tag,
+            algorithm,
+            certificate,
         })
     }
+
+    /// Returns the mnemonic for the certificate type, for example "PKIX" for 1.
+    pub fn type_to_mnemonic(cert_type: u16) -> Option<&'static str> {
+        CERT_TYPES
+            .iter()
+            .find(|(value, _)| *value == cert_type)
+            .map(|(_, mnemonic)| *mnemonic)
+    }
+
+    /// Returns the certificate type for a mnemonic (or number), for
+    /// example 1 for "PKIX", or "1".
+    pub fn type_from_mnemonic(mnemonic: &str) -> Option<u16> {
+        if let Ok(cert_type) = mnemonic.parse() {
+            return Some(cert_type);
+        }
+
+        CERT_TYPES
+            .iter()
+            .find(|(_, m)| m.eq_ignore_ascii_case(mnemonic))
+            .map(|(value, _)| *value)
+    }
+}
+
+impl APLItem {
+    /// Returns the address family number, 1 for IPv4 or 2 for IPv6.
+    pub fn family(&self) -> u16 {
+        match self.address {
+            IpAddr::V4(_) => 1,
+            IpAddr::V6(_) => 2,
+        }
+    }
+}
+
+impl TXT {
+    /// Returns the character-strings concatenated together, as many uses of
+    /// TXT records (such as SPF, or DKIM) treat them as one long string.
+    pub fn joined(&self) -> Vec<u8> {
+        self.0.concat()
+    }
 }
 
 impl From<&str> for TXT {
@@ -308,8 +2183,38 @@ impl From<&[&str]> for TXT {
 
 #[cfg(test)]
 mod tests {
+    use crate::APLItem;
+    use crate::Type;
+    use crate::APL;
+    use crate::CSYNC;
+    use crate::DS;
+    use crate::TLSA;
+    use crate::Resource;
+    use crate::WKS;
+    use crate::SIG;
+    use crate::TKEY;
+    use crate::TSIG;
+    use crate::SOAError;
+    use crate::SvcParam;
+    use crate::SVCB;
     use crate::SOA;
+    use chrono::NaiveDate;
     use pretty_assertions::assert_eq;
+    use std::io::Cursor;
+    use std::time::Duration;
+    use std::time::SystemTime;
+
+    fn soa(serial: u32, refresh: u64, retry: u64, expire: u64) -> SOA {
+        SOA {
+            mname: "ns.example.com".to_string(),
+            rname: "admin@example.com".to_string(),
+            serial,
+            refresh: Duration::from_secs(refresh),
+            retry: Duration::from_secs(retry),
+            expire: Duration::from_secs(expire),
+            minimum: Duration::from_secs(3600),
+        }
+    }
 
     static RNAME_TESTS: &[(&str, &str)] = &[
         ("username.example.com", "username@example.com"),
@@ -337,4 +2242,326 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_soa_next_serial_date() {
+        let today = NaiveDate::from_ymd_opt(2022, 1, 5).unwrap();
+        let tests = vec![
+            // Older date, reset to today.
+            (2021123105, 2022010500),
+            // Same date, increment the counter.
+            (2022010500, 2022010501),
+            (2022010542, 2022010543),
+            // Ran out of changes for today, so move into tomorrow.
+            (2022010599, 2022010600),
+            // Serial is already in the future (or not a date), just increment.
+            (2022020100, 2022020101),
+            (4000000000, 4000000001),
+            // Serials wrap around, so today's date is greater than the largest serial.
+            (u32::MAX, 2022010500),
+            // Small serials are moved to the date format.
+            (1, 2022010500),
+        ];
+
+        for (serial, want) in tests {
+            assert_eq!(
+                soa(serial, 7200, 3600, 1209600).next_serial_date(today),
+                want,
+                "incorrect result for {}",
+                serial
+            );
+        }
+
+        assert!(SOA::serial_greater(1, 0));
+        assert!(SOA::serial_greater(0, u32::MAX));
+        assert!(!SOA::serial_greater(0, 0));
+        assert!(!SOA::serial_greater(u32::MAX, 0));
+    }
+
+    #[test]
+    fn test_soa_validate() {
+        let tests = vec![
+            (soa(1, 7200, 3600, 1209600), Ok(())),
+            (
+                soa(1, 3600, 3600, 1209600),
+                Err(SOAError::RetryTooLarge {
+                    refresh: 3600,
+                    retry: 3600,
+                }),
+            ),
+            (
+                soa(1, 600, 7200, 1209600),
+                Err(SOAError::RetryTooLarge {
+                    refresh: 600,
+                    retry: 7200,
+                }),
+            ),
+            (
+                soa(1, 7200, 3600, 10800),
+                Err(SOAError::ExpireTooSmall {
+                    expire: 10800,
+                    refresh_retry: 10800,
+                }),
+            ),
+        ];
+
+        for (soa, want) in tests {
+            assert_eq!(soa.validate(), want, "incorrect result for {:?}", soa);
+        }
+    }
+
+    #[test]
+    fn test_soa_is_expired() {
+        let soa = soa(1, 7200, 3600, 1209600);
+        let last_refresh = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+
+        assert!(!soa.is_expired(last_refresh, last_refresh));
+        assert!(!soa.is_expired(last_refresh + Duration::from_secs(1209599), last_refresh));
+        assert!(soa.is_expired(last_refresh + Duration::from_secs(1209600), last_refresh));
+
+        // A refresh in the future is never expired.
+        assert!(!soa.is_expired(last_refresh, last_refresh + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_csync_parse() {
+        // Example from https://datatracker.ietf.org/doc/html/rfc7477#section-2.3
+        let input = [
+            0x4c, 0x37, 0x6d, 0x29, // serial 1278700841
+            0x00, 0x03, // flags
+            0x00, 0x04, 0x60, 0x00, 0x00, 0x08, // A NS AAAA
+        ];
+
+        let got = CSYNC::parse(&mut Cursor::new(&input[..])).expect("failed to parse");
+        assert_eq!(
+            got,
+            CSYNC {
+                serial: 1278700841,
+                flags: 3,
+                types: vec![Type::A, Type::NS, Type::AAAA],
+            }
+        );
+    }
+
+    #[test]
+    fn test_sig_parse() {
+        let input = [
+            0x00, 0x01, // type covered A
+            0x01, // algorithm
+            0x03, // labels
+            0x00, 0x01, 0x51, 0x80, // original TTL 86400
+            0x3e, 0x7c, 0x9d, 0xd7, // expiration 20030322173103
+            0x3e, 0x55, 0x10, 0xd7, // inception 20030220173103
+            0x0a, 0x52, // key tag 2642
+            0x07, 0x65, 0x78, 0x61, 0x6d, 0x70, 0x6c, 0x65, // example
+            0x03, 0x63, 0x6f, 0x6d, 0x00, // com
+            0x01, 0x02, 0x03, // signature
+        ];
+
+        let got = SIG::parse(&mut Cursor::new(&input[..])).expect("failed to parse");
+        assert_eq!(
+            got,
+            SIG {
+                type_covered: Type::A,
+                algorithm: 1,
+                labels: 3,
+                original_ttl: 86400,
+                expiration: 1048354263,
+                inception: 1045762263,
+                key_tag: 2642,
+                signer_name: "example.com.".to_string(),
+                signature: vec![1, 2, 3],
+            }
+        );
+
+        assert_eq!(SIG::time_to_string(got.expiration), "20030322173103");
+        assert_eq!(SIG::time_from_str("20030322173103"), Some(1048354263));
+        assert_eq!(SIG::time_from_str("1048354263"), Some(1048354263));
+        assert_eq!(SIG::time_from_str("20031322173103"), None);
+    }
+
+    #[test]
+    fn test_tsig_parse() {
+        let input = [
+            0x0b, // length
+            0x68, 0x6d, 0x61, 0x63, 0x2d, 0x73, 0x68, 0x61, 0x32, 0x35, 0x36, // hmac-sha256
+            0x00, // root
+            0x00, 0x00, 0x65, 0x53, 0xf1, 0x00, // time signed 1700000000
+            0x01, 0x2c, // fudge 300
+            0x00, 0x04, 0x01, 0x02, 0x03, 0x04, // mac
+            0x30, 0x39, // original id 12345
+            0x00, 0x12, // error BADTIME
+            0x00, 0x06, 0x00, 0x00, 0x65, 0x53, 0xf2, 0x2c, // other data
+        ];
+
+        let got = TSIG::parse(&mut Cursor::new(&input[..])).expect("failed to parse");
+        let want = TSIG::new("hmac-sha256.", 1700000000, 300)
+            .with_mac(vec![1, 2, 3, 4])
+            .with_original_id(12345)
+            .with_error(18)
+            .with_other_data(vec![0x00, 0x00, 0x65, 0x53, 0xf2, 0x2c]);
+        assert_eq!(got, want);
+        assert_eq!(Resource::TSIG(got).canonical_rdata(), input);
+
+        assert_eq!(TSIG::error_to_string(18), "BADTIME");
+        assert_eq!(TSIG::error_to_string(23), "23");
+        assert_eq!(TSIG::error_from_str("badsig"), Some(16));
+        assert_eq!(TSIG::error_from_str("23"), Some(23));
+        assert_eq!(TSIG::error_from_str("BADCOFFEE"), None);
+    }
+
+    #[test]
+    fn test_tkey_parse() {
+        let input = [
+            0x08, 0x67, 0x73, 0x73, 0x2d, 0x74, 0x73, 0x69, 0x67, 0x00, // gss-tsig
+            0x65, 0x53, 0xf1, 0x00, // inception 1700000000
+            0x65, 0x53, 0xff, 0x10, // expiration 1700003600
+            0x00, 0x03, // mode GSS-API
+            0x00, 0x00, // error
+            0x00, 0x02, 0xab, 0xcd, // key
+            0x00, 0x00, // other data
+        ];
+
+        let got = TKEY::parse(&mut Cursor::new(&input[..])).expect("failed to parse");
+        let want = TKEY::new("gss-tsig.", 1700000000, 1700003600)
+            .with_mode(3)
+            .with_key(vec![0xab, 0xcd]);
+        assert_eq!(got, want);
+        assert_eq!(Resource::TKEY(got).canonical_rdata(), input);
+    }
+
+    #[test]
+    fn test_ds_parse() {
+        let input = [
+            0xec, 0x45, // key tag 60485
+            0x05, // algorithm
+            0x01, // digest type
+            0x2b, 0xb1, 0x83, 0xaf, // digest
+        ];
+
+        let got = DS::parse(&mut Cursor::new(&input[..])).expect("failed to parse");
+        assert_eq!(
+            got,
+            DS {
+                key_tag: 60485,
+                algorithm: 5,
+                digest_type: 1,
+                digest: vec![0x2b, 0xb1, 0x83, 0xaf],
+            }
+        );
+        assert!(!got.is_delete());
+
+        // The CDS "0 0 0 00" record, asking for the DS records to be removed.
+        let input = [0x00, 0x00, 0x00, 0x00, 0x00];
+        let got = DS::parse(&mut Cursor::new(&input[..])).expect("failed to parse");
+        assert!(got.is_delete());
+    }
+
+    #[test]
+    fn test_tlsa_parse() {
+        let input = [
+            0x03, // usage
+            0x01, // selector
+            0x01, // matching type
+            0xd2, 0xab, 0xde, 0x24, // data
+        ];
+
+        let got = TLSA::parse(&mut Cursor::new(&input[..])).expect("failed to parse");
+        assert_eq!(
+            got,
+            TLSA {
+                usage: 3,
+                selector: 1,
+                matching_type: 1,
+                data: vec![0xd2, 0xab, 0xde, 0x24],
+            }
+        );
+    }
+
+    #[test]
+    fn test_wks_parse() {
+        let input = [
+            0xc0, 0x00, 0x02, 0x01, // address 192.0.2.1
+            0x06, // protocol (tcp)
+            0x00, 0x00, 0x00, 0x40, // port 25
+            0x00, 0x00, 0x00, 0x00, //
+            0x00, 0x00, 0x80, // port 80
+        ];
+
+        let got = WKS::parse(&mut Cursor::new(&input[..])).expect("failed to parse");
+        let want = WKS {
+            address: "192.0.2.1".parse().unwrap(),
+            protocol: 6,
+            ports: vec![25, 80],
+        };
+        assert_eq!(got, want);
+        assert_eq!(Resource::WKS(want).canonical_rdata(), input);
+
+        assert_eq!(WKS::protocol_from_name("TCP"), Some(6));
+        assert_eq!(WKS::protocol_from_name("17"), Some(17));
+        assert_eq!(WKS::port_from_name("smtp"), Some(25));
+        assert_eq!(WKS::port_from_name("bogus"), None);
+    }
+
+    #[test]
+    fn test_svcb_parse() {
+        // Example from https://datatracker.ietf.org/doc/html/rfc9460#appendix-D.2
+        // 16 foo.example.org. alpn=h2,h3-19 mandatory=ipv4hint,alpn ipv4hint=192.0.2.1
+        let input = [
+            0x00, 0x10, // priority
+            0x03, 0x66, 0x6f, 0x6f, // foo
+            0x07, 0x65, 0x78, 0x61, 0x6d, 0x70, 0x6c, 0x65, // example
+            0x03, 0x6f, 0x72, 0x67, 0x00, // org
+            0x00, 0x00, 0x00, 0x04, 0x00, 0x01, 0x00, 0x04, // mandatory
+            0x00, 0x01, 0x00, 0x09, // alpn
+            0x02, 0x68, 0x32, 0x05, 0x68, 0x33, 0x2d, 0x31, 0x39, // h2,h3-19
+            0x00, 0x04, 0x00, 0x04, 0xc0, 0x00, 0x02, 0x01, // ipv4hint
+        ];
+
+        let got = SVCB::parse(&mut Cursor::new(&input[..])).expect("failed to parse");
+        assert_eq!(
+            got,
+            SVCB {
+                priority: 16,
+                target: "foo.example.org.".to_string(),
+                params: vec![
+                    SvcParam::Mandatory(vec![1, 4]),
+                    SvcParam::Alpn(vec!["h2".to_string(), "h3-19".to_string()]),
+                    SvcParam::Ipv4Hint(vec!["192.0.2.1".parse().unwrap()]),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_apl_parse() {
+        // 1:192.0.2.0/24 !1:192.0.2.128/25 2:2001:db8::/32
+        let input = [
+            0x00, 0x01, 0x18, 0x03, 0xc0, 0x00, 0x02, // 1:192.0.2.0/24
+            0x00, 0x01, 0x19, 0x84, 0xc0, 0x00, 0x02, 0x80, // !1:192.0.2.128/25
+            0x00, 0x02, 0x20, 0x04, 0x20, 0x01, 0x0d, 0xb8, // 2:2001:db8::/32
+        ];
+
+        let got = APL::parse(&mut Cursor::new(&input[..])).expect("failed to parse");
+        assert_eq!(
+            got,
+            APL(vec![
+                APLItem {
+                    negation: false,
+                    address: "192.0.2.0".parse().unwrap(),
+                    prefix: 24,
+                },
+                APLItem {
+                    negation: true,
+                    address: "192.0.2.128".parse().unwrap(),
+                    prefix: 25,
+                },
+                APLItem {
+                    negation: false,
+                    address: "2001:db8::".parse().unwrap(),
+                    prefix: 32,
+                },
+            ])
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use crate::resource::*;
+use num_traits::FromPrimitive;
 use std::net::SocketAddr;
 use std::time::Duration;
 use std::time::SystemTime;
@@ -400,6 +401,11 @@ pub enum Type {
     CNAME = 5,
     SOA = 6,
 
+    /// Well Known Services. See [rfc1035]
+    ///
+    /// [rfc1035]: https://datatracker.ietf.org/doc/html/rfc1035
+    WKS = 11,
+
     /// Domain name pointer. See [`util::reverse()`] to create a valid domain name from a IP address.
     ///
     /// [`util::reverse()`]: crate::util::reverse()
@@ -411,18 +417,157 @@ pub enum Type {
     /// Text strings.
     TXT = 16,
 
+    /// AFS Data Base location. See [rfc1183]
+    ///
+    /// [rfc1183]: https://datatracker.ietf.org/doc/html/rfc1183
+    AFSDB = 18,
+
+    /// X.25 PSDN address. See [rfc1183]
+    ///
+    /// [rfc1183]: https://datatracker.ietf.org/doc/html/rfc1183
+    X25 = 19,
+
+    /// ISDN address. See [rfc1183]
+    ///
+    /// [rfc1183]: https://datatracker.ietf.org/doc/html/rfc1183
+    ISDN = 20,
+
+    /// Route Through. See [rfc1183]
+    ///
+    /// [rfc1183]: https://datatracker.ietf.org/doc/html/rfc1183
+    RT = 21,
+
+    /// OSI Network Service Access Point address. See [rfc1706]
+    ///
+    /// [rfc1706]: https://datatracker.ietf.org/doc/html/rfc1706
+    NSAP = 22,
+
+    /// Domain name pointer for a NSAP address, similar to a PTR. See [rfc1706]
+    ///
+    /// [rfc1706]: https://datatracker.ietf.org/doc/html/rfc1706
+    #[allow(non_camel_case_types)]
+    #[strum(serialize = "NSAP-PTR")]
+    NSAP_PTR = 23,
+
+    /// Signature. Deprecated by [rfc3755] in favour of RRSIG, see [rfc2535].
+    ///
+    /// [rfc2535]: https://datatracker.ietf.org/doc/html/rfc2535
+    /// [rfc3755]: https://datatracker.ietf.org/doc/html/rfc3755
+    SIG = 24,
+
+    /// Key. Deprecated by [rfc3755] in favour of DNSKEY, see [rfc2535].
+    ///
+    /// [rfc2535]: https://datatracker.ietf.org/doc/html/rfc2535
+    /// [rfc3755]: https://datatracker.ietf.org/doc/html/rfc3755
+    KEY = 25,
+
+    /// Geographical Position, the predecessor to LOC. See [rfc1712].
+    ///
+    /// [rfc1712]: https://datatracker.ietf.org/doc/html/rfc1712
+    GPOS = 27,
+
     /// IPv6 Address.
     AAAA = 28,
 
     /// Server Selection
     SRV = 33,
 
+    /// ATM Address. See the ATM Forum's "ATM Name System Specification
+    /// Version 1.0" (af-dans-0152.000).
+    ATMA = 34,
+
+    /// Certificate. See [rfc4398]
+    ///
+    /// [rfc4398]: https://datatracker.ietf.org/doc/html/rfc4398
+    CERT = 37,
+
     /// EDNS(0) Opt type. See [rfc3225] and [rfc6891].
     ///
     /// [rfc3225]: https://datatracker.ietf.org/doc/html/rfc3225
     /// [rfc6891]: https://datatracker.ietf.org/doc/html/rfc6891
     OPT = 41,
 
+    /// Address Prefix List. See [rfc3123]
+    ///
+    /// [rfc3123]: https://datatracker.ietf.org/doc/html/rfc3123
+    APL = 42,
+
+    /// DHCP Information. See [rfc4701]
+    ///
+    /// [rfc4701]: https://datatracker.ietf.org/doc/html/rfc4701
+    DHCID = 49,
+
+    /// S/MIME certificate association, in the same format as a TLSA record.
+    /// See [rfc8162]
+    ///
+    /// [rfc8162]: https://datatracker.ietf.org/doc/html/rfc8162
+    SMIMEA = 53,
+
+    /// Host Identity Protocol. See [rfc8005]
+    ///
+    /// [rfc8005]: https://datatracker.ietf.org/doc/html/rfc8005
+    HIP = 55,
+
+    /// Child copy of a DS record, for the parent to publish. See [rfc7344]
+    ///
+    /// [rfc7344]: https://datatracker.ietf.org/doc/html/rfc7344
+    CDS = 59,
+
+    /// Child copy of a DNSKEY record, for the parent to publish. See [rfc7344]
+    ///
+    /// [rfc7344]: https://datatracker.ietf.org/doc/html/rfc7344
+    CDNSKEY = 60,
+
+    /// OpenPGP Public Key. See [rfc7929]
+    ///
+    /// [rfc7929]: https://datatracker.ietf.org/doc/html/rfc7929
+    OPENPGPKEY = 61,
+
+    /// Child-To-Parent Synchronization. See [rfc7477]
+    ///
+    /// [rfc7477]: https://datatracker.ietf.org/doc/html/rfc7477
+    CSYNC = 62,
+
+    /// Service Binding. See [rfc9460]
+    ///
+    /// [rfc9460]: https://datatracker.ietf.org/doc/html/rfc9460
+    SVCB = 64,
+
+    /// Service Binding for HTTPS. See [rfc9460]
+    ///
+    /// [rfc9460]: https://datatracker.ietf.org/doc/html/rfc9460
+    HTTPS = 65,
+
+    /// Node Identifier, for the Identifier-Locator Network Protocol (ILNP). See [rfc6742]
+    ///
+    /// [rfc6742]: https://datatracker.ietf.org/doc/html/rfc6742
+    NID = 104,
+
+    /// 32-bit Locator, for ILNPv4. See [rfc6742]
+    ///
+    /// [rfc6742]: https://datatracker.ietf.org/doc/html/rfc6742
+    L32 = 105,
+
+    /// 64-bit Locator, for ILNPv6. See [rfc6742]
+    ///
+    /// [rfc6742]: https://datatracker.ietf.org/doc/html/rfc6742
+    L64 = 106,
+
+    /// Locator FQDN, naming a subnetwork for ILNP. See [rfc6742]
+    ///
+    /// [rfc6742]: https://datatracker.ietf.org/doc/html/rfc6742
+    LP = 107,
+
+    /// 48-bit Extended Unique Identifier, such as a MAC address. See [rfc7043]
+    ///
+    /// [rfc7043]: https://datatracker.ietf.org/doc/html/rfc7043
+    EUI48 = 108,
+
+    /// 64-bit Extended Unique Identifier. See [rfc7043]
+    ///
+    /// [rfc7043]: https://datatracker.ietf.org/doc/html/rfc7043
+    EUI64 = 109,
+
     /// Sender Policy Framework. See [rfc4408]
     /// Discontinued in [rfc7208] due to widespread lack of support.
     ///
@@ -430,9 +575,38 @@ pub enum Type {
     /// [rfc7208]: https://datatracker.ietf.org/doc/html/rfc7208
     SPF = 99,
 
+    /// Transaction Key, used to agree a shared secret for TSIG. Only valid
+    /// in the additional section of a message. See [rfc2930]
+    ///
+    /// [rfc2930]: https://datatracker.ietf.org/doc/html/rfc2930
+    TKEY = 249,
+
+    /// Transaction Signature, authenticating a message. Only valid in the
+    /// additional section of a message. See [rfc8945]
+    ///
+    /// [rfc8945]: https://datatracker.ietf.org/doc/html/rfc8945
+    TSIG = 250,
+
     /// Any record type.
     /// Only valid as a Question Type.
     ANY = 255,
+
+    /// Uniform Resource Identifier. See [rfc7553]
+    ///
+    /// [rfc7553]: https://datatracker.ietf.org/doc/html/rfc7553
+    URI = 256,
+
+    /// Application Visibility and Control, describing a application, in the
+    /// same format as a TXT record. See the [IANA registry].
+    ///
+    /// [IANA registry]: https://www.iana.org/assignments/dns-parameters/dns-parameters.xhtml#dns-parameters-4
+    AVC = 258,
+
+    /// Digital Object Architecture, locating a digital object. See
+    /// [draft-durand-doa-over-dns].
+    ///
+    /// [draft-durand-doa-over-dns]: https://datatracker.ietf.org/doc/html/draft-durand-doa-over-dns
+    DOA = 259,
 }
 
 /// Defaults to [`Type::ANY`].
@@ -442,6 +616,32 @@ impl Default for Type {
     }
 }
 
+impl Type {
+    /// Returns the number of the type, as used on the wire, for example 1
+    /// for [`Type::A`].
+    pub fn to_u16(&self) -> u16 {
+        *self as u16
+    }
+
+    /// Returns the type with the given number, or None if the type is not
+    /// supported. Such types may still be held as a [`Resource::Unknown`].
+    pub fn from_u16(n: u16) -> Option<Type> {
+        FromPrimitive::from_u16(n)
+    }
+
+    /// Returns the name of the type with the given number, for example "A",
+    /// or "TYPE<n>" if the type is not supported, as described by
+    /// [rfc3597 section 5].
+    ///
+    /// [rfc3597 section 5]: https://datatracker.ietf.org/doc/html/rfc3597#section-5
+    pub fn name_of(n: u16) -> String {
+        match Type::from_u16(n) {
+            Some(r#type) => r#type.to_string(),
+            None => format!("TYPE{}", n),
+        }
+    }
+}
+
 /// Resource Record Class, for example Internet.
 #[derive(Copy, Clone, Debug, Display, EnumString, Eq, FromPrimitive, Hash, PartialEq)]
 #[repr(u16)]
@@ -507,10 +707,52 @@ pub enum Resource {
     // TODO per RFC 4408 a TXT record is allowed to contain multiple strings
     TXT(TXT),
     SPF(TXT),
+    AVC(TXT),
 
     MX(MX),
+    WKS(WKS),
+    AFSDB(AFSDB),
+    X25(X25),
+    ISDN(ISDN),
+    RT(RT),
+    NSAP(NSAP),
+    #[allow(non_camel_case_types)]
+    NSAP_PTR(PTR),
+    GPOS(GPOS),
     SOA(SOA),
     SRV(SRV),
+    ATMA(ATMA),
+    URI(URI),
+    APL(APL),
+    CERT(CERT),
+    DHCID(DHCID),
+    SMIMEA(TLSA),
+    HIP(HIP),
+    CDS(DS),
+    CDNSKEY(KEY),
+    OPENPGPKEY(OPENPGPKEY),
+    CSYNC(CSYNC),
+    SVCB(SVCB),
+    HTTPS(SVCB),
+    NID(NID),
+    L32(L32),
+    L64(L64),
+    LP(LP),
+    EUI48(EUI48),
+    EUI64(EUI64),
+    KEY(KEY),
+    SIG(SIG),
+    DOA(DOA),
+    TKEY(TKEY),
+    TSIG(TSIG),
+
+    /// A record of a type this crate does not support, holding the type's
+    /// number and its RDATA, as written in the generic form ([rfc3597]).
+    /// As there is no matching [`Type`], [`Resource::r#type`] returns
+    /// [`Type::Reserved`], use [`Resource::type_code`] instead.
+    ///
+    /// [rfc3597]: https://datatracker.ietf.org/doc/html/rfc3597
+    Unknown(u16, Vec<u8>),
 
     OPT,
 
@@ -529,11 +771,94 @@ impl Resource {
             Resource::PTR(_) => Type::PTR,
             Resource::TXT(_) => Type::TXT,
             Resource::MX(_) => Type::MX,
+            Resource::WKS(_) => Type::WKS,
+            Resource::AFSDB(_) => Type::AFSDB,
+            Resource::X25(_) => Type::X25,
+            Resource::ISDN(_) => Type::ISDN,
+            Resource::RT(_) => Type::RT,
+            Resource::NSAP(_) => Type::NSAP,
+            Resource::NSAP_PTR(_) => Type::NSAP_PTR,
+            Resource::GPOS(_) => Type::GPOS,
             Resource::SOA(_) => Type::SOA,
             Resource::SRV(_) => Type::SRV,
+            Resource::ATMA(_) => Type::ATMA,
             Resource::SPF(_) => Type::SPF,
+            Resource::AVC(_) => Type::AVC,
+            Resource::URI(_) => Type::URI,
+            Resource::APL(_) => Type::APL,
+            Resource::CERT(_) => Type::CERT,
+            Resource::DHCID(_) => Type::DHCID,
+            Resource::SMIMEA(_) => Type::SMIMEA,
+            Resource::HIP(_) => Type::HIP,
+            Resource::CDS(_) => Type::CDS,
+            Resource::CDNSKEY(_) => Type::CDNSKEY,
+            Resource::OPENPGPKEY(_) => Type::OPENPGPKEY,
+            Resource::CSYNC(_) => Type::CSYNC,
+            Resource::SVCB(_) => Type::SVCB,
+            Resource::HTTPS(_) => Type::HTTPS,
+            Resource::NID(_) => Type::NID,
+            Resource::L32(_) => Type::L32,
+            Resource::L64(_) => Type::L64,
+            Resource::LP(_) => Type::LP,
+            Resource::EUI48(_) => Type::EUI48,
+            Resource::EUI64(_) => Type::EUI64,
+            Resource::KEY(_) => Type::KEY,
+            Resource::SIG(_) => Type::SIG,
+            Resource::DOA(_) => Type::DOA,
+            Resource::TKEY(_) => Type::TKEY,
+            Resource::TSIG(_) => Type::TSIG,
+            Resource::Unknown(_, _) => Type::Reserved,
             Resource::OPT => Type::OPT,
             Resource::ANY => Type::ANY,
         }
     }
+
+    /// Returns the number of the type, as used on the wire. Unlike
+    /// [`Resource::r#type`] this is correct for [`Resource::Unknown`].
+    pub fn type_code(&self) -> u16 {
+        match self {
+            Resource::Unknown(r#type, _) => *r#type,
+            _ => self.r#type().to_u16(),
+        }
+    }
+
+    /// Returns the name of the type, for example "A", or "TYPE65280" for
+    /// [`Resource::Unknown`] records.
+    pub(crate) fn type_name(&self) -> String {
+        Type::name_of(self.type_code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Type;
+
+    #[test]
+    fn test_type_u16() {
+        let tests = vec![
+            (Type::A, 1),
+            (Type::NS, 2),
+            (Type::SOA, 6),
+            (Type::MX, 15),
+            (Type::TXT, 16),
+            (Type::AAAA, 28),
+            (Type::SRV, 33),
+            (Type::ATMA, 34),
+            (Type::HTTPS, 65),
+            (Type::ANY, 255),
+            (Type::URI, 256),
+            (Type::DOA, 259),
+        ];
+
+        for (r#type, n) in tests {
+            assert_eq!(r#type.to_u16(), n, "incorrect number for {}", r#type);
+            assert_eq!(Type::from_u16(n), Some(r#type), "incorrect type for {}", n);
+            assert_eq!(Type::name_of(n), r#type.to_string());
+        }
+
+        // Unsupported types.
+        assert_eq!(Type::from_u16(65280), None);
+        assert_eq!(Type::name_of(65280), "TYPE65280");
+        assert_eq!(Type::name_of(38), "TYPE38");
+    }
 }
@@ -1,5 +1,11 @@
+use crate::errors::NameError;
+use crate::resource::write_name;
+use crate::Record;
+use std::cmp::Ordering;
 use std::fmt::Write;
 use std::net::IpAddr;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
 use std::net::IpAddr::V4;
 use std::net::IpAddr::V6;
 
@@ -50,6 +56,239 @@ pub fn reverse(ip: IpAddr) -> String {
     }
 }
 
+/// Returns the IP address for this reverse DNS name, or None if the name is
+/// not a valid reverse DNS name. This is the opposite of [`reverse`], and the
+/// name may, or may not, end with a dot.
+///
+/// # Example
+///
+/// ```rust
+/// use rustdns::util::reverse_to_ip;
+///
+/// assert_eq!(reverse_to_ip("1.0.0.127.in-addr.arpa."), Some("127.0.0.1".parse().unwrap()));
+/// assert_eq!(reverse_to_ip("b.a.9.8.7.6.5.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa"), Some("2001:db8::567:89ab".parse().unwrap()));
+/// assert_eq!(reverse_to_ip("www.example.com."), None);
+/// ```
+pub fn reverse_to_ip(name: &str) -> Option<IpAddr> {
+    let name = name.strip_suffix('.').unwrap_or(name).to_ascii_lowercase();
+
+    if let Some(labels) = name.strip_suffix(".in-addr.arpa") {
+        let mut octets = [0_u8; 4];
+        let mut labels = labels.rsplit('.');
+        for octet in octets.iter_mut() {
+            let label = labels.next()?;
+            if label.is_empty() || (label.len() > 1 && label.starts_with('0')) {
+                return None;
+            }
+            *octet = label.parse().ok()?;
+        }
+
+        if labels.next().is_some() {
+            return None;
+        }
+
+        return Some(V4(Ipv4Addr::from(octets)));
+    }
+
+    if let Some(labels) = name.strip_suffix(".ip6.arpa") {
+        let mut octets = [0_u8; 16];
+        let mut labels = labels.rsplit('.');
+        for i in 0..32 {
+            let label = labels.next()?;
+            if label.len() != 1 {
+                return None;
+            }
+            let nibble = u8::from_str_radix(label, 16).ok()?;
+            octets[i / 2] |= if i % 2 == 0 { nibble << 4 } else { nibble };
+        }
+
+        if labels.next().is_some() {
+            return None;
+        }
+
+        return Some(V6(Ipv6Addr::from(octets)));
+    }
+
+    None
+}
+
+/// Returns the labels of a domain name, with any escapes (such as "\\." or
+/// "\\DDD") decoded. The root does not have any labels.
+pub(crate) fn name_labels(name: &str) -> Vec<Vec<u8>> {
+    let mut labels = Vec::new();
+    if name == "." {
+        return labels;
+    }
+
+    let mut label = Vec::new();
+
+    let mut bytes = name.bytes().peekable();
+    while let Some(b) = bytes.next() {
+        match b {
+            b'\\' => {
+                let digits: Vec<u8> = (0..3)
+                    .map_while(|_| bytes.next_if(u8::is_ascii_digit))
+                    .collect();
+                if digits.is_empty() {
+                    label.extend(bytes.next());
+                } else {
+                    // Invalid escapes, such as "\\1" or "\\300", are kept as is.
+                    match std::str::from_utf8(&digits).unwrap().parse::<u8>() {
+                        Ok(b) if digits.len() == 3 => label.push(b),
+                        _ => {
+                            label.push(b'\\');
+                            label.extend(digits);
+                        }
+                    }
+                }
+            }
+            b'.' => labels.push(std::mem::take(&mut label)),
+            _ => label.push(b),
+        }
+    }
+
+    // Any trailing dot does not create a extra label.
+    if !label.is_empty() {
+        labels.push(label);
+    }
+
+    labels
+}
+
+/// Returns the domain name in the canonical wire format, as defined by
+/// [rfc4034 section 6.2]. That is each label prefixed by its length, and
+/// lowercased, ending with the (empty) root label. Any escapes, such as
+/// "\\." or "\\DDD", are decoded. The name may, or may not, end with a dot.
+///
+/// # Example
+///
+/// ```rust
+/// use rustdns::util::canonical_name;
+///
+/// assert_eq!(canonical_name("Example.COM."), b"\x07example\x03com\x00");
+/// assert_eq!(canonical_name("."), b"\x00");
+/// ```
+///
+/// [rfc4034 section 6.2]: https://datatracker.ietf.org/doc/html/rfc4034#section-6.2
+pub fn canonical_name(name: &str) -> Vec<u8> {
+    let mut buf = Vec::new();
+    write_name(&mut buf, name, true);
+    buf
+}
+
+/// Compares two domain names in the canonical DNS name order, as defined by
+/// [rfc4034 section 6.1]. Names are compared one label at a time, starting
+/// with the right most label, ignoring case. The names may, or may not, end
+/// with a dot.
+///
+/// # Example
+///
+/// ```rust
+/// use rustdns::util::canonical_name_cmp;
+/// use std::cmp::Ordering;
+///
+/// assert_eq!(canonical_name_cmp("example.", "a.example."), Ordering::Less);
+/// assert_eq!(canonical_name_cmp("z.example.", "a.b.example."), Ordering::Greater);
+/// assert_eq!(canonical_name_cmp("A.example", "a.EXAMPLE."), Ordering::Equal);
+/// ```
+///
+/// [rfc4034 section 6.1]: https://datatracker.ietf.org/doc/html/rfc4034#section-6.1
+pub fn canonical_name_cmp(a: &str, b: &str) -> Ordering {
+    let a = name_labels(a);
+    let b = name_labels(b);
+
+    for (a, b) in a.iter().rev().zip(b.iter().rev()) {
+        let order = a.to_ascii_lowercase().cmp(&b.to_ascii_lowercase());
+        if order != Ordering::Equal {
+            return order;
+        }
+    }
+
+    // The name with fewer labels is first.
+    a.len().cmp(&b.len())
+}
+
+/// Sorts the records into the canonical order, as defined by [rfc4034 section 6].
+/// That is by owner name (see [`canonical_name_cmp`]), then type, and then
+/// the RDATA in its canonical wire format. This is the order required when
+/// signing records with DNSSEC.
+///
+/// [rfc4034 section 6]: https://datatracker.ietf.org/doc/html/rfc4034#section-6
+pub fn canonical_sort(records: &mut [Record]) {
+    records.sort_by(|a, b| {
+        canonical_name_cmp(&a.name, &b.name)
+            .then_with(|| a.resource.type_code().cmp(&b.resource.type_code()))
+            .then_with(|| {
+                a.resource
+                    .canonical_rdata()
+                    .cmp(&b.resource.canonical_rdata())
+            })
+    });
+}
+
+/// Checks that the domain name is a valid host name. That is, at most 255
+/// bytes in wire format, with labels of 1 to 63 bytes made up of letters,
+/// digits, hyphens and underscores, and no label starting or ending with a
+/// hyphen. If `allow_wildcard` is true, the left most label may be `*`. The
+/// name may, or may not, end with a dot, and "." is the valid root name.
+///
+/// # Example
+///
+/// ```rust
+/// use rustdns::util::validate_domain;
+/// use rustdns::NameError;
+///
+/// assert_eq!(validate_domain("www.example.com.", false), Ok(()));
+/// assert_eq!(validate_domain("*.example.com", true), Ok(()));
+/// assert_eq!(validate_domain("*.example.com", false), Err(NameError::Wildcard));
+/// assert_eq!(validate_domain("-a.example.com", false), Err(NameError::HyphenPlacement("-a".to_string())));
+/// ```
+pub fn validate_domain(name: &str, allow_wildcard: bool) -> Result<(), NameError> {
+    if name.is_empty() {
+        return Err(NameError::Empty);
+    }
+    if name == "." {
+        return Ok(());
+    }
+
+    let name = name.strip_suffix('.').unwrap_or(name);
+
+    // Each label is prefixed by its length, plus the final root label.
+    let len = name.len() + 2;
+    if len > 255 {
+        return Err(NameError::TooLong(len));
+    }
+
+    for (i, label) in name.split('.').enumerate() {
+        if label.is_empty() {
+            return Err(NameError::EmptyLabel);
+        }
+        if label.len() > 63 {
+            return Err(NameError::LabelTooLong(label.to_string()));
+        }
+        if label.contains('*') {
+            if allow_wildcard && i == 0 && label == "*" {
+                continue;
+            }
+            return Err(NameError::Wildcard);
+        }
+        if let Some(ch) = label
+            .chars()
+            .find(|c| !(c.is_ascii_alphanumeric() || *c == '-' || *c == '_'))
+        {
+            return Err(NameError::InvalidCharacter {
+                label: label.to_string(),
+                ch,
+            });
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Err(NameError::HyphenPlacement(label.to_string()));
+        }
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_reverse() {
     let tests: Vec<(IpAddr, &str)> = vec![
@@ -65,3 +304,207 @@ fn test_reverse() {
         assert_eq!(reverse(test.0), test.1);
     }
 }
+
+#[test]
+fn test_reverse_to_ip() {
+    let tests: Vec<(&str, Option<IpAddr>)> = vec![
+        ("1.0.0.127.in-addr.arpa.", Some("127.0.0.1".parse().unwrap())),
+        ("4.4.8.8.in-addr.arpa", Some("8.8.4.4".parse().unwrap())),
+        ("4.4.8.8.IN-ADDR.ARPA.", Some("8.8.4.4".parse().unwrap())),
+        (
+            "b.a.9.8.7.6.5.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa.",
+            Some("2001:db8::567:89ab".parse().unwrap()),
+        ),
+        // Invalid names
+        ("www.example.com.", None),
+        ("in-addr.arpa.", None),
+        ("4.8.8.in-addr.arpa.", None),       // Too short
+        ("1.4.4.8.8.in-addr.arpa.", None),   // Too long
+        ("256.4.8.8.in-addr.arpa.", None),   // Out of range
+        ("04.4.8.8.in-addr.arpa.", None),    // Leading zero
+        ("4..8.8.in-addr.arpa.", None),      // Empty label
+        ("1.0.0.2.ip6.arpa.", None),         // Too short
+        (
+            "ab.9.8.7.6.5.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa.",
+            None, // Not a single nibble
+        ),
+    ];
+
+    for (name, want) in tests {
+        assert_eq!(reverse_to_ip(name), want, "incorrect result for '{}'", name);
+    }
+
+    // Check we can round trip.
+    for ip in &["127.0.0.1", "192.0.2.255", "::1", "2001:db8::567:89ab"] {
+        let ip: IpAddr = ip.parse().unwrap();
+        assert_eq!(reverse_to_ip(&reverse(ip)), Some(ip));
+    }
+}
+
+#[test]
+fn test_canonical_sort() {
+    use crate::resource::MX;
+    use crate::{Class, Resource};
+    use std::time::Duration;
+
+    // The example ordering from rfc4034 section 6.1.
+    let want = vec![
+        "example.",
+        "a.example.",
+        "yljkjljk.a.example.",
+        "Z.a.example.",
+        "zABC.a.EXAMPLE.",
+        "z.example.",
+        "\\001.z.example.",
+        "*.z.example.",
+        "\\200.z.example.",
+    ];
+
+    let a = Resource::A("192.0.2.1".parse().unwrap());
+    let mut records: Vec<Record> = want
+        .iter()
+        .rev()
+        .map(|name| Record::new(name, Class::Internet, Duration::from_secs(3600), a.clone()))
+        .collect();
+    records.swap(1, 5);
+
+    canonical_sort(&mut records);
+    let got: Vec<&str> = records.iter().map(|r| r.name.as_str()).collect();
+    assert_eq!(got, want);
+
+    // Records with the same owner name are ordered by type, and then RDATA.
+    let mx = |preference, exchange: &str| {
+        Resource::MX(MX {
+            preference,
+            exchange: exchange.to_string(),
+        })
+    };
+    let want = vec![
+        Resource::A("192.0.2.1".parse().unwrap()),
+        Resource::A("192.0.2.10".parse().unwrap()),
+        Resource::NS("a.example.".to_string()),
+        Resource::NS("B.example.".to_string()),
+        mx(10, "b.example."),
+        mx(10, "mail.EXAMPLE."),
+        mx(20, "a.example."),
+        Resource::AAAA("2001:db8::1".parse().unwrap()),
+    ];
+
+    let mut records: Vec<Record> = want
+        .iter()
+        .rev()
+        .map(|resource| {
+            Record::new(
+                "example.",
+                Class::Internet,
+                Duration::from_secs(3600),
+                resource.clone(),
+            )
+        })
+        .collect();
+
+    canonical_sort(&mut records);
+    let got: Vec<Resource> = records.into_iter().map(|r| r.resource).collect();
+    assert_eq!(got, want);
+}
+
+#[test]
+fn test_canonical_name_cmp() {
+    let tests = vec![
+        ("example.", "example", Ordering::Equal),
+        ("EXAMPLE.", "example.", Ordering::Equal),
+        (".", "example.", Ordering::Less),
+        ("a.example.", "b.example.", Ordering::Less),
+        ("b.example.", "a.a.example.", Ordering::Greater),
+        ("a\\.b.example.", "a.b.example.", Ordering::Less), // "a.b" is a single label
+        ("\\065.example.", "a.example.", Ordering::Equal),
+    ];
+
+    for (a, b, want) in tests {
+        assert_eq!(
+            canonical_name_cmp(a, b),
+            want,
+            "incorrect result for '{}' '{}'",
+            a,
+            b
+        );
+    }
+}
+
+#[test]
+fn test_canonical_name() {
+    let tests: Vec<(&str, &[u8])> = vec![
+        ("example.com.", b"\x07example\x03com\x00"),
+        ("EXAMPLE.com", b"\x07example\x03com\x00"),
+        ("*.example.com.", b"\x01*\x07example\x03com\x00"),
+        // A escaped dot is part of the label, and escaped digits are decoded.
+        ("dns\\.admin.Example.com.", b"\x09dns.admin\x07example\x03com\x00"),
+        ("a\\065b.example.", b"\x03aab\x07example\x00"),
+        (".", b"\x00"),
+        ("", b"\x00"),
+    ];
+
+    for (name, want) in tests {
+        assert_eq!(
+            canonical_name(name),
+            want,
+            "incorrect result for '{}'",
+            name
+        );
+    }
+}
+
+#[test]
+fn test_validate_domain() {
+    let valid = vec![
+        ".",
+        "com",
+        "example.com.",
+        "www.example.com",
+        "_sip._tcp.example.com.",
+        "xn--bcher-kva.example",
+        "a1-b2.example",
+    ];
+    for name in valid {
+        assert_eq!(validate_domain(name, false), Ok(()), "{}", name);
+    }
+
+    assert_eq!(validate_domain("*.example.com.", true), Ok(()));
+
+    let long_label = "a".repeat(64);
+    let long_name = vec!["a".repeat(63); 4].join(".");
+    let long_label_name = format!("{}.example.", long_label);
+
+    let invalid = vec![
+        ("", NameError::Empty),
+        ("a..example", NameError::EmptyLabel),
+        (".example", NameError::EmptyLabel),
+        (&long_name, NameError::TooLong(257)),
+        (
+            &long_label_name,
+            NameError::LabelTooLong(long_label.clone()),
+        ),
+        (
+            "ex ample.com",
+            NameError::InvalidCharacter {
+                label: "ex ample".to_string(),
+                ch: ' ',
+            },
+        ),
+        (
+            "www.exa$mple.com",
+            NameError::InvalidCharacter {
+                label: "exa$mple".to_string(),
+                ch: '$',
+            },
+        ),
+        ("a-.example", NameError::HyphenPlacement("a-".to_string())),
+        ("*.example", NameError::Wildcard),
+    ];
+    for (name, want) in invalid {
+        assert_eq!(validate_domain(name, false), Err(want), "{}", name);
+    }
+
+    assert_eq!(validate_domain("www.*.example", true), Err(NameError::Wildcard));
+    assert_eq!(validate_domain("w*.example", true), Err(NameError::Wildcard));
+}
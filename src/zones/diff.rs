@@ -0,0 +1,180 @@
+// Compares two versions of a processed Zone File, for example to review a
+// change before it is deployed.
+
+use crate::Class;
+use crate::Record;
+use crate::Type;
+use std::collections::HashMap;
+
+/// The differences between two sets of records, as returned by [`zone_diff`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ZoneDiff {
+    /// Records only found in the new zone.
+    pub added: Vec<Record>,
+
+    /// Records only found in the old zone.
+    pub removed: Vec<Record>,
+
+    /// Records whose TTL, or RDATA, changed. Each is a pair of the old and
+    /// new record, which share the same owner name, class and type.
+    pub modified: Vec<(Record, Record)>,
+}
+
+impl ZoneDiff {
+    /// Returns true if the zones contain the same records.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+// Records are grouped by their owner name, class and type.
+type Key = (String, Class, Type);
+
+fn key(record: &Record) -> Key {
+    (
+        record.name.to_ascii_lowercase(),
+        record.class,
+        record.r#type(),
+    )
+}
+
+/// Returns the records added, removed, or modified between the `old` and `new`
+/// zones. The order of the records, and the case of the owner names, is
+/// ignored.
+///
+/// A removed and added record with the same owner name, class and type are
+/// reported as a modification. If there are more than one, they are paired
+/// in the order they appear.
+///
+/// ```
+/// use rustdns::zones::{parse_zone, zone_diff};
+///
+/// let old = parse_zone("$ORIGIN example.com.\n$TTL 3600\nwww IN A 192.0.2.1").unwrap();
+/// let new = parse_zone("$ORIGIN example.com.\n$TTL 3600\nwww IN A 192.0.2.2").unwrap();
+///
+/// let diff = zone_diff(&old, &new);
+/// assert_eq!(diff.modified, vec![(old[0].clone(), new[0].clone())]);
+/// assert!(diff.added.is_empty());
+/// assert!(diff.removed.is_empty());
+/// ```
+pub fn zone_diff(old: &[Record], new: &[Record]) -> ZoneDiff {
+    // The index of the old records, grouped by key, in the order they appear.
+    let mut old_keys: HashMap<Key, Vec<usize>> = HashMap::new();
+    for (i, record) in old.iter().enumerate() {
+        old_keys.entry(key(record)).or_default().push(i);
+    }
+
+    // First match any records that are unchanged.
+    let mut matched = vec![false; old.len()];
+    let mut unmatched = Vec::new();
+    for record in new {
+        let found = old_keys.get(&key(record)).and_then(|indexes| {
+            indexes.iter().find(|&&i| {
+                !matched[i] && old[i].ttl == record.ttl && old[i].resource == record.resource
+            })
+        });
+
+        match found {
+            Some(&i) => matched[i] = true,
+            None => unmatched.push(record),
+        }
+    }
+
+    // Then pair up the remaining records with the same key.
+    let mut diff = ZoneDiff::default();
+    for record in unmatched {
+        let found = old_keys
+            .get(&key(record))
+            .and_then(|indexes| indexes.iter().find(|&&i| !matched[i]));
+
+        match found {
+            Some(&i) => {
+                matched[i] = true;
+                diff.modified.push((old[i].clone(), record.clone()));
+            }
+            None => diff.added.push(record.clone()),
+        }
+    }
+
+    diff.removed = old
+        .iter()
+        .zip(matched)
+        .filter(|(_, matched)| !matched)
+        .map(|(record, _)| record.clone())
+        .collect();
+
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zones::parse_zone;
+    use crate::zones::zone_diff;
+    use crate::zones::ZoneDiff;
+    use crate::Record;
+    use pretty_assertions::assert_eq;
+
+    // Example from https://en.wikipedia.org/wiki/Zone_file
+    static INPUT: &str = "$ORIGIN example.com.
+        $TTL 3600
+        example.com.  IN  SOA   ns.example.com. username.example.com. ( 2020091025 7200 3600 1209600 3600 )
+        example.com.  IN  NS    ns
+        example.com.  IN  NS    ns.somewhere.example.
+        example.com.  IN  MX    10 mail.example.com.
+        @             IN  MX    20 mail2.example.com.
+        @             IN  MX    50 mail3
+        example.com.  IN  A     192.0.2.1
+                      IN  AAAA  2001:db8:10::1
+        ns            IN  A     192.0.2.2
+                      IN  AAAA  2001:db8:10::2
+        www           IN  CNAME example.com.
+        wwwtest       IN  CNAME www
+        mail          IN  A     192.0.2.3
+        mail2         IN  A     192.0.2.4
+        mail3         IN  A     192.0.2.5";
+
+    // Returns the record parsed from a single absolute line.
+    fn record(line: &str) -> Record {
+        parse_zone(&format!("$TTL 3600\n{}", line)).unwrap()[0].clone()
+    }
+
+    #[test]
+    fn test_zone_diff() {
+        let old = parse_zone(INPUT).expect("failed to parse");
+
+        // Unchanged, even if the records are reordered, or differ in case.
+        let mut reordered = old.clone();
+        reordered.reverse();
+        reordered[0].name = reordered[0].name.to_ascii_uppercase();
+        assert_eq!(zone_diff(&old, &reordered), ZoneDiff::default());
+        assert!(zone_diff(&old, &old).is_empty());
+
+        // A single changed A record.
+        let input = INPUT.replace("192.0.2.3", "192.0.2.33");
+        let new = parse_zone(&input).expect("failed to parse");
+
+        assert_eq!(
+            zone_diff(&old, &new),
+            ZoneDiff {
+                modified: vec![(
+                    record("mail.example.com. IN A 192.0.2.3"),
+                    record("mail.example.com. IN A 192.0.2.33"),
+                )],
+                ..Default::default()
+            }
+        );
+
+        // A added and removed record, of different types.
+        let input = INPUT.replace("MX    50 mail3", "TXT   \"v=spf1 -all\"");
+        let new = parse_zone(&input).expect("failed to parse");
+
+        assert_eq!(
+            zone_diff(&old, &new),
+            ZoneDiff {
+                added: vec![record("example.com. IN TXT \"v=spf1 -all\"")],
+                removed: vec![record("example.com. IN MX 50 mail3.example.com.")],
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,247 @@
+use crate::zones::parser::Rule;
+use crate::Type;
+use pest::error::ErrorVariant;
+use pest::error::InputLocation;
+use pest::Position;
+use pest::Span;
+use std::str::FromStr;
+use thiserror::Error;
+
+/// Errors returned while parsing, or processing, a zone file.
+#[derive(Error, Debug, PartialEq)]
+pub enum ZoneParseError {
+    /// The input did not match the zone file grammar.
+    #[error(transparent)]
+    SyntaxError(Box<pest_consume::Error<Rule>>),
+
+    #[error("invalid origin '{0}': must be a absolute domain")]
+    InvalidOrigin(String),
+
+    #[error("record '{0}' has no TTL, and no default TTL was set")]
+    MissingTtl(String),
+
+    #[error("record '{0}' has no class, and no previous class was set")]
+    MissingClass(String),
+
+    #[error("record has no name, and no previous name was set")]
+    MissingName,
+
+    #[error("relative name '{0}' found, but no origin was set")]
+    MissingOrigin(String),
+
+    #[error("invalid rname '{0}': must contain a mailbox and domain")]
+    InvalidRname(String),
+
+    /// A SOA mname or rname is missing its trailing dot, which is only
+    /// returned if [`ZoneOptions::strict_soa_names`] is set.
+    ///
+    /// [`ZoneOptions::strict_soa_names`]: crate::zones::ZoneOptions::strict_soa_names
+    #[error("SOA {field} '{name}' is relative, a trailing dot may be missing")]
+    RelativeSoaName { field: &'static str, name: String },
+
+    #[error("zone is {size} bytes, but the limit is {limit} bytes")]
+    TooLarge { size: usize, limit: usize },
+
+    #[error("zone has more than the limit of {limit} records")]
+    TooManyRecords { limit: usize },
+
+    /// The zone could not be read, for example the file does not exist, or
+    /// is not valid UTF-8.
+    #[error("failed to read zone: {0}")]
+    ReadError(String),
+}
+
+impl From<std::io::Error> for ZoneParseError {
+    fn from(err: std::io::Error) -> Self {
+        ZoneParseError::ReadError(err.to_string())
+    }
+}
+
+impl From<pest_consume::Error<Rule>> for ZoneParseError {
+    fn from(err: pest_consume::Error<Rule>) -> Self {
+        ZoneParseError::SyntaxError(Box::new(err))
+    }
+}
+
+/// Rewrites a error from the zone grammar, which lists the names of the rules
+/// that were expected, into a message for the reader of the zone file. For
+/// example "expected ip4 or generic_marker" becomes "expected a IPv4 address
+/// in A record RDATA". The `rule` is the rule `input` was parsed with.
+pub(crate) fn describe_error(
+    err: pest_consume::Error<Rule>,
+    rule: Rule,
+    input: &str,
+) -> pest_consume::Error<Rule> {
+    let positives = match &err.variant {
+        ErrorVariant::ParsingError { positives, .. } => positives,
+        ErrorVariant::CustomError { .. } => return err,
+    };
+
+    let (start, end) = match err.location {
+        InputLocation::Pos(pos) => (pos, pos),
+        InputLocation::Span(span) => span,
+    };
+
+    // Keep the rules that mean something to the reader, in the order they
+    // were tried. The RDATA is only mentioned if the error is within it.
+    let mut expected = Vec::new();
+    let mut in_rdata = true;
+    for rule in positives {
+        let description = match describe(*rule) {
+            Some(description) => description,
+            None => continue,
+        };
+
+        if matches!(rule, Rule::EOI | Rule::class | Rule::resource_generic) {
+            in_rdata = false;
+        }
+
+        if !expected.contains(&description) {
+            expected.push(description);
+        }
+    }
+
+    let (r#type, field) = match record_type(rule, &input[..start]) {
+        Some((r#type, index)) if in_rdata => (Some(r#type), rdata_field(r#type, index)),
+        _ => (None, None),
+    };
+
+    let message = match (expected.split_last(), r#type, field) {
+        (None, None, _) => return err,
+        (None, Some(r#type), _) => format!("invalid {} record RDATA", r#type),
+        // Once the type is known, name the field that is wrong, for example
+        // "expected the MX preference (a number)".
+        (Some(_), Some(r#type), Some(field)) => {
+            format!(
+                "expected the {} {} ({})",
+                r#type,
+                field,
+                expected.join(" or ")
+            )
+        }
+        (Some((last, rest)), r#type, _) => {
+            let mut message = "expected ".to_string();
+            if !rest.is_empty() {
+                message += &rest.join(", ");
+                message += " or ";
+            }
+            message += last;
+
+            if let Some(r#type) = r#type {
+                message += &format!(" in {} record RDATA", r#type);
+            }
+            message
+        }
+    };
+
+    let variant = ErrorVariant::CustomError { message };
+    if start == end {
+        match Position::new(input, start) {
+            Some(pos) => pest_consume::Error::new_from_pos(variant, pos),
+            None => err,
+        }
+    } else {
+        match Span::new(input, start, end) {
+            Some(span) => pest_consume::Error::new_from_span(variant, span),
+            None => err,
+        }
+    }
+}
+
+// Returns what is expected by the rule, for use in a error message, or None
+// if the rule is only part of a larger one.
+fn describe(rule: Rule) -> Option<&'static str> {
+    Some(match rule {
+        Rule::domain => "a domain name",
+        Rule::string => "a mailbox name",
+        Rule::quoted => "a quoted string",
+        Rule::char_string => "a character string",
+        Rule::ip4 => "a IPv4 address",
+        Rule::ip6 => "a IPv6 address",
+        Rule::locator64 => "a 64-bit locator",
+        Rule::record_type => "a record type",
+        Rule::cert_type => "a certificate type",
+        Rule::eui => "a EUI",
+        Rule::nsap => "a NSAP address",
+        Rule::atma_address => "a ATM address",
+        Rule::wks_protocol => "a protocol",
+        Rule::wks_service => "a service",
+        Rule::base64 | Rule::base64_field => "base64 data",
+        Rule::sized_data => "a size followed by base64 data",
+        Rule::tsig_error => "a error code",
+        Rule::hex => "hex data",
+        Rule::hip_hit => "a HIT",
+        Rule::hip_key => "a public key",
+        Rule::apl_item => "a address prefix",
+        Rule::svc_param | Rule::svc_key => "a service parameter",
+        Rule::number => "a number",
+        Rule::duration => "a TTL",
+        Rule::class => "a class",
+        // Tried first, whenever a record type and RDATA are expected.
+        Rule::resource_generic => "a record type followed by its RDATA",
+        Rule::EOI => "the end of the record",
+        Rule::origin | Rule::ttl | Rule::record => "a record",
+        _ => return None,
+    })
+}
+
+// Returns the type of the record being parsed when the error occurred, found
+// by looking back over the record up to the error, along with the number of
+// RDATA fields before the error.
+fn record_type(rule: Rule, before: &str) -> Option<(Type, usize)> {
+    let line = match before.rfind('\n') {
+        Some(i) => &before[i + 1..],
+        None => before,
+    };
+
+    let mut words = line.split(|c: char| c.is_ascii_whitespace() || c == '(' || c == ')');
+
+    // A line that doesn't start with whitespace starts with a owner name,
+    // which may itself look like a type, for example "mx IN A 192.0.2.1".
+    if rule != Rule::single_resource && !line.starts_with(|c: char| c.is_ascii_whitespace()) {
+        words.next();
+    }
+
+    let mut words = words.filter(|word| !word.is_empty());
+    let r#type = words.find_map(|word| Type::from_str(&word.to_ascii_uppercase()).ok())?;
+
+    Some((r#type, words.count()))
+}
+
+// Returns the name of the RDATA field at `index`, for the types whose RDATA
+// is a fixed list of fields, each a single word.
+fn rdata_field(r#type: Type, index: usize) -> Option<&'static str> {
+    let fields: &[&str] = match r#type {
+        Type::MX => &["preference", "exchange"],
+        Type::AFSDB => &["subtype", "hostname"],
+        Type::RT => &["preference", "intermediate host"],
+        Type::SOA => &[
+            "mname", "rname", "serial", "refresh", "retry", "expire", "minimum",
+        ],
+        Type::SRV => &["priority", "weight", "port", "target"],
+        Type::URI => &["priority", "weight", "target"],
+        Type::CERT => &["certificate type", "key tag", "algorithm", "certificate"],
+        Type::KEY | Type::CDNSKEY => &["flags", "protocol", "algorithm", "public key"],
+        Type::CDS => &["key tag", "algorithm", "digest type", "digest"],
+        Type::SMIMEA => &["usage", "selector", "matching type", "certificate data"],
+        Type::CSYNC => &["serial", "flags"],
+        Type::SVCB | Type::HTTPS => &["priority", "target"],
+        Type::NID => &["preference", "node ID"],
+        Type::L32 | Type::L64 => &["preference", "locator"],
+        Type::LP => &["preference", "FQDN"],
+        Type::SIG => &[
+            "type covered",
+            "algorithm",
+            "labels",
+            "original TTL",
+            "expiration",
+            "inception",
+            "key tag",
+            "signer name",
+            "signature",
+        ],
+        _ => return None,
+    };
+
+    fields.get(index).copied()
+}
@@ -0,0 +1,156 @@
+// Associates NS records with their glue, the address records for the name
+// servers, as needed to build a referral.
+
+use crate::Record;
+use crate::Resource;
+use std::collections::HashMap;
+
+/// A NS record, and the address records for its server, as returned by
+/// [`delegations`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Delegation {
+    /// The NS record, which is returned in the authority section of a referral.
+    pub ns: Record,
+
+    /// The A and AAAA records for the server named by the NS record, which
+    /// are returned in the additional section. This is empty if the server
+    /// is not within the zone.
+    pub glue: Vec<Record>,
+}
+
+/// Returns each NS record, along with the A and AAAA records (glue) for the
+/// server it names, in the order they appear. Names are compared ignoring
+/// case.
+///
+/// ```
+/// use rustdns::zones::{delegations, parse_zone};
+///
+/// let records = parse_zone("$ORIGIN example.com.
+///     $TTL 3600
+///     sub     IN  NS  ns.sub
+///     ns.sub  IN  A   192.0.2.1").unwrap();
+///
+/// let delegations = delegations(&records);
+/// assert_eq!(delegations[0].ns, records[0]);
+/// assert_eq!(delegations[0].glue, vec![records[1].clone()]);
+/// ```
+pub fn delegations(records: &[Record]) -> Vec<Delegation> {
+    // The address records, grouped by their owner name.
+    let mut addresses: HashMap<String, Vec<&Record>> = HashMap::new();
+    for record in records {
+        if let Resource::A(_) | Resource::AAAA(_) = record.resource {
+            addresses.entry(key(&record.name)).or_default().push(record);
+        }
+    }
+
+    records
+        .iter()
+        .filter_map(|record| match &record.resource {
+            Resource::NS(server) => Some(Delegation {
+                ns: record.clone(),
+                glue: addresses
+                    .get(&key(server))
+                    .map(|glue| glue.iter().map(|&record| record.clone()).collect())
+                    .unwrap_or_default(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn key(name: &str) -> String {
+    name.trim_end_matches('.').to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zones::delegations;
+    use crate::zones::parse_zone;
+    use crate::zones::Delegation;
+    use crate::Class;
+    use crate::Record;
+    use crate::Resource;
+    use core::time::Duration;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_delegations() {
+        // Example from https://datatracker.ietf.org/doc/html/rfc1035#section-5.3
+        // with a $TTL added, as the original relies on the TTL being implied.
+        let records = parse_zone(
+            "$ORIGIN ISI.EDU.
+            $TTL 3600
+            @   IN  SOA     VENERA      Action\\.domains (
+                                             20     ; SERIAL
+                                             7200   ; REFRESH
+                                             600    ; RETRY
+                                             3600000; EXPIRE
+                                             60)    ; MINIMUM
+
+                    NS      A.ISI.EDU.
+                    NS      VENERA
+                    NS      VAXA
+                    MX      10      VENERA
+                    MX      20      VAXA
+
+            A       A       26.3.0.103
+
+            VENERA  A       10.1.0.52
+                    A       128.9.0.32
+
+            VAXA    A       10.2.0.27
+                    A       128.9.0.33",
+        )
+        .expect("failed to parse");
+
+        let record = |name: &str, resource| Record {
+            name: name.to_string(),
+            class: Class::Internet,
+            ttl: Duration::new(3600, 0),
+            resource,
+        };
+        let ns = |server: &str| record("ISI.EDU", Resource::NS(server.to_string()));
+        let a = |name: &str, ip: &str| record(name, Resource::A(ip.parse().unwrap()));
+
+        assert_eq!(
+            delegations(&records),
+            vec![
+                Delegation {
+                    ns: ns("A.ISI.EDU"),
+                    glue: vec![a("A.ISI.EDU", "26.3.0.103")],
+                },
+                Delegation {
+                    ns: ns("VENERA.ISI.EDU"),
+                    glue: vec![
+                        a("VENERA.ISI.EDU", "10.1.0.52"),
+                        a("VENERA.ISI.EDU", "128.9.0.32"),
+                    ],
+                },
+                Delegation {
+                    ns: ns("VAXA.ISI.EDU"),
+                    glue: vec![
+                        a("VAXA.ISI.EDU", "10.2.0.27"),
+                        a("VAXA.ISI.EDU", "128.9.0.33"),
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_delegations_out_of_zone() {
+        let records = parse_zone(
+            "$ORIGIN example.com.
+            $TTL 3600
+            sub     IN  NS    NS.Sub
+            sub     IN  NS    ns.other.example.
+            ns.sub  IN  AAAA  2001:db8::1",
+        )
+        .expect("failed to parse");
+
+        let got = delegations(&records);
+        assert_eq!(got.len(), 2);
+        assert_eq!(got[0].glue, vec![records[2].clone()]);
+        assert_eq!(got[1].glue, vec![]);
+    }
+}
@@ -0,0 +1,504 @@
+// Looks for common misconfigurations in a processed Zone File. Unlike
+// validation, these are only warnings, as the zone may still be served.
+
+use crate::zones::Location;
+use crate::Record;
+use crate::Class;
+use crate::Resource;
+use crate::util::canonical_name;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fmt;
+use std::time::Duration;
+use thiserror::Error;
+
+/// How serious a [`Lint`] is.
+#[derive(Copy, Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    /// Probably intended, but worth checking.
+    Info,
+
+    /// Likely to cause problems for some resolvers.
+    Warning,
+
+    /// Not allowed by the RFCs, but may still work in practice.
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// The kind of problem found by [`lint_zone`].
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum LintKind {
+    /// A NS record names a server within the zone, but there is no A or AAAA
+    /// record (glue) for it, so the server can't be found.
+    #[error("'{name}' has a NS record for '{server}', but '{server}' has no A or AAAA record")]
+    MissingGlue { name: String, server: String },
+
+    /// A MX record points to a CNAME, which is not allowed by
+    /// [rfc2181 section 10.3].
+    ///
+    /// [rfc2181 section 10.3]: https://datatracker.ietf.org/doc/html/rfc2181#section-10.3
+    #[error("'{name}' has a MX record for '{exchange}', but '{exchange}' is a CNAME")]
+    MxToCname { name: String, exchange: String },
+
+    /// The SOA minimum (the negative caching TTL) is larger than the refresh
+    /// interval, so negative answers may be cached longer than expected.
+    #[error("SOA minimum ({}s) is larger than the refresh ({}s)", minimum.as_secs(), refresh.as_secs())]
+    SoaMinimumAboveRefresh {
+        minimum: Duration,
+        refresh: Duration,
+    },
+
+    /// The name repeats its own suffix, for example "www.example.com.example.com",
+    /// which usually means a trailing dot was missing from a absolute name.
+    #[error("'{0}' repeats its suffix, a trailing dot may be missing")]
+    MissingTrailingDot(String),
+
+    /// The record is identical to one defined earlier, with the same name,
+    /// class, TTL and RDATA. This is redundant, and often a copy-paste error.
+    #[error("'{name}' has a duplicate {} record, first defined at {first}", r#type)]
+    DuplicateRecord {
+        name: String,
+        r#type: String,
+        first: Location,
+    },
+}
+
+/// A non-fatal problem found by [`lint_zone`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Lint {
+    pub severity: Severity,
+
+    /// Where the record with the problem was defined.
+    pub location: Location,
+
+    pub kind: LintKind,
+}
+
+impl fmt::Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // "3:1: warning: 'mail.example.com.example.com' repeats its suffix, ..."
+        write!(f, "{}: {}: {}", self.location, self.severity, self.kind)
+    }
+}
+
+/// Checks the records for common misconfigurations, returning any problems
+/// found, in the order the records were defined. Unlike [`validate_zone`]
+/// these are warnings, and the zone may still work.
+///
+/// The records and their locations are as returned by [`parse_zone_with_locations`].
+///
+/// ```
+/// use rustdns::zones::{lint_zone, parse_zone_with_locations, Severity};
+///
+/// let records = parse_zone_with_locations("$ORIGIN example.com.
+///     $TTL 3600
+///     @  IN  NS  ns.example.com").unwrap();
+///
+/// let lints = lint_zone(&records);
+/// assert_eq!(lints[0].severity, Severity::Warning);
+/// assert_eq!(lints[0].to_string(), "3:5: warning: 'ns.example.com.example.com' repeats its suffix, a trailing dot may be missing");
+/// ```
+///
+/// [`parse_zone_with_locations`]: crate::zones::parse_zone_with_locations
+/// [`validate_zone`]: crate::zones::validate_zone
+pub fn lint_zone(records: &[(Record, Location)]) -> Vec<Lint> {
+    let mut addresses = HashSet::new();
+    let mut cnames = HashSet::new();
+    let mut apexes = Vec::new();
+    for (record, _) in records {
+        let name = key(&record.name);
+        match record.resource {
+            Resource::A(_) | Resource::AAAA(_) => {
+                addresses.insert(name);
+            }
+            Resource::CNAME(_) => {
+                cnames.insert(name);
+            }
+            Resource::SOA(_) => apexes.push(name),
+            _ => (),
+        }
+    }
+
+    let mut lints = Vec::new();
+    let mut seen = HashMap::new();
+    for (record, location) in records {
+        let mut lint = |severity, kind| {
+            lints.push(Lint {
+                severity,
+                location: *location,
+                kind,
+            })
+        };
+
+        let names = std::iter::once(record.name.as_str())
+            .chain(target(&record.resource))
+            .chain(soa_names(&record.resource));
+        for name in names {
+            if repeats_suffix(name) {
+                lint(
+                    Severity::Warning,
+                    LintKind::MissingTrailingDot(name.to_owned()),
+                );
+            }
+        }
+
+        match &record.resource {
+            Resource::NS(server) => {
+                let server_lower = key(server);
+
+                // Glue is only needed for servers within the zone, or the
+                // delegated zone.
+                let in_zone = std::iter::once(key(&record.name))
+                    .chain(apexes.iter().cloned())
+                    .any(|zone| is_subdomain(&server_lower, &zone));
+
+                if in_zone && !addresses.contains(&server_lower) {
+                    lint(
+                        Severity::Warning,
+                        LintKind::MissingGlue {
+                            name: record.name.to_owned(),
+                            server: server.to_owned(),
+                        },
+                    );
+                }
+            }
+            Resource::MX(mx) if cnames.contains(&key(&mx.exchange)) => {
+                lint(
+                    Severity::Error,
+                    LintKind::MxToCname {
+                        name: record.name.to_owned(),
+                        exchange: mx.exchange.to_owned(),
+                    },
+                );
+            }
+            Resource::SOA(soa) if soa.minimum > soa.refresh => {
+                lint(
+                    Severity::Info,
+                    LintKind::SoaMinimumAboveRefresh {
+                        minimum: soa.minimum,
+                        refresh: soa.refresh,
+                    },
+                );
+            }
+            _ => (),
+        }
+
+        if let Some(first) = seen.get(&duplicate_key(record)) {
+            lint(
+                Severity::Warning,
+                LintKind::DuplicateRecord {
+                    name: record.name.to_owned(),
+                    r#type: record.resource.type_name(),
+                    first: *first,
+                },
+            );
+        } else {
+            seen.insert(duplicate_key(record), *location);
+        }
+    }
+
+    lints
+}
+
+// Returns the record in its canonical form, so records that only differ in
+// the case of their names, or a trailing dot, are treated as duplicates.
+pub(crate) fn duplicate_key(record: &Record) -> (Vec<u8>, Class, Duration, u16, Vec<u8>) {
+    (
+        canonical_name(&record.name),
+        record.class,
+        record.ttl,
+        record.resource.type_code(),
+        record.resource.canonical_rdata(),
+    )
+}
+
+// Returns the name lowercased, without any trailing dot, so absolute names
+// compare equal to the names returned by parse_zone.
+fn key(name: &str) -> String {
+    name.trim_end_matches('.').to_ascii_lowercase()
+}
+
+// Returns the name the resource points to, if any.
+fn target(resource: &Resource) -> Option<&str> {
+    Some(match resource {
+        Resource::CNAME(name) | Resource::NS(name) | Resource::PTR(name) => name,
+        Resource::MX(mx) => &mx.exchange,
+        Resource::AFSDB(afsdb) => &afsdb.hostname,
+        Resource::RT(rt) => &rt.host,
+        Resource::SRV(srv) => &srv.name,
+        Resource::LP(lp) => &lp.fqdn,
+        Resource::SVCB(svcb) | Resource::HTTPS(svcb) => &svcb.target,
+        _ => return None,
+    })
+}
+
+// Returns the mname, and the domain of the rname (without the mailbox), if
+// the resource is a SOA.
+fn soa_names(resource: &Resource) -> Vec<&str> {
+    match resource {
+        Resource::SOA(soa) => {
+            let rname = match soa.rname.rsplit_once('@') {
+                Some((_, domain)) => domain,
+                None => &soa.rname,
+            };
+            vec![&soa.mname, rname]
+        }
+        _ => Vec::new(),
+    }
+}
+
+// Returns true if the name is equal to, or is a subdomain of, the zone.
+pub(crate) fn is_subdomain(name: &str, zone: &str) -> bool {
+    // Every name is within the root zone.
+    if zone.is_empty() {
+        return true;
+    }
+
+    match name.strip_suffix(zone) {
+        Some(prefix) => prefix.is_empty() || prefix.ends_with('.'),
+        None => false,
+    }
+}
+
+// Returns true if the name ends with the same (two or more) labels repeated
+// twice, for example "www.example.com.example.com".
+fn repeats_suffix(name: &str) -> bool {
+    let labels: Vec<String> = name.split('.').map(|l| l.to_ascii_lowercase()).collect();
+    let len = labels.len();
+
+    (2..=len / 2).any(|n| labels[len - n..] == labels[len - 2 * n..len - n])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zones::lint_zone;
+    use crate::zones::parse_zone_with_locations;
+    use crate::zones::Lint;
+    use crate::zones::LintKind;
+    use crate::zones::Location;
+    use crate::zones::Severity;
+    use crate::Class;
+    use crate::Record;
+    use crate::Resource;
+    use crate::Type;
+    use core::time::Duration;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_lint_zone() {
+        let records = parse_zone_with_locations(
+            "$ORIGIN example.com.
+            $TTL 3600
+            @     IN  SOA    ns admin ( 1 7200 3600 1209600 3600 )
+            @     IN  NS     ns
+            @     IN  NS     ns.other.example.
+            @     IN  MX     10 mail
+            ns    IN  A      192.0.2.1
+            mail  IN  A      192.0.2.2
+            www   IN  CNAME  @",
+        )
+        .expect("failed to parse");
+
+        assert_eq!(lint_zone(&records), vec![]);
+    }
+
+    #[test]
+    fn test_lint_zone_glue() {
+        let records = parse_zone_with_locations(
+            "$ORIGIN example.com.
+            $TTL 3600
+            @        IN  SOA  ns admin ( 1 7200 3600 1209600 3600 )
+            @        IN  NS   @
+            @        IN  NS   ns.example.com.
+            @        IN  NS   ns.other.example.
+            ns       IN  A    192.0.2.1
+            sub      IN  NS   ns.sub
+            sub      IN  NS   ns.example.com.
+            sub      IN  NS   ns.other.example.
+            ns.sub   IN  AAAA 2001:db8::1",
+        )
+        .expect("failed to parse");
+
+        // The apex itself has no address, but the other servers within the
+        // zone have glue, and "ns.other.example" is outside the zone.
+        assert_eq!(
+            lint_zone(&records),
+            vec![Lint {
+                severity: Severity::Warning,
+                location: Location { line: 4, column: 13 },
+                kind: LintKind::MissingGlue {
+                    name: "example.com".to_string(),
+                    server: "example.com".to_string(),
+                },
+            }]
+        );
+
+        // Absolute names, with the trailing dot, are treated the same.
+        let location = Location { line: 1, column: 1 };
+        let ttl = Duration::new(3600, 0);
+        let soa = "ns.example.com. admin.example.com. 1 7200 3600 1209600 3600";
+        let records = vec![
+            (Record::new("example.com", Class::Internet, ttl, Resource::from_str(Type::SOA, soa).unwrap()), location),
+            (Record::new("example.com", Class::Internet, ttl, Resource::NS("ns1.example.com.".to_string())), location),
+            (Record::new("example.com", Class::Internet, ttl, Resource::NS("ns2.example.com.".to_string())), location),
+            (Record::new("ns1.example.com", Class::Internet, ttl, Resource::A("192.0.2.1".parse().unwrap())), location),
+        ];
+        assert_eq!(
+            lint_zone(&records),
+            vec![Lint {
+                severity: Severity::Warning,
+                location,
+                kind: LintKind::MissingGlue {
+                    name: "example.com".to_string(),
+                    server: "ns2.example.com.".to_string(),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_lint_zone_soa_names() {
+        // Absolute, or relative, SOA names are fine.
+        let records = parse_zone_with_locations(
+            "$ORIGIN example.com.
+            $TTL 3600
+            @     IN  SOA    ns.example.com. admin.example.com. ( 1 7200 3600 1209600 3600 )
+            sub   IN  SOA    ns admin ( 1 7200 3600 1209600 3600 )",
+        )
+        .expect("failed to parse");
+        assert_eq!(lint_zone(&records), vec![]);
+
+        // But absolute names missing the trailing dot are probably a mistake.
+        let records = parse_zone_with_locations(
+            "$ORIGIN example.com.
+            $TTL 3600
+            @     IN  SOA    ns.example.com admin.example.com ( 1 7200 3600 1209600 3600 )",
+        )
+        .expect("failed to parse");
+
+        let lint = |name: &str| Lint {
+            severity: Severity::Warning,
+            location: Location { line: 3, column: 13 },
+            kind: LintKind::MissingTrailingDot(name.to_string()),
+        };
+        assert_eq!(
+            lint_zone(&records),
+            vec![
+                lint("ns.example.com.example.com"),
+                lint("example.com.example.com"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_zone_duplicates() {
+        let records = parse_zone_with_locations(
+            "$ORIGIN example.com.
+            $TTL 3600
+            www   IN  A      192.0.2.1
+            www   IN  A      192.0.2.2
+            www   IN  A      192.0.2.1
+            mail  IN  CNAME  www
+            MAIL  IN  CNAME  WWW.example.com.",
+        )
+        .expect("failed to parse");
+
+        assert_eq!(
+            lint_zone(&records),
+            vec![
+                Lint {
+                    severity: Severity::Warning,
+                    location: Location { line: 5, column: 13 },
+                    kind: LintKind::DuplicateRecord {
+                        name: "www.example.com".to_string(),
+                        r#type: "A".to_string(),
+                        first: Location { line: 3, column: 13 },
+                    },
+                },
+                Lint {
+                    severity: Severity::Warning,
+                    location: Location { line: 7, column: 13 },
+                    kind: LintKind::DuplicateRecord {
+                        name: "MAIL.example.com".to_string(),
+                        r#type: "CNAME".to_string(),
+                        first: Location { line: 6, column: 13 },
+                    },
+                },
+            ]
+        );
+        assert_eq!(
+            lint_zone(&records)[0].to_string(),
+            "5:13: warning: 'www.example.com' has a duplicate A record, first defined at 3:13"
+        );
+    }
+
+    #[test]
+    fn test_lint_zone_problems() {
+        let records = parse_zone_with_locations(
+            "$ORIGIN example.com.
+            $TTL 3600
+            @     IN  SOA    ns admin ( 1 600 3600 1209600 3600 )
+            @     IN  NS     ns1
+            sub   IN  NS     ns.sub
+            @     IN  MX     10 mail
+            mail  IN  CNAME  www
+            www   IN  A      192.0.2.1
+            ftp   IN  CNAME  www.example.com",
+        )
+        .expect("failed to parse");
+
+        let location = |line| Location { line, column: 13 };
+        assert_eq!(
+            lint_zone(&records),
+            vec![
+                Lint {
+                    severity: Severity::Info,
+                    location: location(3),
+                    kind: LintKind::SoaMinimumAboveRefresh {
+                        minimum: Duration::new(3600, 0),
+                        refresh: Duration::new(600, 0),
+                    },
+                },
+                Lint {
+                    severity: Severity::Warning,
+                    location: location(4),
+                    kind: LintKind::MissingGlue {
+                        name: "example.com".to_string(),
+                        server: "ns1.example.com".to_string(),
+                    },
+                },
+                Lint {
+                    severity: Severity::Warning,
+                    location: location(5),
+                    kind: LintKind::MissingGlue {
+                        name: "sub.example.com".to_string(),
+                        server: "ns.sub.example.com".to_string(),
+                    },
+                },
+                Lint {
+                    severity: Severity::Error,
+                    location: location(6),
+                    kind: LintKind::MxToCname {
+                        name: "example.com".to_string(),
+                        exchange: "mail.example.com".to_string(),
+                    },
+                },
+                Lint {
+                    severity: Severity::Warning,
+                    location: location(9),
+                    kind: LintKind::MissingTrailingDot("www.example.com.example.com".to_string()),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,17 @@
+use std::fmt;
+
+/// A position within a zone file, used to report where a entry was defined.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Location {
+    /// The line number, starting from 1.
+    pub line: usize,
+
+    /// The column, starting from 1.
+    pub column: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
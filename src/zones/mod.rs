@@ -1,25 +1,379 @@
 /// TODO Document
 // TODO https://github.com/Badcow/DNS-Parser has a nice custom format extension. Perhaps include?
+use crate::zones::preprocessor::convert_error;
+use crate::zones::preprocessor::normalise_newlines;
 use crate::zones::preprocessor::preprocess;
+use crate::zones::preprocessor::relocate_error;
+use crate::zones::preprocessor::shift_error;
+use crate::zones::preprocessor::strip_bom;
 use crate::zones::parser::Rule;
 use crate::zones::parser::ZoneParser;
 use crate::Class;
 use crate::Resource;
-use pest_consume::Parser;
+use derivative::Derivative;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::Path;
 use std::str::FromStr;
 use std::time::Duration;
+use std::time::Instant;
 use strum_macros::Display;
 
+mod diff;
+mod errors;
+mod glue;
+mod lint;
+mod location;
+mod options;
 mod parser;
 mod parser_tests;
 mod preprocessor;
 mod process;
+mod raw;
+mod rdata;
+mod reverse;
+mod stats;
+mod tokens;
+mod validate;
+mod writer;
+mod zone;
+
+pub use self::diff::{zone_diff, ZoneDiff};
+pub use self::errors::ZoneParseError;
+pub use self::glue::{delegations, Delegation};
+pub use self::lint::{lint_zone, Lint, LintKind, Severity};
+pub use self::location::Location;
+pub use self::options::ZoneOptions;
+pub use self::raw::{parse_zone_raw, parse_zone_raw_with_options, RawRecord, RawZone};
+pub use self::rdata::*;
+pub use self::reverse::build_ptr_zone;
+pub use self::stats::ZoneStats;
+pub use self::tokens::{tokens, tokens_with_options, Token, TokenType, Tokens};
+pub use self::validate::*;
+pub use self::writer::write_zone;
+pub use self::writer::write_zone_raw;
+pub use self::writer::WriteOptions;
+pub use self::zone::{WireRecord, Zone, ZoneBuilder};
+
+/// Parses a full zone file, and returns the resolved records.
+///
+/// Parsing never writes to stdout or stderr, any problems are returned as a
+/// [`ZoneParseError`]. A file that only contains comments, directives or blank
+/// lines is valid, and returns no records.
+///
+/// ```
+/// use rustdns::zones::parse_zone;
+///
+/// let records = parse_zone("$ORIGIN example.com.\n$TTL 3600\nwww IN A 192.0.2.1").unwrap();
+/// assert_eq!(records[0].name, "www.example.com");
+/// ```
+pub fn parse_zone(input: &str) -> Result<Vec<crate::Record>, ZoneParseError> {
+    File::from_str(input)?.into_records()
+}
+
+/// Same as [`parse_zone`] but accepts bytes, returning a error (instead of
+/// panicking) if they are not valid UTF-8. Any input, no matter how
+/// malformed, returns a error instead of panicking, which makes this suitable
+/// as a fuzzing target.
+///
+/// ```
+/// use rustdns::zones::try_parse_zone;
+///
+/// assert!(try_parse_zone(b"$TTL 3600\nexample.com. IN A 192.0.2.1").is_ok());
+/// assert!(try_parse_zone(b"\xff\xfe").is_err());
+/// ```
+pub fn try_parse_zone(input: &[u8]) -> Result<Vec<crate::Record>, ZoneParseError> {
+    let input =
+        std::str::from_utf8(input).map_err(|err| ZoneParseError::ReadError(err.to_string()))?;
+    parse_zone(input)
+}
+
+/// Same as [`parse_zone`] but allows defaults to be provided, which is
+/// useful when parsing a fragment of a zone file.
+///
+/// ```
+/// use rustdns::zones::{parse_zone_with_options, ZoneOptions};
+/// use rustdns::Class;
+/// use std::time::Duration;
+///
+/// let options = ZoneOptions {
+///     origin: Some("example.com.".to_string()),
+///     default_ttl: Some(Duration::new(300, 0)),
+///     default_class: Some(Class::Internet),
+///     ..Default::default()
+/// };
+///
+/// let records = parse_zone_with_options("www A 192.0.2.1", &options).unwrap();
+/// assert_eq!(records[0].name, "www.example.com");
+/// assert_eq!(records[0].ttl, Duration::new(300, 0));
+/// ```
+pub fn parse_zone_with_options(
+    input: &str,
+    options: &ZoneOptions,
+) -> Result<Vec<crate::Record>, ZoneParseError> {
+    if let Some(limit) = options.max_bytes {
+        if input.len() > limit {
+            return Err(ZoneParseError::TooLarge {
+                size: input.len(),
+                limit,
+            });
+        }
+    }
+
+    File::from_str_with_options(input, options)?.into_records_with_options(options)
+}
+
+/// Parses a single record, such as "www 300 IN A 192.0.2.1", and returns the
+/// resolved record. As there is no surrounding zone to inherit from, the
+/// origin, and any missing TTL or class, are taken from the `options`. This
+/// is useful when building DNS UPDATE ([rfc2136]) requests.
+///
+/// ```
+/// use rustdns::zones::{parse_record, ZoneOptions};
+/// use rustdns::Class;
+/// use std::time::Duration;
+///
+/// let options = ZoneOptions {
+///     origin: Some("example.com.".to_string()),
+///     default_ttl: Some(Duration::new(300, 0)),
+///     default_class: Some(Class::Internet),
+///     ..Default::default()
+/// };
+///
+/// let record = parse_record("www A 192.0.2.1", &options).unwrap();
+/// assert_eq!(record.name, "www.example.com");
+/// assert_eq!(record.ttl, Duration::new(300, 0));
+/// ```
+///
+/// [rfc2136]: https://datatracker.ietf.org/doc/html/rfc2136
+pub fn parse_record(input: &str, options: &ZoneOptions) -> Result<crate::Record, ZoneParseError> {
+    let record = Record::from_str_with_options(input, options)?;
+    let mut records =
+        File::new(None, vec![Entry::Record(record)]).into_records_with_options(options)?;
+
+    Ok(records.remove(0))
+}
+
+/// Same as [`parse_zone`] but reads the zone from the file at `path`.
+///
+/// If the `gzip` feature is enabled, gzip compressed files (such as the
+/// published TLD zone dumps) are transparently decompressed.
+pub fn parse_zone_from_path<P: AsRef<Path>>(path: P) -> Result<Vec<crate::Record>, ZoneParseError> {
+    parse_zone_from_reader(fs::File::open(path)?)
+}
+
+/// Same as [`parse_zone`] but reads the zone from `reader`.
+///
+/// The whole zone is read into memory before it is parsed, as a record may
+/// be split over multiple lines. Input that is not valid UTF-8 is returned as
+/// a [`ZoneParseError::ReadError`].
+///
+/// ```
+/// use rustdns::zones::parse_zone_from_reader;
+/// use std::io::Cursor;
+///
+/// let reader = Cursor::new("$ORIGIN example.com.\n$TTL 3600\nwww IN A 192.0.2.1");
+/// let records = parse_zone_from_reader(reader).unwrap();
+/// assert_eq!(records[0].name, "www.example.com");
+/// ```
+///
+/// If the `gzip` feature is enabled, and the input starts with the gzip magic
+/// bytes, it is transparently decompressed.
+pub fn parse_zone_from_reader<R: Read>(
+    mut reader: R,
+) -> Result<Vec<crate::Record>, ZoneParseError> {
+    let mut input = Vec::new();
+    reader.read_to_end(&mut input)?;
+
+    #[cfg(feature = "gzip")]
+    if input.starts_with(&[0x1f, 0x8b]) {
+        let mut decompressed = Vec::new();
+        flate2::read::MultiGzDecoder::new(&input[..]).read_to_end(&mut decompressed)?;
+        input = decompressed;
+    }
+
+    try_parse_zone(&input)
+}
+
+/// Same as [`parse_zone`] but also returns [`ZoneStats`] about the zone, which
+/// may help to diagnose slow or unexpectedly large zones.
+pub fn parse_zone_with_stats(
+    input: &str,
+) -> Result<(Vec<crate::Record>, ZoneStats), ZoneParseError> {
+    let start = Instant::now();
+    let records = parse_zone(input)?;
+    let stats = ZoneStats::new(input, &records, start.elapsed());
+
+    Ok((records, stats))
+}
+
+/// Same as [`parse_zone`] but also returns the [`Location`] each record was
+/// defined at, which is useful for reporting problems with the records.
+///
+/// ```
+/// use rustdns::zones::{parse_zone_with_locations, Location};
+///
+/// let records = parse_zone_with_locations("$ORIGIN example.com.\n$TTL 3600\nwww IN A 192.0.2.1").unwrap();
+/// assert_eq!(records[0].0.name, "www.example.com");
+/// assert_eq!(records[0].1, Location { line: 3, column: 1 });
+/// ```
+pub fn parse_zone_with_locations(
+    input: &str,
+) -> Result<Vec<(crate::Record, Location)>, ZoneParseError> {
+    File::from_str(input)?.into_located_records(&ZoneOptions::default())
+}
+
+/// Same as [`parse_zone`] but also returns the RDATA of each record in the
+/// canonical wire format (see [`Resource::canonical_rdata`]), for tools that
+/// sign or forward the records.
+///
+/// ```
+/// use rustdns::zones::parse_zone_with_rdata;
+///
+/// let records = parse_zone_with_rdata("$ORIGIN example.com.\n$TTL 3600\nwww IN A 192.0.2.1").unwrap();
+/// assert_eq!(records[0].0.name, "www.example.com");
+/// assert_eq!(records[0].1, vec![192, 0, 2, 1]);
+/// ```
+pub fn parse_zone_with_rdata(input: &str) -> Result<Vec<(crate::Record, Vec<u8>)>, ZoneParseError> {
+    Ok(parse_zone(input)?
+        .into_iter()
+        .map(|record| {
+            let rdata = record.resource.canonical_rdata();
+            (record, rdata)
+        })
+        .collect())
+}
+
+/// Same as [`parse_zone`] but groups the resources by their owner name,
+/// which is convenient for looking up all the records for a name. Resources
+/// are kept in the order they appear in the zone.
+///
+/// ```
+/// use rustdns::zones::parse_zone_grouped;
+///
+/// let zone = parse_zone_grouped("$ORIGIN example.com.\n$TTL 3600\nwww IN A 192.0.2.1\nwww IN A 192.0.2.2").unwrap();
+/// assert_eq!(zone["www.example.com"].len(), 2);
+/// ```
+pub fn parse_zone_grouped(input: &str) -> Result<HashMap<String, Vec<Resource>>, ZoneParseError> {
+    let mut grouped: HashMap<String, Vec<Resource>> = HashMap::new();
+    for record in parse_zone(input)? {
+        grouped
+            .entry(record.name)
+            .or_default()
+            .push(record.resource);
+    }
+
+    Ok(grouped)
+}
+
+/// Same as [`parse_zone`] but carries on after a error, instead of giving up
+/// on the whole zone. Each record (or directive) is parsed on its own, up to
+/// the next newline outside of any parentheses, so a syntax error only skips
+/// that record. Likewise a record that can't be resolved (such as one missing
+/// a TTL) is skipped. The records that could be parsed are returned, along
+/// with a error for each record that was skipped.
+///
+/// ```
+/// use rustdns::zones::parse_zone_lenient;
+///
+/// let (records, errors) = parse_zone_lenient("$TTL 3600\na.example. IN A 192.0.2.1\nb.example. IN A 192.0.2.300");
+/// assert_eq!(records.len(), 1);
+/// assert_eq!(errors.len(), 1);
+/// ```
+pub fn parse_zone_lenient(input: &str) -> (Vec<crate::Record>, Vec<ZoneParseError>) {
+    let input = strip_bom(input);
+    let options = ZoneOptions::default();
+
+    // Errors are reported within the whole input, where a lone carriage
+    // return is a newline, as when the whole input is parsed at once.
+    let normalised = normalise_newlines(input);
+
+    let mut entries = Vec::new();
+    let mut locations = Vec::new();
+    let mut errors = Vec::new();
+
+    for (offset, line, record) in split_records(input) {
+        match File::from_str_with_options(record, &options) {
+            Ok(file) => {
+                entries.extend(file.entries);
+                locations.extend(file.locations.into_iter().map(|location| Location {
+                    line: location.line + line - 1,
+                    ..location
+                }));
+            }
+            Err(err) => errors.push(shift_error(err, &normalised, offset).into()),
+        }
+    }
+
+    let file = File {
+        locations,
+        ..File::new(None, entries)
+    };
+    let (records, resolve_errors) = file.into_located_records_lenient(&options);
+    errors.extend(resolve_errors);
+
+    (
+        records.into_iter().map(|(record, _)| record).collect(),
+        errors,
+    )
+}
+
+/// Splits `input` into records, at each newline outside of any parentheses
+/// (ignoring those within quotes or comments). Returns the byte offset, and
+/// (1-based) line, that each record starts at.
+fn split_records(input: &str) -> Vec<(usize, usize, &str)> {
+    let mut records = Vec::new();
+    let mut start = 0;
+    let mut start_line = 1;
+    let mut line = 1;
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut comment = false;
+
+    let mut chars = input.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        // A lone carriage return also ends a line, as in the preprocessor.
+        let newline = c == '\n' || (c == '\r' && chars.peek().map(|&(_, c)| c) != Some('\n'));
+        if newline {
+            // A quoted string can't span lines, so a unterminated quote ends
+            // at the end of the line, instead of swallowing the rest.
+            comment = false;
+            quoted = false;
+            line += 1;
+            if depth == 0 {
+                records.push((start, start_line, &input[start..i]));
+                start = i + 1;
+                start_line = line;
+            }
+            continue;
+        }
+
+        match c {
+            _ if comment => {}
+            '\\' => {
+                // A escaped newline still ends the line.
+                chars.next_if(|&(_, c)| c != '\n' && c != '\r');
+            }
+            '"' => quoted = !quoted,
+            _ if quoted => {}
+            ';' => comment = true,
+            '(' => depth += 1,
+            ')' => depth = usize::saturating_sub(depth, 1),
+            _ => {}
+        }
+    }
+    records.push((start, start_line, &input[start..]));
+
+    records
+}
 
 /// A Zone File. This is the unprocessed version of the zone file
 /// where domains such as "@" have not yet been resolved, and fields
 /// are optional. To turn this into [`Vec<rustdns::Record>`] call
 /// [`process`].
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(PartialEq)]
 pub struct File {
     /// The origin as defined when creating the Zone File. This is different than
     /// a origin set within the zone file.
@@ -29,6 +383,10 @@ pub struct File {
 
     /// The list of Entries within the Zone File.
     pub entries: Vec<Entry>,
+
+    /// Where each entry was found in the input, if known.
+    #[derivative(PartialEq = "ignore")]
+    pub(crate) locations: Vec<Location>,
 }
 
 impl File {
@@ -41,7 +399,40 @@ impl File {
             }
         }
 
-        File { origin, entries }
+        File {
+            origin,
+            entries,
+            locations: Vec::new(),
+        }
+    }
+
+    /// Same as [`File::from_str`] but using the supplied [`ZoneOptions`] to
+    /// control the parsing.
+    #[allow(clippy::result_large_err)]
+    pub fn from_str_with_options(
+        input_str: &str,
+        options: &ZoneOptions,
+    ) -> Result<Self, pest_consume::Error<Rule>> {
+        let input_str = strip_bom(input_str);
+        let preprocessed =
+            preprocess(input_str, options).map_err(|err| convert_error(err, input_str))?;
+
+        // Errors are relative to the preprocessed input, so move them back.
+        let relocate = |err| relocate_error(err, input_str);
+
+        let inputs =
+            ZoneParser::parse_input(Rule::file, &preprocessed, options).map_err(relocate)?;
+        let input = inputs.single().map_err(relocate)?;
+
+        let (entries, locations) = ZoneParser::file(input)
+            .map_err(relocate)?
+            .into_iter()
+            .unzip();
+
+        Ok(File {
+            locations,
+            ..File::new(None, entries)
+        })
     }
 }
 
@@ -67,12 +458,7 @@ impl FromStr for File {
     /// ])));
     /// ```
     fn from_str(input_str: &str) -> Result<Self, Self::Err> {
-        let input_str = preprocess(input_str).unwrap(); // TODO
-
-        let inputs = ZoneParser::parse(Rule::file, &input_str)?;
-        let input = inputs.single()?;
-
-        ZoneParser::file(input).map(|x| File::new(None, x))
+        File::from_str_with_options(input_str, &ZoneOptions::default())
     }
 }
 
@@ -108,6 +494,20 @@ impl Default for Record {
     }
 }
 
+impl Record {
+    /// Same as [`Record::from_str`] but using the supplied [`ZoneOptions`] to
+    /// control the parsing.
+    #[allow(clippy::result_large_err)]
+    pub fn from_str_with_options(
+        input_str: &str,
+        options: &ZoneOptions,
+    ) -> Result<Self, pest_consume::Error<Rule>> {
+        let inputs = ZoneParser::parse_input(Rule::single_record, strip_bom(input_str), options)?;
+        let input = inputs.single()?;
+        ZoneParser::single_record(input)
+    }
+}
+
 impl FromStr for Record {
     type Err = pest_consume::Error<Rule>;
 
@@ -132,8 +532,39 @@ impl FromStr for Record {
     /// This function is mostly useful for test code, or quickly parsing a
     /// single record. Please prefer to use [`File::from_str`] to parse full files.
     fn from_str(input_str: &str) -> Result<Self, Self::Err> {
-        let inputs = ZoneParser::parse(Rule::single_record, input_str)?;
+        Record::from_str_with_options(input_str, &ZoneOptions::default())
+    }
+}
+
+impl FromStr for Resource {
+    type Err = ZoneParseError;
+
+    /// Parse a single resource, in zone file format. For example:
+    ///
+    /// ```
+    /// use rustdns::Resource;
+    /// use rustdns::MX;
+    ///
+    /// let a: Resource = "A 192.0.2.1".parse().unwrap();
+    /// assert_eq!(a, Resource::A("192.0.2.1".parse().unwrap()));
+    ///
+    /// let aaaa: Resource = "AAAA 2001:db8::1".parse().unwrap();
+    /// assert_eq!(aaaa, Resource::AAAA("2001:db8::1".parse().unwrap()));
+    ///
+    /// let mx: Resource = "MX 10 mail.example.com.".parse().unwrap();
+    /// assert_eq!(mx, Resource::MX(MX {
+    ///   preference: 10,
+    ///   exchange: "mail.example.com.".to_string(),
+    /// }));
+    /// ```
+    ///
+    /// As there is no zone to provide context, any relative domain names are
+    /// returned as is. This function is mostly useful for test code, or small
+    /// scripts.
+    fn from_str(input_str: &str) -> Result<Self, Self::Err> {
+        let options = ZoneOptions::default();
+        let inputs = ZoneParser::parse_input(Rule::single_resource, input_str, &options)?;
         let input = inputs.single()?;
-        ZoneParser::single_record(input)
+        Ok(ZoneParser::single_resource(input)?)
     }
 }
@@ -0,0 +1,95 @@
+use crate::Class;
+use crate::Type;
+use derivative::Derivative;
+use std::collections::HashSet;
+use std::time::Duration;
+
+/// Options to control how a zone file is parsed. See [`parse_zone_with_options`].
+///
+/// Each option is only used until the zone file says otherwise. For example, the
+/// `default_ttl` is used until a `$TTL` entry is found.
+///
+/// [`parse_zone_with_options`]: crate::zones::parse_zone_with_options
+#[derive(Clone, Debug, Default, Derivative)]
+#[derivative(PartialEq)]
+pub struct ZoneOptions {
+    /// The origin used to resolve relative domain names. Must be a absolute
+    /// domain, e.g "example.com.".
+    pub origin: Option<String>,
+
+    /// The TTL used for records that do not specify one.
+    pub default_ttl: Option<Duration>,
+
+    /// The class used for records that do not specify one.
+    pub default_class: Option<Class>,
+
+    /// Also treat '#' as the start of a comment, as used by some tools. This is
+    /// off by default, as it is not part of RFC 1035.
+    pub hash_comments: bool,
+
+    /// Reject any extensions to RFC 1035 (and RFC 2308's `$TTL`), such as
+    /// BIND style durations ("1h"), or '#' comments. This is useful to check
+    /// a zone file is portable between different servers.
+    pub strict: bool,
+
+    /// The maximum number of records the zone may contain. Useful to limit
+    /// the resources used when parsing untrusted zones.
+    pub max_records: Option<usize>,
+
+    /// The maximum size of the zone, in bytes. This is checked before parsing
+    /// begins, so a oversized zone is rejected cheaply.
+    pub max_bytes: Option<usize>,
+
+    /// Reject AAAA records written with a embedded IPv4 address, such as
+    /// "::ffff:192.0.2.1", as some operators only allow the hexadecimal form.
+    pub strict_ipv6: bool,
+
+    /// Reject SOA records whose mname or rname are relative, that is missing
+    /// the trailing dot, as it is usually left off by mistake. For example
+    /// "ns.example.com" in the zone "example.com." is "ns.example.com.example.com".
+    pub strict_soa_names: bool,
+
+    /// Lowercase the owner name of each record, as names are case
+    /// insensitive (see [rfc4343]). Only ASCII letters are lowercased, as
+    /// other bytes are compared as is. This makes the records simpler to store,
+    /// or look up, by name. Names within the RDATA, such as a CNAME's target,
+    /// are kept as written, unless `lowercase_targets` is also set.
+    ///
+    /// [rfc4343]: https://datatracker.ietf.org/doc/html/rfc4343
+    pub lowercase_names: bool,
+
+    /// Lowercase the domain names within the RDATA, such as a CNAME's
+    /// target, or a MX's exchange. As with `lowercase_names`, only ASCII
+    /// letters are lowercased.
+    pub lowercase_targets: bool,
+
+    /// The only record types the zone may contain, for example a DNS provider
+    /// may only support A, AAAA, CNAME, MX and TXT records. Records of any
+    /// other type, including those in the generic `\# len hex` form, are
+    /// rejected. All types are allowed if this is `None`.
+    pub allowed_types: Option<HashSet<Type>>,
+
+    /// Interprets a TTL, returning `None` to fall back to the standard
+    /// parser. This allows the non-standard TTLs written by some tools to be
+    /// understood, such as "TTL=3600". Only TTLs containing a '=' are
+    /// accepted, in addition to the standard forms, as others could be
+    /// mistaken for a name, class or type.
+    ///
+    /// This is ignored when comparing options, as functions can't be
+    /// reliably compared.
+    #[derivative(PartialEq = "ignore")]
+    pub ttl_parser: Option<fn(&str) -> Option<Duration>>,
+
+    /// The maximum length of a line, in bytes, not including the newline.
+    /// BIND may fail to load zones with very long lines, so this can be used
+    /// to check a zone is compatible.
+    pub max_line_length: Option<usize>,
+
+    /// The maximum length of a record, in bytes, including any lines it
+    /// continues onto within parentheses.
+    pub max_record_length: Option<usize>,
+
+    /// The maximum depth of nested parentheses, for example 1 allows
+    /// "( 1 2 )" but not "( ( 1 2 ) )". BIND does not allow nesting.
+    pub max_paren_depth: Option<usize>,
+}
@@ -1,25 +1,134 @@
 // Parses a Zone File following RFC 1035 (section 5).
 
+use crate::zones::errors::describe_error;
 use crate::zones::Entry;
+use crate::zones::Location;
 use crate::zones::Record;
 use crate::zones::Resource;
+use crate::zones::ZoneOptions;
+use crate::from_str::parse_eui;
+use crate::from_str::parse_nsap;
+use crate::from_str::unescape;
+use crate::APLItem;
 use crate::Class;
+use crate::APL;
+use crate::CERT;
+use crate::CSYNC;
+use crate::DOA;
+use crate::DS;
+use crate::TLSA;
+use crate::GPOS;
+use crate::ISDN;
+use crate::ATMA;
+use crate::HIP;
+use crate::KEY;
+use crate::L32;
+use crate::L64;
+use crate::LP;
+use crate::NID;
+use crate::SIG;
+use crate::TKEY;
+use crate::TSIG;
+use crate::Type;
 use crate::MX;
+use crate::AFSDB;
+use crate::WKS;
+use crate::RT;
+use crate::SvcParam;
 use crate::SOA;
+use crate::SRV;
+use crate::SVCB;
+use crate::TXT;
+use crate::URI;
+use num_traits::FromPrimitive;
 use pest_consume::match_nodes;
 use pest_consume::Error;
+use pest_consume::Nodes;
 use pest_consume::Parser;
 use std::net::Ipv4Addr;
 use std::net::Ipv6Addr;
+use std::num::IntErrorKind;
+use std::num::ParseIntError;
 use std::str::FromStr;
 use std::time::Duration;
 
+// Types registered with IANA that are not supported, but may be written
+// with their mnemonic in the generic form, for example "NINFO \# 2 abcd".
+// These are parsed as a Resource::Unknown.
+static UNSUPPORTED_TYPES: &[(&str, u16)] = &[
+    ("NINFO", 56),
+    ("RKEY", 57),
+    ("TALINK", 58),
+    ("UINFO", 100),
+    ("UID", 101),
+    ("GID", 102),
+    ("UNSPEC", 103),
+];
+
+// Types that are no longer used, and the RFC that obsoleted them. These are
+// rejected with a clear error, rather than as a unknown type.
+static OBSOLETE_TYPES: &[(&str, &str)] = &[
+    ("MD", "RFC 973"),
+    ("MF", "RFC 973"),
+    ("NXT", "RFC 3755"),
+    ("A6", "RFC 6563"),
+];
+
+// How a record type, given by its mnemonic or "TYPE<n>", is handled.
+enum TypeStatus {
+    // A type with its own RDATA format.
+    Supported(Type),
+
+    // A registered type that may only be given in the generic form, and is
+    // parsed as a Resource::Unknown.
+    Unsupported(u16),
+
+    // A type that is no longer used, with the RFC that obsoleted it.
+    Obsolete(&'static str),
+
+    Unknown,
+}
+
+// type_status returns how the record type is handled, matching the mnemonic
+// case-insensitively. All record types are recognised here.
+fn type_status(name: &str) -> TypeStatus {
+    let name = name.to_ascii_uppercase();
+    if let Some((_, rfc)) = OBSOLETE_TYPES.iter().find(|(mnemonic, _)| *mnemonic == name) {
+        return TypeStatus::Obsolete(rfc);
+    }
+
+    if let Ok(r#type) = Type::from_str(&name) {
+        return TypeStatus::Supported(r#type);
+    }
+
+    let number = match name.strip_prefix("TYPE") {
+        Some(n) => n.parse().ok(),
+        None => UNSUPPORTED_TYPES
+            .iter()
+            .find(|(mnemonic, _)| *mnemonic == name)
+            .map(|(_, n)| *n),
+    };
+
+    match number {
+        Some(n) => match Type::from_u16(n) {
+            Some(r#type) => TypeStatus::Supported(r#type),
+            None => TypeStatus::Unsupported(n),
+        },
+        None => TypeStatus::Unknown,
+    }
+}
+
 #[derive(Parser)]
 #[grammar = "zones/zones.pest"]
 pub(crate) struct ZoneParser;
 
 type Result<T> = std::result::Result<T, Error<Rule>>;
-type Node<'i> = pest_consume::Node<'i, Rule, ()>;
+// The options are passed to each node, so parsing can depend on them.
+type Node<'i> = pest_consume::Node<'i, Rule, &'i ZoneOptions>;
+
+// The largest TTL allowed, as TTLs are limited to 2^31 - 1 seconds. See
+// rfc2181 section 8.
+const MAX_TTL: u64 = 2147483647;
 
 #[pest_consume::parser]
 impl ZoneParser {
@@ -46,22 +155,246 @@ impl ZoneParser {
         }
     }
 
-    fn duration(input: Node) -> Result<Duration> {
-        assert_eq!(input.as_rule(), Rule::duration);
+    fn locator64(input: Node) -> Result<u64> {
+        assert_eq!(input.as_rule(), Rule::locator64);
+
+        match L64::locator_from_str(input.as_str()) {
+            Some(locator) => Ok(locator),
+            None => Err(input.error(format!("invalid 64-bit locator '{}'", input.as_str()))),
+        }
+    }
+
+    fn apl_item(input: Node) -> Result<APLItem> {
+        assert_eq!(input.as_rule(), Rule::apl_item);
+
+        match APLItem::from_str(input.as_str()) {
+            Ok(item) => Ok(item),
+            Err(e) => Err(input.error(e)),
+        }
+    }
+
+    fn record_type(input: Node) -> Result<Type> {
+        assert_eq!(input.as_rule(), Rule::record_type);
+
+        match type_status(input.as_str()) {
+            TypeStatus::Supported(r#type) => Ok(r#type),
+            status => Err(Self::type_error(&input, status)),
+        }
+    }
+
+    fn nsap(input: Node) -> Result<Vec<u8>> {
+        assert_eq!(input.as_rule(), Rule::nsap);
+
+        match parse_nsap(input.as_str()) {
+            Ok(nsap) => Ok(nsap),
+            Err(_) => Err(input.error(format!(
+                "invalid NSAP address '{}': expected a even number of hex digits after the '0x'",
+                input.as_str()
+            ))),
+        }
+    }
+
+    fn atma_address(input: Node) -> Result<ATMA> {
+        assert_eq!(input.as_rule(), Rule::atma_address);
 
-        // TODO Support more complex duration types (e.g "1d")
         match input.as_str().parse() {
-            Ok(i) => Ok(Duration::new(i, 0)),
+            Ok(atma) => Ok(atma),
+            Err(_) => Err(input.error(format!(
+                "invalid ATMA address '{}': expected '+' followed by decimal digits, or a even number of hex digits",
+                input.as_str()
+            ))),
+        }
+    }
+
+    fn cert_type(input: Node) -> Result<u16> {
+        assert_eq!(input.as_rule(), Rule::cert_type);
+
+        match CERT::type_from_mnemonic(input.as_str()) {
+            Some(cert_type) => Ok(cert_type),
+            None => Err(input.error(format!("unknown certificate type '{}'", input.as_str()))),
+        }
+    }
+
+    // A EUI48 or EUI64, where N is the number of bytes.
+    fn eui<const N: usize>(input: Node) -> Result<[u8; N]> {
+        assert_eq!(input.as_rule(), Rule::eui);
+
+        match parse_eui(input.as_str()) {
+            Ok(eui) => Ok(eui),
+            Err(_) => Err(input.error(format!(
+                "invalid EUI{} '{}': expected {} hyphen separated pairs of hex digits, such as '{}'",
+                N * 8,
+                input.as_str(),
+                N,
+                ["00"; N].join("-"),
+            ))),
+        }
+    }
+
+    fn wks_protocol(input: Node) -> Result<u8> {
+        assert_eq!(input.as_rule(), Rule::wks_protocol);
+
+        match WKS::protocol_from_name(input.as_str()) {
+            Some(protocol) => Ok(protocol),
+            None => Err(input.error(format!("unknown protocol '{}'", input.as_str()))),
+        }
+    }
+
+    fn wks_service(input: Node) -> Result<u16> {
+        assert_eq!(input.as_rule(), Rule::wks_service);
+
+        match WKS::port_from_name(input.as_str()) {
+            Some(port) => Ok(port),
+            None => Err(input.error(format!("unknown service '{}'", input.as_str()))),
+        }
+    }
+
+    fn base64(input: Node) -> Result<Vec<u8>> {
+        assert_eq!(input.as_rule(), Rule::base64);
+
+        // Remove any whitespace (or parentheses) between the base64 chunks.
+        let s: String = input
+            .as_str()
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '\t' | '(' | ')'))
+            .collect();
+
+        match base64::decode(s) {
+            Ok(data) => Ok(data),
             Err(e) => Err(input.error(e)),
         }
     }
 
+    fn hip_hit(input: Node) -> Result<Vec<u8>> {
+        assert_eq!(input.as_rule(), Rule::hip_hit);
+
+        match hex::decode(input.as_str()) {
+            Ok(hit) => Ok(hit),
+            Err(e) => Err(input.error(e)),
+        }
+    }
+
+    fn hip_key(input: Node) -> Result<Vec<u8>> {
+        assert_eq!(input.as_rule(), Rule::hip_key);
+
+        match base64::decode(input.as_str()) {
+            Ok(key) => Ok(key),
+            Err(e) => Err(input.error(e)),
+        }
+    }
+
+    fn hex(input: Node) -> Result<Vec<u8>> {
+        assert_eq!(input.as_rule(), Rule::hex);
+
+        // Remove any whitespace (or parentheses) between the hex chunks.
+        let s: String = input
+            .as_str()
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '\t' | '(' | ')'))
+            .collect();
+
+        match hex::decode(s) {
+            Ok(data) => Ok(data),
+            Err(e) => Err(input.error(e)),
+        }
+    }
+
+    fn duration(input: Node) -> Result<Duration> {
+        assert_eq!(input.as_rule(), Rule::duration);
+
+        let ttl = match Self::parse_custom_ttl(&input) {
+            Some(ttl) => ttl,
+            None => Self::parse_duration(&input, "TTL")?,
+        };
+        Self::check_ttl(&input, ttl)
+    }
+
+    fn custom_ttl(input: Node) -> Result<Duration> {
+        assert_eq!(input.as_rule(), Rule::custom_ttl);
+
+        match Self::parse_custom_ttl(&input) {
+            Some(ttl) => Self::check_ttl(&input, ttl),
+            None => Err(input.error(format!("invalid TTL '{}'", input.as_str()))),
+        }
+    }
+
     fn string(input: Node) -> Result<&str> {
         assert_eq!(input.as_rule(), Rule::string);
 
         Ok(input.as_str())
     }
 
+    fn quoted(input: Node) -> Result<String> {
+        assert_eq!(input.as_rule(), Rule::quoted);
+
+        // Strip the quotes, and unescape any characters.
+        let s = input.as_str();
+        let mut result = String::with_capacity(s.len());
+        let mut chars = s[1..s.len() - 1].chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => result.extend(chars.next()),
+                _ => result.push(c),
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn char_string(input: Node) -> Result<Vec<u8>> {
+        assert_eq!(input.as_rule(), Rule::char_string);
+
+        // Strip any quotes, and decode the escaped characters.
+        let s = input.as_str();
+        let s = match s.strip_prefix('"') {
+            Some(quoted) => &quoted[..quoted.len() - 1],
+            None => s,
+        };
+
+        match unescape(s) {
+            Ok(bytes) if bytes.len() > 255 => Err(input.error(format!(
+                "character-string is {} bytes long, but must be at most 255 bytes",
+                bytes.len()
+            ))),
+            Ok(bytes) => Ok(bytes),
+            Err(_) => Err(input.error(format!("invalid escape in '{}'", s))),
+        }
+    }
+
+    fn svc_key(input: Node<'_>) -> Result<&str> {
+        assert_eq!(input.as_rule(), Rule::svc_key);
+        Ok(input.as_str())
+    }
+
+    fn svc_quoted(input: Node<'_>) -> Result<&str> {
+        assert_eq!(input.as_rule(), Rule::svc_quoted);
+        Ok(input.as_str())
+    }
+
+    fn svc_value(input: Node<'_>) -> Result<&str> {
+        assert_eq!(input.as_rule(), Rule::svc_value);
+        Ok(input.as_str())
+    }
+
+    fn svc_param(input: Node) -> Result<SvcParam> {
+        assert_eq!(input.as_rule(), Rule::svc_param);
+
+        let (key, value) = match_nodes!(input.children();
+            [svc_key(key)] => (key, None),
+            [svc_key(key), svc_quoted(value)] => (key, Some(value)),
+            [svc_key(key), svc_value(value)] => (key, Some(value)),
+        );
+
+        match SvcParam::from_key_value(key, value) {
+            Ok(param) => Ok(param),
+            Err(e) => Err(input.error(format!(
+                "invalid service parameter '{}': {}",
+                input.as_str(),
+                e
+            ))),
+        }
+    }
+
     fn domain(input: Node) -> Result<&str> {
         assert_eq!(input.as_rule(), Rule::domain);
 
@@ -72,21 +405,91 @@ impl ZoneParser {
     fn class(input: Node) -> Result<Class> {
         assert_eq!(input.as_rule(), Rule::class);
 
-        match input.as_str().parse() {
+        let s = input.as_str().to_ascii_uppercase();
+        if let Some(n) = s.strip_prefix("CLASS") {
+            return match n.parse().ok().and_then(FromPrimitive::from_u16) {
+                Some(class) => Ok(class),
+                None => Err(input.error(format!("unsupported class '{}'", input.as_str()))),
+            };
+        }
+
+        match s.parse() {
             Ok(class) => Ok(class),
             Err(e) => Err(input.error(e)),
         }
     }
 
-    fn number<T: std::str::FromStr>(input: Node) -> Result<T>
+    // Always returns a error, as the class is not known.
+    fn unknown_class(input: Node) -> Result<Class> {
+        assert_eq!(input.as_rule(), Rule::unknown_class);
+
+        Err(input.error(format!(
+            "unknown class '{0}', expected IN, CS, CH, HS or CLASS<n> (if '{0}' is a domain, write it in lowercase)",
+            input.as_str()
+        )))
+    }
+
+    fn number<T>(input: Node) -> Result<T>
     where
-        T::Err: std::fmt::Display,
+        T: FromStr<Err = ParseIntError>,
     {
         assert_eq!(input.as_rule(), Rule::number);
 
-        match input.as_str().parse() {
-            Ok(i) => Ok(i),
-            Err(e) => Err(input.error(e)),
+        Self::parse_int(&input, "number")
+    }
+
+    fn generic_marker(input: Node) -> Result<()> {
+        assert_eq!(input.as_rule(), Rule::generic_marker);
+        Ok(())
+    }
+
+    #[alias(resource)]
+    fn resource_generic(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_generic);
+
+        let mut children = input.children();
+        let type_node = children.next().unwrap();
+        Self::generic_marker(children.next().unwrap())?;
+        let len: usize = Self::parse_int(&children.next().unwrap(), "RDATA length")?;
+        let rdata = match children.next() {
+            Some(hex) => Self::hex(hex)?,
+            None => Vec::new(),
+        };
+
+        if rdata.len() != len {
+            return Err(input.error(format!(
+                "RDATA length is {}, but {} bytes were given",
+                len,
+                rdata.len()
+            )));
+        }
+
+        // Types that are not supported are kept as is.
+        let r#type = match Self::generic_type(&type_node)? {
+            Ok(r#type) => r#type,
+            Err(r#type) => return Ok(Resource::Unknown(r#type, rdata)),
+        };
+
+        // The class isn't known here, but the only class dependent types
+        // (A and AAAA) are only supported in the Internet class.
+        match Resource::from_rdata(r#type, Class::Internet, &rdata) {
+            Ok(resource) => Ok(resource),
+            Err(e) => Err(input.error(format!("invalid {} RDATA: {}", r#type, e))),
+        }
+    }
+
+    #[alias(resource)]
+    fn resource_unmatched(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_unmatched);
+
+        // The types with their own RDATA format are never matched here.
+        let type_node = input.children().next().unwrap();
+        match type_status(type_node.as_str()) {
+            TypeStatus::Supported(_) | TypeStatus::Unsupported(_) => Err(type_node.error(format!(
+                "{} records may only be given in the generic '\\#' form",
+                type_node.as_str().to_ascii_uppercase()
+            ))),
+            status => Err(Self::type_error(&type_node, status)),
         }
     }
 
@@ -103,9 +506,15 @@ impl ZoneParser {
     fn resource_aaaa(input: Node) -> Result<Resource> {
         assert_eq!(input.as_rule(), Rule::resource_aaaa);
 
-        Ok(match_nodes!(input.into_children();
-            [ip6(ip)] => Resource::AAAA(ip),
-        ))
+        let ip = input.children().next().unwrap();
+        if input.user_data().strict_ipv6 && ip.as_str().contains('.') {
+            return Err(ip.error(format!(
+                "embedded IPv4 notation is not allowed for AAAA record '{}', write it in hexadecimal",
+                ip.as_str()
+            )));
+        }
+
+        Ok(Resource::AAAA(Self::ip6(ip)?))
     }
 
     #[alias(resource)]
@@ -131,13 +540,136 @@ impl ZoneParser {
         assert_eq!(input.as_rule(), Rule::resource_mx);
 
         Ok(match_nodes!(input.into_children();
-            [number(preference), domain(exchange)] => Resource::MX(MX {
-                preference,
+            [preference, domain(exchange)] => Resource::MX(MX {
+                preference: Self::parse_int(&preference, "MX preference")?,
                 exchange: exchange.to_string()
             }),
         ))
     }
 
+    #[alias(resource)]
+    fn resource_wks(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_wks);
+
+        let mut children = input.children();
+        let address = Self::ip4(children.next().unwrap())?;
+        let protocol = Self::wks_protocol(children.next().unwrap())?;
+
+        let mut ports = children
+            .map(Self::wks_service)
+            .collect::<Result<Vec<u16>>>()?;
+        ports.sort_unstable();
+        ports.dedup();
+
+        Ok(Resource::WKS(WKS {
+            address,
+            protocol,
+            ports,
+        }))
+    }
+
+    #[alias(resource)]
+    fn resource_afsdb(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_afsdb);
+
+        Ok(match_nodes!(input.into_children();
+            [subtype, domain(hostname)] => Resource::AFSDB(AFSDB {
+                subtype: Self::parse_int(&subtype, "AFSDB subtype")?,
+                hostname: hostname.to_string()
+            }),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_rt(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_rt);
+
+        Ok(match_nodes!(input.into_children();
+            [preference, domain(host)] => Resource::RT(RT {
+                preference: Self::parse_int(&preference, "RT preference")?,
+                host: host.to_string()
+            }),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_x25(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_x25);
+
+        let node = input.into_children().next().unwrap();
+        let address = Self::utf8_string(node.clone(), "X25 address")?;
+
+        // rfc1183 section 3.1 requires at least 4 digits.
+        if address.len() < 4 || !address.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(node.error(format!(
+                "invalid X25 address '{}': expected at least 4 decimal digits",
+                address
+            )));
+        }
+
+        Ok(Resource::X25(address))
+    }
+
+    #[alias(resource)]
+    fn resource_isdn(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_isdn);
+
+        let mut children = input.children();
+        let address = Self::utf8_string(children.next().unwrap(), "ISDN address")?;
+        let subaddress = match children.next() {
+            Some(node) => Some(Self::utf8_string(node, "ISDN subaddress")?),
+            None => None,
+        };
+
+        Ok(Resource::ISDN(ISDN {
+            address,
+            subaddress,
+        }))
+    }
+
+    #[alias(resource)]
+    fn resource_nsap(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_nsap);
+
+        Ok(match_nodes!(input.into_children();
+            [nsap(nsap)] => Resource::NSAP(nsap),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_nsap_ptr(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_nsap_ptr);
+
+        Ok(match_nodes!(input.into_children();
+            [domain(name)] => Resource::NSAP_PTR(name.to_string()),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_atma(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_atma);
+
+        Ok(match_nodes!(input.into_children();
+            [atma_address(atma)] => Resource::ATMA(atma),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_gpos(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_gpos);
+
+        let mut children = input.children();
+        let longitude = Self::gpos_value(children.next().unwrap(), "longitude", Some(180.0))?;
+        let latitude = Self::gpos_value(children.next().unwrap(), "latitude", Some(90.0))?;
+        let altitude = Self::gpos_value(children.next().unwrap(), "altitude", None)?;
+
+        Ok(Resource::GPOS(GPOS {
+            longitude,
+            latitude,
+            altitude,
+        }))
+    }
+
     #[alias(resource)]
     fn resource_ptr(input: Node) -> Result<Resource> {
         assert_eq!(input.as_rule(), Rule::resource_ptr);
@@ -152,14 +684,437 @@ impl ZoneParser {
         assert_eq!(input.as_rule(), Rule::resource_soa);
 
         Ok(match_nodes!(input.into_children();
-            [domain(mname), string(rname), number(serial), duration(refresh), duration(retry), duration(expire), duration(minimum)] => Resource::SOA(SOA {
+            [domain(mname), string(rname), serial, refresh, retry, expire, minimum] => Resource::SOA(SOA {
                 mname: mname.to_string(),
                 rname: rname.to_string(), // TODO Should this actually be a domain?
-                serial, refresh, retry, expire, minimum
+                serial: Self::parse_int(&serial, "serial")?,
+                refresh: Self::parse_duration(&refresh, "refresh")?,
+                retry: Self::parse_duration(&retry, "retry")?,
+                expire: Self::parse_duration(&expire, "expire")?,
+                minimum: Self::parse_duration(&minimum, "minimum")?,
+            }),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_txt(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_txt);
+
+        let txts = input
+            .into_children()
+            .map(Self::char_string)
+            .collect::<Result<Vec<Vec<u8>>>>()?;
+
+        Ok(Resource::TXT(TXT(txts)))
+    }
+
+    #[alias(resource)]
+    fn resource_spf(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_spf);
+
+        let txts = input
+            .into_children()
+            .map(Self::char_string)
+            .collect::<Result<Vec<Vec<u8>>>>()?;
+
+        Ok(Resource::SPF(TXT(txts)))
+    }
+
+    #[alias(resource)]
+    fn resource_srv(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_srv);
+
+        Ok(match_nodes!(input.into_children();
+            [priority, weight, port, domain(name)] => Resource::SRV(SRV {
+                priority: Self::parse_int(&priority, "priority")?,
+                weight: Self::parse_int(&weight, "weight")?,
+                port: Self::parse_int(&port, "port")?,
+                name: name.to_string(),
+            }),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_uri(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_uri);
+
+        Ok(match_nodes!(input.into_children();
+            [priority, weight, quoted(target)] => Resource::URI(URI {
+                priority: Self::parse_int(&priority, "priority")?,
+                weight: Self::parse_int(&weight, "weight")?,
+                target,
+            }),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_apl(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_apl);
+
+        let items = input
+            .into_children()
+            .map(Self::apl_item)
+            .collect::<Result<Vec<APLItem>>>()?;
+
+        Ok(Resource::APL(APL(items)))
+    }
+
+    #[alias(resource)]
+    fn resource_cert(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_cert);
+
+        Ok(match_nodes!(input.into_children();
+            [cert_type(cert_type), key_tag, algorithm, base64(certificate)] => Resource::CERT(CERT {
+                cert_type,
+                key_tag: Self::parse_int(&key_tag, "key tag")?,
+                algorithm: Self::parse_int(&algorithm, "algorithm")?,
+                certificate,
+            }),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_dhcid(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_dhcid);
+
+        Ok(match_nodes!(input.into_children();
+            [base64(dhcid)] => Resource::DHCID(dhcid),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_openpgpkey(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_openpgpkey);
+
+        Ok(match_nodes!(input.into_children();
+            [base64(key)] => Resource::OPENPGPKEY(key),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_hip(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_hip);
+
+        let mut children = input.children();
+        let algorithm = children.next().unwrap();
+        let hit = Self::hip_hit(children.next().unwrap())?;
+        let public_key = Self::hip_key(children.next().unwrap())?;
+
+        let rendezvous_servers = children
+            .map(Self::domain)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+
+        Ok(Resource::HIP(HIP {
+            algorithm: Self::parse_int(&algorithm, "algorithm")?,
+            hit,
+            public_key,
+            rendezvous_servers,
+        }))
+    }
+
+    #[alias(resource)]
+    fn resource_csync(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_csync);
+
+        let mut children = input.children();
+        let serial = children.next().unwrap();
+        let flags = children.next().unwrap();
+
+        let types = children
+            .map(Self::record_type)
+            .collect::<Result<Vec<Type>>>()?;
+
+        Ok(Resource::CSYNC(CSYNC {
+            serial: Self::parse_int(&serial, "serial")?,
+            flags: Self::parse_int(&flags, "flags")?,
+            types,
+        }))
+    }
+
+    #[alias(resource)]
+    fn resource_svcb(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_svcb);
+
+        Ok(Resource::SVCB(Self::parse_svcb(input)?))
+    }
+
+    #[alias(resource)]
+    fn resource_https(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_https);
+
+        Ok(Resource::HTTPS(Self::parse_svcb(input)?))
+    }
+
+    #[alias(resource)]
+    fn resource_smimea(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_smimea);
+
+        Ok(match_nodes!(input.into_children();
+            [usage, selector, matching_type, hex(data)] => Resource::SMIMEA(TLSA {
+                usage: Self::parse_int(&usage, "usage")?,
+                selector: Self::parse_int(&selector, "selector")?,
+                matching_type: Self::parse_int(&matching_type, "matching type")?,
+                data,
+            }),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_cds(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_cds);
+
+        Ok(match_nodes!(input.into_children();
+            [key_tag, algorithm, digest_type, hex(digest)] => Resource::CDS(DS {
+                key_tag: Self::parse_int(&key_tag, "key tag")?,
+                algorithm: Self::parse_int(&algorithm, "algorithm")?,
+                digest_type: Self::parse_int(&digest_type, "digest type")?,
+                digest,
+            }),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_cdnskey(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_cdnskey);
+
+        Ok(match_nodes!(input.into_children();
+            [flags, protocol, algorithm, base64(public_key)] => Resource::CDNSKEY(KEY {
+                flags: Self::parse_int(&flags, "flags")?,
+                protocol: Self::parse_int(&protocol, "protocol")?,
+                algorithm: Self::parse_int(&algorithm, "algorithm")?,
+                public_key,
+            }),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_nid(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_nid);
+
+        Ok(match_nodes!(input.into_children();
+            [preference, locator64(node_id)] => Resource::NID(NID {
+                preference: Self::parse_int(&preference, "NID preference")?,
+                node_id,
+            }),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_l32(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_l32);
+
+        Ok(match_nodes!(input.into_children();
+            [preference, ip4(locator)] => Resource::L32(L32 {
+                preference: Self::parse_int(&preference, "L32 preference")?,
+                locator,
+            }),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_l64(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_l64);
+
+        Ok(match_nodes!(input.into_children();
+            [preference, locator64(locator)] => Resource::L64(L64 {
+                preference: Self::parse_int(&preference, "L64 preference")?,
+                locator,
+            }),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_lp(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_lp);
+
+        Ok(match_nodes!(input.into_children();
+            [preference, domain(fqdn)] => Resource::LP(LP {
+                preference: Self::parse_int(&preference, "LP preference")?,
+                fqdn: fqdn.to_string(),
+            }),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_eui48(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_eui48);
+
+        Ok(match_nodes!(input.into_children();
+            [eui(eui)] => Resource::EUI48(eui),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_eui64(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_eui64);
+
+        Ok(match_nodes!(input.into_children();
+            [eui(eui)] => Resource::EUI64(eui),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_key(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_key);
+
+        Ok(match_nodes!(input.into_children();
+            [flags, protocol, algorithm, base64(public_key)] => Resource::KEY(KEY {
+                flags: Self::parse_int(&flags, "flags")?,
+                protocol: Self::parse_int(&protocol, "protocol")?,
+                algorithm: Self::parse_int(&algorithm, "algorithm")?,
+                public_key,
+            }),
+        ))
+    }
+
+    #[alias(resource)]
+    fn resource_sig(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_sig);
+
+        Ok(match_nodes!(input.into_children();
+            [record_type(type_covered), algorithm, labels, original_ttl, expiration, inception, key_tag, domain(signer_name), base64(signature)] => Resource::SIG(SIG {
+                type_covered,
+                algorithm: Self::parse_int(&algorithm, "algorithm")?,
+                labels: Self::parse_int(&labels, "labels")?,
+                original_ttl: Self::parse_int(&original_ttl, "original TTL")?,
+                expiration: Self::parse_time(&expiration, "expiration")?,
+                inception: Self::parse_time(&inception, "inception")?,
+                key_tag: Self::parse_int(&key_tag, "key tag")?,
+                signer_name: signer_name.to_string(),
+                signature,
             }),
         ))
     }
 
+    #[alias(resource)]
+    fn resource_avc(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_avc);
+
+        let txts = input
+            .into_children()
+            .map(Self::char_string)
+            .collect::<Result<Vec<Vec<u8>>>>()?;
+
+        Ok(Resource::AVC(TXT(txts)))
+    }
+
+    fn doa_empty(input: Node) -> Result<Vec<u8>> {
+        assert_eq!(input.as_rule(), Rule::doa_empty);
+        Ok(Vec::new())
+    }
+
+    #[alias(resource)]
+    fn resource_doa(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_doa);
+
+        let (enterprise, doa_type, location, media_type, data) = match_nodes!(input.into_children();
+            [enterprise, doa_type, location, media_type, doa_empty(data)] => (enterprise, doa_type, location, media_type, data),
+            [enterprise, doa_type, location, media_type, base64(data)] => (enterprise, doa_type, location, media_type, data),
+        );
+
+        Ok(Resource::DOA(DOA {
+            enterprise: Self::parse_int(&enterprise, "enterprise")?,
+            doa_type: Self::parse_int(&doa_type, "DOA type")?,
+            location: Self::parse_int(&location, "location")?,
+            media_type: Self::utf8_string(media_type, "media type")?,
+            data,
+        }))
+    }
+
+    fn base64_field(input: Node) -> Result<Vec<u8>> {
+        assert_eq!(input.as_rule(), Rule::base64_field);
+
+        match base64::decode(input.as_str()) {
+            Ok(data) => Ok(data),
+            Err(e) => Err(input.error(e)),
+        }
+    }
+
+    fn empty_data(input: Node) -> Result<Vec<u8>> {
+        assert_eq!(input.as_rule(), Rule::empty_data);
+        Ok(Vec::new())
+    }
+
+    fn sized_data(input: Node) -> Result<Vec<u8>> {
+        assert_eq!(input.as_rule(), Rule::sized_data);
+
+        let span = input.clone();
+        let (size, data) = match_nodes!(input.into_children();
+            [empty_data(data)] => return Ok(data),
+            [size, base64_field(data)] => (size, data),
+        );
+
+        let size: u16 = Self::parse_int(&size, "data size")?;
+        if data.len() != usize::from(size) {
+            return Err(span.error(format!(
+                "data is {} bytes, but its size is given as {}",
+                data.len(),
+                size
+            )));
+        }
+
+        Ok(data)
+    }
+
+    fn tsig_error(input: Node) -> Result<u16> {
+        assert_eq!(input.as_rule(), Rule::tsig_error);
+
+        match TSIG::error_from_str(input.as_str()) {
+            Some(error) => Ok(error),
+            None => Err(input.error(format!(
+                "invalid error '{}': expected a number, or a name such as 'BADSIG'",
+                input.as_str()
+            ))),
+        }
+    }
+
+    #[alias(resource)]
+    fn resource_tkey(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_tkey);
+
+        let (algorithm, inception, expiration, mode, error, key, other_data) = match_nodes!(input.into_children();
+            [domain(algorithm), inception, expiration, mode, tsig_error(error), sized_data(key), sized_data(other_data)] => (algorithm, inception, expiration, mode, error, key, other_data),
+        );
+
+        Ok(Resource::TKEY(TKEY {
+            algorithm: algorithm.to_string(),
+            inception: Self::parse_time(&inception, "inception")?,
+            expiration: Self::parse_time(&expiration, "expiration")?,
+            mode: Self::parse_int(&mode, "mode")?,
+            error,
+            key,
+            other_data,
+        }))
+    }
+
+    #[alias(resource)]
+    fn resource_tsig(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::resource_tsig);
+
+        let (algorithm, time_signed, fudge, mac, original_id, error, other_data) = match_nodes!(input.into_children();
+            [domain(algorithm), time_signed, fudge, sized_data(mac), original_id, tsig_error(error), sized_data(other_data)] => (algorithm, time_signed, fudge, mac, original_id, error, other_data),
+        );
+
+        // The time signed is a 48 bit number.
+        let time_signed_value: u64 = Self::parse_int(&time_signed, "time signed")?;
+        if time_signed_value > 0xffff_ffff_ffff {
+            return Err(time_signed.error("value out of range for time signed"));
+        }
+
+        Ok(Resource::TSIG(TSIG {
+            algorithm: algorithm.to_string(),
+            time_signed: time_signed_value,
+            fudge: Self::parse_int(&fudge, "fudge")?,
+            mac,
+            original_id: Self::parse_int(&original_id, "original ID")?,
+            error,
+            other_data,
+        }))
+    }
+
     #[alias(entry)]
     fn origin(input: Node) -> Result<Entry> {
         assert_eq!(input.as_rule(), Rule::origin);
@@ -175,9 +1130,18 @@ impl ZoneParser {
 
         Ok(match_nodes!(input.into_children();
             [duration(ttl)] => Entry::TTL(ttl),
+            [custom_ttl(ttl)] => Entry::TTL(ttl),
         ))
     }
 
+    #[alias(entry)]
+    fn unknown_directive(input: Node) -> Result<Entry> {
+        assert_eq!(input.as_rule(), Rule::unknown_directive);
+
+        let name = input.as_str().split_whitespace().next().unwrap_or_default();
+        Err(input.error(format!("unsupported directive '{}'", name)))
+    }
+
     #[alias(entry)]
     fn record(input: Node) -> Result<Entry> {
         assert_eq!(input.as_rule(), Rule::record);
@@ -196,20 +1160,228 @@ impl ZoneParser {
         )
     }
 
-    pub fn file(input: Node) -> Result<Vec<Entry>> {
-        assert_eq!(input.as_rule(), Rule::file);
+    pub fn single_resource(input: Node) -> Result<Resource> {
+        assert_eq!(input.as_rule(), Rule::single_resource);
 
         match_nodes!(input.into_children();
-            [entry(entrys).., _EOI] => Ok(entrys.collect()),
+            [resource(resource), _EOI] => Ok(resource)
         )
     }
+
+    pub fn file(input: Node) -> Result<Vec<(Entry, Location)>> {
+        assert_eq!(input.as_rule(), Rule::file);
+
+        // Instead of match_nodes! iterate, so we can record where each entry was.
+        let mut entries = Vec::new();
+        for node in input.into_children() {
+            if node.as_rule() == Rule::EOI {
+                break;
+            }
+
+            let (line, column) = node.as_span().start_pos().line_col();
+            entries.push((Self::entry(node)?, Location { line, column }));
+        }
+
+        Ok(entries)
+    }
 }
 
 impl ZoneParser {
+    // Same as parse_with_userdata, but with grammar errors described in terms
+    // of the zone file, see describe_error.
+    #[allow(clippy::result_large_err)]
+    pub(crate) fn parse_input<'i>(
+        rule: Rule,
+        input: &'i str,
+        options: &'i ZoneOptions,
+    ) -> Result<Nodes<'i, Rule, &'i ZoneOptions>> {
+        Self::parse_with_userdata(rule, input, options)
+            .map_err(|err| describe_error(err, rule, input))
+    }
+
+    // Returns the type of a generic record, which may be a mnemonic, or the
+    // "TYPE<n>" form from rfc3597. Types that are not supported are returned
+    // as the Err number.
+    #[allow(clippy::result_large_err)]
+    fn generic_type(input: &Node) -> Result<std::result::Result<Type, u16>> {
+        match type_status(input.as_str()) {
+            TypeStatus::Supported(r#type) => Ok(Ok(r#type)),
+            TypeStatus::Unsupported(n) => Ok(Err(n)),
+            status => Err(Self::type_error(input, status)),
+        }
+    }
+
+    // type_error returns the error for a record type that can't be used.
+    fn type_error(input: &Node, status: TypeStatus) -> Error<Rule> {
+        match status {
+            TypeStatus::Obsolete(rfc) => input.error(format!(
+                "{} is obsolete ({})",
+                input.as_str().to_ascii_uppercase(),
+                rfc
+            )),
+            _ => input.error(format!("unknown record type '{}'", input.as_str())),
+        }
+    }
+
+    // parse_int parses the node as an integer, returning a error naming the
+    // field if the value is too large, or uses duration units.
+    #[allow(clippy::result_large_err)]
+    fn parse_int<T>(input: &Node, field: &str) -> Result<T>
+    where
+        T: FromStr<Err = ParseIntError>,
+    {
+        if input.as_str().contains(|c: char| c.is_ascii_alphabetic()) {
+            return Err(input.error(format!(
+                "invalid {} '{}': expected a number, duration units (such as '1h') are only allowed for TTLs and SOA timers",
+                field,
+                input.as_str()
+            )));
+        }
+
+        match input.as_str().parse() {
+            Ok(i) => Ok(i),
+            Err(e) if *e.kind() == IntErrorKind::PosOverflow => {
+                Err(input.error(format!("value out of range for {}", field)))
+            }
+            Err(e) => Err(input.error(e)),
+        }
+    }
+
+    // Parses a character-string that must be valid UTF-8, such as a DOA
+    // media type.
+    #[allow(clippy::result_large_err)]
+    fn utf8_string(input: Node, field: &str) -> Result<String> {
+        match String::from_utf8(Self::char_string(input.clone())?) {
+            Ok(value) => Ok(value),
+            Err(e) => Err(input.error(format!("invalid {}: {}", field, e))),
+        }
+    }
+
+    // Parses a GPOS value, checking it is a decimal number, between -limit
+    // and limit (if given).
+    #[allow(clippy::result_large_err)]
+    fn gpos_value(input: Node, field: &str, limit: Option<f64>) -> Result<String> {
+        let value = match String::from_utf8(Self::char_string(input.clone())?) {
+            Ok(value) => value,
+            Err(e) => return Err(input.error(e)),
+        };
+
+        match (value.parse::<f64>(), limit) {
+            (Ok(n), Some(limit)) if n.is_finite() && n.abs() <= limit => Ok(value),
+            (Ok(n), None) if n.is_finite() => Ok(value),
+            (_, Some(limit)) => Err(input.error(format!(
+                "invalid {} '{}': expected a number between -{} and {}",
+                field, value, limit, limit
+            ))),
+            (_, None) => Err(input.error(format!(
+                "invalid {} '{}': expected a number",
+                field, value
+            ))),
+        }
+    }
+
+    // Returns the TTL from the ZoneOptions::ttl_parser, if there is one, and
+    // it understood the TTL.
+    fn parse_custom_ttl(input: &Node) -> Option<Duration> {
+        input
+            .user_data()
+            .ttl_parser
+            .and_then(|parser| parser(input.as_str()))
+    }
+
+    // check_ttl returns a error if the TTL is larger than allowed.
+    #[allow(clippy::result_large_err)]
+    fn check_ttl(input: &Node, ttl: Duration) -> Result<Duration> {
+        if ttl.as_secs() > MAX_TTL {
+            return Err(input.error(format!(
+                "value out of range for TTL, the maximum is {}",
+                MAX_TTL
+            )));
+        }
+
+        Ok(ttl)
+    }
+
+    // parse_duration parses the node as a number of seconds, optionally using
+    // BIND style units, e.g "1h30m". Durations (such as TTLs) are 32 bit
+    // values on the wire, so are limited to a u32.
+    #[allow(clippy::result_large_err)]
+    fn parse_duration(input: &Node, field: &str) -> Result<Duration> {
+        let out_of_range = || input.error(format!("value out of range for {}", field));
+
+        let mut secs: u32 = 0;
+        let mut value: u32 = 0;
+        for c in input.as_str().chars() {
+            if let Some(digit) = c.to_digit(10) {
+                value = value
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add(digit))
+                    .ok_or_else(out_of_range)?;
+                continue;
+            }
+
+            if input.user_data().strict {
+                return Err(input.error(format!(
+                    "BIND style duration units (such as '1h') are not allowed in strict mode for {}",
+                    field
+                )));
+            }
+
+            let unit = match c.to_ascii_lowercase() {
+                'w' => 7 * 24 * 60 * 60,
+                'd' => 24 * 60 * 60,
+                'h' => 60 * 60,
+                'm' => 60,
+                's' => 1,
+                _ => unreachable!("unexpected duration unit '{}'", c),
+            };
+
+            secs = value
+                .checked_mul(unit)
+                .and_then(|v| v.checked_add(secs))
+                .ok_or_else(out_of_range)?;
+            value = 0;
+        }
+
+        // Any trailing value without a unit is in seconds.
+        secs = secs.checked_add(value).ok_or_else(out_of_range)?;
+
+        Ok(Duration::from_secs(secs.into()))
+    }
+
+    // parse_time parses the node as a signature time, either formatted as
+    // "YYYYMMDDHHmmSS", or as a number of seconds.
+    #[allow(clippy::result_large_err)]
+    fn parse_time(input: &Node, field: &str) -> Result<u32> {
+        match SIG::time_from_str(input.as_str()) {
+            Some(time) => Ok(time),
+            None => Err(input.error(format!("invalid time for {}", field))),
+        }
+    }
+
+    // parse_svcb parses the shared SVCB and HTTPS format.
+    #[allow(clippy::result_large_err)]
+    fn parse_svcb(input: Node) -> Result<SVCB> {
+        let mut children = input.children();
+        let priority = children.next().unwrap();
+        let target = children.next().unwrap();
+
+        let params = children
+            .map(Self::svc_param)
+            .collect::<Result<Vec<SvcParam>>>()?;
+
+        Ok(SVCB {
+            priority: Self::parse_int(&priority, "priority")?,
+            target: Self::domain(target)?.to_string(),
+            params,
+        })
+    }
+
     // parse_record does the heavy lifting parsing a single record entry.
     // This is in a seperate ZoneParser impl, due to limitations with
     // `#[pest_consume::parser]` which does not allow aliased methods to be
     // called, or used in match_nodes.
+    #[allow(clippy::result_large_err)]
     fn parse_record(input: Node) -> Result<Record> {
         assert_eq!(input.as_rule(), Rule::record);
 
@@ -239,11 +1411,17 @@ impl ZoneParser {
 
                     record.ttl = Some(Self::duration(node)?)
                 }
+                Rule::custom_ttl => {
+                    assert!(record.ttl.is_none(), "record ttl was set twice");
+
+                    record.ttl = Some(Self::custom_ttl(node)?)
+                }
                 Rule::class => {
                     assert!(record.class.is_none(), "record class was set twice");
 
                     record.class = Some(Self::class(node)?)
                 }
+                Rule::unknown_class => record.class = Some(Self::unknown_class(node)?),
 
                 _ => {
                     // Rule::resource have many aliases, try one of them.
@@ -254,7 +1432,17 @@ impl ZoneParser {
                                 "record resource was set twice"
                             );
 
-                            record.resource = Self::resource(node)?
+                            record.resource = Self::resource(node.clone())?;
+
+                            if let Some(allowed) = &node.user_data().allowed_types {
+                                let r#type = record.resource.r#type();
+                                if r#type == Type::Reserved || !allowed.contains(&r#type) {
+                                    return Err(node.error(format!(
+                                        "record type '{}' is not allowed",
+                                        record.resource.type_name()
+                                    )));
+                                }
+                            }
                         }
 
                         _ => panic!("Unexpected token: {:?} '{:?}'", rule, node.as_str()),
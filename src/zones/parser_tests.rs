@@ -7,11 +7,38 @@ mod tests {
     use crate::zones::File;
     use crate::zones::Record;
     use crate::zones::Resource;
+    use crate::zones::ZoneOptions;
     use crate::Class;
+    use crate::Type;
     use core::time::Duration;
+    use pest::error::LineColLocation;
     use pretty_assertions::assert_eq;
     use std::str::FromStr;
 
+    // Example from https://datatracker.ietf.org/doc/html/rfc1035#section-5.3
+    static RFC1035_EXAMPLE: &str = "$ORIGIN ISI.EDU.
+            @   IN  SOA     VENERA      Action\\.domains (
+                                             20     ; SERIAL
+                                             7200   ; REFRESH
+                                             600    ; RETRY
+                                             3600000; EXPIRE
+                                             60)    ; MINIMUM
+
+                    NS      A.ISI.EDU.
+                    NS      VENERA
+                    NS      VAXA
+                    MX      10      VENERA
+                    MX      20      VAXA
+
+            A       A       26.3.0.103
+
+            VENERA  A       10.1.0.52
+                    A       128.9.0.32
+
+            VAXA    A       10.2.0.27
+                    A       128.9.0.33
+            ";
+
     #[test]
     fn test_parse_combinations() {
         let tests = vec![
@@ -74,7 +101,7 @@ mod tests {
             (
                 "IN       A       26.3.0.103",
                 Record {
-                    name: None, // TODO It thinks IN is the name
+                    name: None,
                     ttl: None,
                     class: Some(Class::Internet),
                     resource: Resource::A("26.3.0.103".parse().unwrap()),
@@ -83,7 +110,7 @@ mod tests {
             (
                 "1       A       26.3.0.103",
                 Record {
-                    name: None, // TODO It thinks 1 is the name
+                    name: None,
                     ttl: Some(Duration::new(1, 0)),
                     class: None,
                     resource: Resource::A("26.3.0.103".parse().unwrap()),
@@ -101,12 +128,103 @@ mod tests {
             (
                 "1       IN       A       26.3.0.103",
                 Record {
-                    name: None, // TODO It thinks 1 is the name
+                    name: None,
                     ttl: Some(Duration::new(1, 0)),
                     class: Some(Class::Internet),
                     resource: Resource::A("26.3.0.103".parse().unwrap()),
                 },
             ),
+            // The same again, with a unambiguous domain, and a TTL with units.
+            (
+                "www     1h      IN      A       26.3.0.103",
+                Record {
+                    name: Some("www".to_string()),
+                    ttl: Some(Duration::new(3600, 0)),
+                    class: Some(Class::Internet),
+                    resource: Resource::A("26.3.0.103".parse().unwrap()),
+                },
+            ),
+            (
+                "www     IN      1h      A       26.3.0.103",
+                Record {
+                    name: Some("www".to_string()),
+                    ttl: Some(Duration::new(3600, 0)),
+                    class: Some(Class::Internet),
+                    resource: Resource::A("26.3.0.103".parse().unwrap()),
+                },
+            ),
+            (
+                "www     1h      A       26.3.0.103",
+                Record {
+                    name: Some("www".to_string()),
+                    ttl: Some(Duration::new(3600, 0)),
+                    class: None,
+                    resource: Resource::A("26.3.0.103".parse().unwrap()),
+                },
+            ),
+            (
+                "www     IN      A       26.3.0.103",
+                Record {
+                    name: Some("www".to_string()),
+                    ttl: None,
+                    class: Some(Class::Internet),
+                    resource: Resource::A("26.3.0.103".parse().unwrap()),
+                },
+            ),
+            (
+                "1h      IN      A       26.3.0.103",
+                Record {
+                    name: None,
+                    ttl: Some(Duration::new(3600, 0)),
+                    class: Some(Class::Internet),
+                    resource: Resource::A("26.3.0.103".parse().unwrap()),
+                },
+            ),
+            (
+                "IN      1h      A       26.3.0.103",
+                Record {
+                    name: None,
+                    ttl: Some(Duration::new(3600, 0)),
+                    class: Some(Class::Internet),
+                    resource: Resource::A("26.3.0.103".parse().unwrap()),
+                },
+            ),
+            (
+                "1h      A       26.3.0.103",
+                Record {
+                    name: None,
+                    ttl: Some(Duration::new(3600, 0)),
+                    class: None,
+                    resource: Resource::A("26.3.0.103".parse().unwrap()),
+                },
+            ),
+            (
+                "IN      A       26.3.0.103",
+                Record {
+                    name: None,
+                    ttl: None,
+                    class: Some(Class::Internet),
+                    resource: Resource::A("26.3.0.103".parse().unwrap()),
+                },
+            ),
+            (
+                "3600    CLASS1  A       26.3.0.103",
+                Record {
+                    name: None,
+                    ttl: Some(Duration::new(3600, 0)),
+                    class: Some(Class::Internet),
+                    resource: Resource::A("26.3.0.103".parse().unwrap()),
+                },
+            ),
+            (
+                "CLASS1  3600    A       26.3.0.103",
+                Record {
+                    name: None,
+                    ttl: Some(Duration::new(3600, 0)),
+                    class: Some(Class::Internet),
+                    resource: Resource::A("26.3.0.103".parse().unwrap()),
+                },
+            ),
         ];
 
         for (input, want) in tests {
@@ -138,6 +256,83 @@ mod tests {
                     resource: Resource::A("10.1.0.52".parse().unwrap()),
                 },
             ),
+            // The generic class syntax from rfc3597.
+            (
+                "VENERA  CLASS1  A  10.1.0.52",
+                Record {
+                    name: Some("VENERA".to_string()),
+                    ttl: None,
+                    class: Some(Class::Internet),
+                    resource: Resource::A("10.1.0.52".parse().unwrap()),
+                },
+            ),
+            (
+                "class3  3600  class3  TXT  \"hello\"",
+                Record {
+                    name: Some("class3".to_string()),
+                    ttl: Some(Duration::new(3600, 0)),
+                    class: Some(Class::Chaos),
+                    resource: Resource::TXT(TXT::from("hello")),
+                },
+            ),
+            (
+                "@ GPOS \"116.8652\" \"-32.6882\" \"10.0\"",
+                Record {
+                    name: Some("@".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::GPOS(GPOS {
+                        longitude: "116.8652".to_string(),
+                        latitude: "-32.6882".to_string(),
+                        altitude: "10.0".to_string(),
+                    }),
+                },
+            ),
+            (
+                "@ GPOS -180 90 -0.5",
+                Record {
+                    name: Some("@".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::GPOS(GPOS {
+                        longitude: "-180".to_string(),
+                        latitude: "90".to_string(),
+                        altitude: "-0.5".to_string(),
+                    }),
+                },
+            ),
+            // Wildcards, see rfc4592.
+            (
+                "*  A  192.0.2.1",
+                Record {
+                    name: Some("*".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::A("192.0.2.1".parse().unwrap()),
+                },
+            ),
+            (
+                "*.example.com.  MX  10  mail.example.com.",
+                Record {
+                    name: Some("*.example.com.".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::MX(MX {
+                        preference: 10,
+                        exchange: "mail.example.com.".to_string(),
+                    }),
+                },
+            ),
+            // Lowercase words are not mistaken for a class.
+            (
+                "xx  A  1.2.3.4",
+                Record {
+                    name: Some("xx".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::A("1.2.3.4".parse().unwrap()),
+                },
+            ),
             // All the different record types.
             (
                 "A       128.9.0.32",
@@ -166,6 +361,15 @@ mod tests {
                     resource: Resource::CNAME("example.com".to_string()),
                 },
             ),
+            (
+                "sub     CNAME @",
+                Record {
+                    name: Some("sub".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::CNAME("@".to_string()),
+                },
+            ),
             (
                 "NS      VAXA",
                 Record {
@@ -213,267 +417,951 @@ mod tests {
                     }),
                 },
             ),
-            // Whitespace examples
             (
-                "   VENERA A 10.1.0.52",
+                "TXT     \"a\\195\\169b\"",
                 Record {
-                    name: Some("VENERA".to_string()),
+                    name: None,
                     ttl: None,
                     class: None,
-                    resource: Resource::A("10.1.0.52".parse().unwrap()),
+                    resource: Resource::TXT(TXT(vec![b"a\xc3\xa9b".to_vec()])),
                 },
             ),
             (
-                "VENERA A 10.1.0.52   ",
+                "@ TXT \"v=spf1 -all\" \"say \\\"hi\\\"\" unquoted\\;text",
                 Record {
-                    name: Some("VENERA".to_string()),
+                    name: Some("@".to_string()),
                     ttl: None,
                     class: None,
-                    resource: Resource::A("10.1.0.52".parse().unwrap()),
+                    resource: Resource::TXT(TXT(vec![
+                        b"v=spf1 -all".to_vec(),
+                        b"say \"hi\"".to_vec(),
+                        b"unquoted;text".to_vec(),
+                    ])),
                 },
             ),
             (
-                "   VENERA A 10.1.0.52   ",
+                "SPF     \"v=spf1 -all\"",
                 Record {
-                    name: Some("VENERA".to_string()),
+                    name: None,
                     ttl: None,
                     class: None,
-                    resource: Resource::A("10.1.0.52".parse().unwrap()),
+                    resource: Resource::SPF(TXT::from("v=spf1 -all")),
                 },
             ),
-            // Comments
             (
-                "VENERA A 10.1.0.52;Blah",
+                "@ WKS 192.0.2.1 6 25 80",
                 Record {
-                    name: Some("VENERA".to_string()),
+                    name: Some("@".to_string()),
                     ttl: None,
                     class: None,
-                    resource: Resource::A("10.1.0.52".parse().unwrap()),
+                    resource: Resource::WKS(WKS {
+                        address: "192.0.2.1".parse().unwrap(),
+                        protocol: 6,
+                        ports: vec![25, 80],
+                    }),
                 },
             ),
-        ];
-
-        for (input, want) in tests {
-            match Record::from_str(input) {
-                Ok(got) => assert_eq!(got, want),
-                Err(err) => panic!("'{}' Failed:\n{}", input, err),
-            }
-        }
-    }
-
-    #[test]
-    fn test_parse_record_errors() {
-        let tests = vec![
-            // We don't allow blank entries
-            "",
-            // For sinle records, we don't allow new lines
-            "VENERA A 10.1.0.52\n",
-            "\nVENERA A 10.1.0.52\n",
-        ];
-
-        for input in tests {
-            match Record::from_str(input) {
-                Ok(_got) => panic!("'{}' incorrectly parsed correctly", input),
-                Err(_err) => (), // Expect a error. TODO Maybe check the error message,
-            }
-        }
-    }
-
-    // TODO Take test from https://datatracker.ietf.org/doc/html/rfc2308#section-10
-
-    // Test Full files
-    #[test]
-    fn test_parse() {
-        // TODO add some bad data examples
-        // "$ORIGIN @"
-        // "$TTL blah"
-
-        let tests = vec![
-            // The control entry types
-            ("$ORIGIN 1.example.org.", vec![Entry::Origin("1.example.org.".to_string())]),
-            ("$TTL 3600", vec![Entry::TTL(Duration::new(3600, 0))]),
-
-            // Wrapped with newlines
-            ("\n\n$ORIGIN 2.example.org.\n", vec![Entry::Origin("2.example.org.".to_string())]),
-
-            // Wrapped with various whitespace
-            ("\n \t \n \t \n \t $ORIGIN 3.example.org.  \n \t \n \t \n  ", vec![Entry::Origin("3.example.org.".to_string())]),
-
-            // TODO Include
-
-            // Examples modified from https://nsd.docs.nlnetlabs.nl/en/latest/reference/grammar.html
-            ("SOA    soa    soa    ( 1 2 3 4 5 )",
-                vec![
-                    Entry::Record(Record {
-                        resource: Resource::SOA(SOA {
-                            mname: "soa".to_string(),
-                            rname: "soa".to_string(),
-                            serial: 1,
-                            refresh: Duration::new(2, 0),
-                            retry: Duration::new(3, 0),
-                            expire: Duration::new(4, 0),
-                            minimum: Duration::new(5, 0),
-                        }),
-                        ..Default::default()
-                    }),
-                ]),
-
-            ("SOA    soa    soa    ( 1 2 ) ( 3 4 ) ( 5 )",
-                vec![
-                    Entry::Record(Record {
-                        resource: Resource::SOA(SOA {
-                            mname: "soa".to_string(),
-                            rname: "soa".to_string(), // TODO Fix the \\ thing
-                            serial: 1,
-                            refresh: Duration::new(2, 0),
-                            retry: Duration::new(3, 0),
-                            expire: Duration::new(4, 0),
-                            minimum: Duration::new(5, 0),
-                        }),
-                        ..Default::default()
+            (
+                "@ AFSDB 1 afsdb.example.com.",
+                Record {
+                    name: Some("@".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::AFSDB(AFSDB {
+                        subtype: 1,
+                        hostname: "afsdb.example.com.".to_string(),
                     }),
-                ]),
-
-            // Compact together
-            ("SOA soa soa (1 2)(3 4)(5)",
-                vec![
-                    Entry::Record(Record {
-                        resource: Resource::SOA(SOA {
-                            mname: "soa".to_string(),
-                            rname: "soa".to_string(), // TODO Fix the \\ thing
-                            serial: 1,
-                            refresh: Duration::new(2, 0),
-                            retry: Duration::new(3, 0),
-                            expire: Duration::new(4, 0),
-                            minimum: Duration::new(5, 0),
-                        }),
-                        ..Default::default()
+                },
+            ),
+            (
+                "host RT 10 relay.example.com.",
+                Record {
+                    name: Some("host".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::RT(RT {
+                        preference: 10,
+                        host: "relay.example.com.".to_string(),
                     }),
-                ]),
-
+                },
+            ),
             (
-            // Examples from https://datatracker.ietf.org/doc/html/rfc1035#section-5.3
-            "$ORIGIN ISI.EDU.
-            @   IN  SOA     VENERA      Action\\.domains (
-                                             20     ; SERIAL
-                                             7200   ; REFRESH
-                                             600    ; RETRY
-                                             3600000; EXPIRE
-                                             60)    ; MINIMUM
-
-                    NS      A.ISI.EDU.
-                    NS      VENERA
-                    NS      VAXA
-                    MX      10      VENERA
-                    MX      20      VAXA
-
-            A       A       26.3.0.103
-
-            VENERA  A       10.1.0.52
-                    A       128.9.0.32
-
-            VAXA    A       10.2.0.27
-                    A       128.9.0.33
-            ", vec![
-                Entry::Origin("ISI.EDU.".to_string()),
-                Entry::Record(Record {
+                // From https://datatracker.ietf.org/doc/html/rfc1183#section-3.1
+                "host X25 \"311061700956\"",
+                Record {
+                    name: Some("host".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::X25("311061700956".to_string()),
+                },
+            ),
+            (
+                // From https://datatracker.ietf.org/doc/html/rfc1183#section-3.2
+                "host ISDN \"150862028003217\" \"004\"",
+                Record {
+                    name: Some("host".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::ISDN(ISDN {
+                        address: "150862028003217".to_string(),
+                        subaddress: Some("004".to_string()),
+                    }),
+                },
+            ),
+            (
+                "host IN ISDN 150862028003217",
+                Record {
+                    name: Some("host".to_string()),
+                    ttl: None,
+                    class: Some(Class::Internet),
+                    resource: Resource::ISDN(ISDN {
+                        address: "150862028003217".to_string(),
+                        subaddress: None,
+                    }),
+                },
+            ),
+            (
+                "host IN ATMA 39.246f.000e7c9c0312.0001.0001.000012345678.00",
+                Record {
+                    name: Some("host".to_string()),
+                    ttl: None,
+                    class: Some(Class::Internet),
+                    resource: Resource::ATMA(ATMA::AESA(vec![
+                        0x39, 0x24, 0x6f, 0x00, 0x0e, 0x7c, 0x9c, 0x03, 0x12, 0x00, 0x01, 0x00,
+                        0x01, 0x00, 0x00, 0x12, 0x34, 0x56, 0x78, 0x00,
+                    ])),
+                },
+            ),
+            (
+                "host ATMA +358.400.1234567",
+                Record {
+                    name: Some("host".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::ATMA(ATMA::E164("3584001234567".to_string())),
+                },
+            ),
+            (
+                // From https://datatracker.ietf.org/doc/html/rfc1706#section-6
+                "host IN NSAP 0x47.0005.80.005a00.0000.0001.e133.ffffff000161.00",
+                Record {
+                    name: Some("host".to_string()),
+                    ttl: None,
+                    class: Some(Class::Internet),
+                    resource: Resource::NSAP(vec![
+                        0x47, 0x00, 0x05, 0x80, 0x00, 0x5a, 0x00, 0x00, 0x00, 0x00, 0x01, 0xe1,
+                        0x33, 0xff, 0xff, 0xff, 0x00, 0x01, 0x61, 0x00,
+                    ]),
+                },
+            ),
+            (
+                "NSAP 0X4700058000",
+                Record {
+                    name: None,
+                    ttl: None,
+                    class: None,
+                    resource: Resource::NSAP(vec![0x47, 0x00, 0x05, 0x80, 0x00]),
+                },
+            ),
+            (
+                "1.0.0.0.6.1.0.0.0.f.f.f.f.f.f.3.3.1.e.1.0.0.0.0.0.0.0.0.0.a.5.0.0.0.8.5.0.0.0.7.4.NSAP.INT. NSAP-PTR host.example.com.",
+                Record {
+                    name: Some("1.0.0.0.6.1.0.0.0.f.f.f.f.f.f.3.3.1.e.1.0.0.0.0.0.0.0.0.0.a.5.0.0.0.8.5.0.0.0.7.4.NSAP.INT.".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::NSAP_PTR("host.example.com.".to_string()),
+                },
+            ),
+            (
+                "_sip._tcp SRV 0 5 5060 sip.example.com.",
+                Record {
+                    name: Some("_sip._tcp".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::SRV(SRV {
+                        priority: 0,
+                        weight: 5,
+                        port: 5060,
+                        name: "sip.example.com.".to_string(),
+                    }),
+                },
+            ),
+            (
+                "_ldap._tcp IN SRV ( 10 0 389 ldap )",
+                Record {
+                    name: Some("_ldap._tcp".to_string()),
+                    ttl: None,
+                    class: Some(Class::Internet),
+                    resource: Resource::SRV(SRV {
+                        priority: 10,
+                        weight: 0,
+                        port: 389,
+                        name: "ldap".to_string(),
+                    }),
+                },
+            ),
+            (
+                "_http._tcp URI 10 1 \"https://example.com/\"",
+                Record {
+                    name: Some("_http._tcp".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::URI(URI {
+                        priority: 10,
+                        weight: 1,
+                        target: "https://example.com/".to_string(),
+                    }),
+                },
+            ),
+            (
+                "@ URI 1 0 \"https://example.com/a path;with \\\"quotes\\\"\"",
+                Record {
                     name: Some("@".to_string()),
                     ttl: None,
+                    class: None,
+                    resource: Resource::URI(URI {
+                        priority: 1,
+                        weight: 0,
+                        target: "https://example.com/a path;with \"quotes\"".to_string(),
+                    }),
+                },
+            ),
+            (
+                "APL 1:192.0.2.0/24 !1:192.0.2.128/25",
+                Record {
+                    name: None,
+                    ttl: None,
+                    class: None,
+                    resource: Resource::APL(APL(vec![
+                        APLItem {
+                            negation: false,
+                            address: "192.0.2.0".parse().unwrap(),
+                            prefix: 24,
+                        },
+                        APLItem {
+                            negation: true,
+                            address: "192.0.2.128".parse().unwrap(),
+                            prefix: 25,
+                        },
+                    ])),
+                },
+            ),
+            (
+                "net 3600 IN APL 2:2001:db8::/32",
+                Record {
+                    name: Some("net".to_string()),
+                    ttl: Some(Duration::new(3600, 0)),
                     class: Some(Class::Internet),
-                    resource: Resource::SOA(SOA {
-                        mname: "VENERA".to_string(),
-                        rname: "Action\\.domains".to_string(), // TODO Fix the \\ thing
-                        serial: 20,
-                        refresh: Duration::new(7200, 0),
-                        retry: Duration::new(600, 0),
-                        expire: Duration::new(3600000, 0),
-                        minimum: Duration::new(60, 0),
+                    resource: Resource::APL(APL(vec![APLItem {
+                        negation: false,
+                        address: "2001:db8::".parse().unwrap(),
+                        prefix: 32,
+                    }])),
+                },
+            ),
+            (
+                "@ CERT PKIX 12345 8 Y2VydGlm aWNhdGU=",
+                Record {
+                    name: Some("@".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::CERT(CERT {
+                        cert_type: 1,
+                        key_tag: 12345,
+                        algorithm: 8,
+                        certificate: b"certificate".to_vec(),
                     }),
-                }),
-                Entry::Record(Record {
-                    resource: Resource::NS("A.ISI.EDU.".to_string()),
-                    ..Default::default()
-                }),
-                Entry::Record(Record {
-                    resource: Resource::NS("VENERA".to_string()),
-                    ..Default::default()
-                }),
-                Entry::Record(Record {
-                    resource: Resource::NS("VAXA".to_string()),
-                    ..Default::default()
-                }),
-                Entry::Record(Record {
-                    resource: Resource::MX(MX{
+                },
+            ),
+            (
+                "CERT 254 0 0 AAEC",
+                Record {
+                    name: None,
+                    ttl: None,
+                    class: None,
+                    resource: Resource::CERT(CERT {
+                        cert_type: 254,
+                        key_tag: 0,
+                        algorithm: 0,
+                        certificate: vec![0, 1, 2],
+                    }),
+                },
+            ),
+            (
+                "@ CSYNC 1278700841 3 A NS AAAA",
+                Record {
+                    name: Some("@".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::CSYNC(CSYNC {
+                        serial: 1278700841,
+                        flags: 3,
+                        types: vec![Type::A, Type::NS, Type::AAAA],
+                    }),
+                },
+            ),
+            (
+                // ILNP records, from https://datatracker.ietf.org/doc/html/rfc6742#section-3
+                "host NID 10 0014:4fff:ff20:ee64",
+                Record {
+                    name: Some("host".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::NID(NID {
                         preference: 10,
-                        exchange: "VENERA".to_string()
+                        node_id: 0x0014_4fff_ff20_ee64,
                     }),
-                    ..Default::default()
-                }),
-                Entry::Record(Record {
-                    resource: Resource::MX(MX{
-                        preference: 20,
-                        exchange: "VAXA".to_string(),
+                },
+            ),
+            (
+                "host L32 10 10.1.2.0",
+                Record {
+                    name: Some("host".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::L32(L32 {
+                        preference: 10,
+                        locator: "10.1.2.0".parse().unwrap(),
                     }),
-                    ..Default::default()
-                }),
-                Entry::Record(Record {
-                    name: Some("A".to_string()),
-                    resource: Resource::A("26.3.0.103".parse().unwrap()),
-                    ..Default::default()
-                }),
-                Entry::Record(Record {
-                    name: Some("VENERA".to_string()),
-                    resource: Resource::A("10.1.0.52".parse().unwrap()),
-                    ..Default::default()
-                }),
-                Entry::Record(Record {
-                    resource: Resource::A("128.9.0.32".parse().unwrap()),
-                    ..Default::default()
-                }),
-                Entry::Record(Record {
-                    name: Some("VAXA".to_string()),
-                    resource: Resource::A("10.2.0.27".parse().unwrap()),
-                    ..Default::default()
-                }),
-                Entry::Record(Record {
-                    resource: Resource::A("128.9.0.33".parse().unwrap()),
-                    ..Default::default()
-                }),
-            ]),
-
-            // Examples from https://en.wikipedia.org/wiki/Zone_file
-            ("
-            $ORIGIN example.com.     ; designates the start of this zone file in the namespace
-            $TTL 3600                ; default expiration time (in seconds) of all RRs without their own TTL value
-            example.com.  IN  SOA   ns.example.com. username.example.com. ( 2020091025 7200 3600 1209600 3600 )
-            example.com.  IN  NS    ns                    ; ns.example.com is a nameserver for example.com
-            example.com.  IN  NS    ns.somewhere.example. ; ns.somewhere.example is a backup nameserver for example.com
-            example.com.  IN  MX    10 mail.example.com.  ; mail.example.com is the mailserver for example.com
-            @             IN  MX    20 mail2.example.com. ; equivalent to above line, '@' represents zone origin
-            @             IN  MX    50 mail3              ; equivalent to above line, but using a relative host name
-            example.com.  IN  A     192.0.2.1             ; IPv4 address for example.com
-                          IN  AAAA  2001:db8:10::1        ; IPv6 address for example.com
-            ns            IN  A     192.0.2.2             ; IPv4 address for ns.example.com
-                          IN  AAAA  2001:db8:10::2        ; IPv6 address for ns.example.com
-            www           IN  CNAME example.com.          ; www.example.com is an alias for example.com
-            wwwtest       IN  CNAME www                   ; wwwtest.example.com is another alias for www.example.com
-            mail          IN  A     192.0.2.3             ; IPv4 address for mail.example.com
-            mail2         IN  A     192.0.2.4             ; IPv4 address for mail2.example.com
-            mail3         IN  A     192.0.2.5             ; IPv4 address for mail3.example.com",
-            vec![
-                Entry::Origin("example.com.".to_string()),
-                Entry::TTL(Duration::new(3600, 0)),
-                Entry::Record(Record {
-                        name: Some("example.com.".to_string(),),
-                        ttl: None,
-                        class: Some(Class::Internet,),
-                        resource: Resource::SOA(
-                            SOA {
-                                mname: "ns.example.com.".to_string(),
-                                rname: "username.example.com.".to_string(),
-                                serial: 2020091025,
-                                refresh: Duration::new(7200, 0),
-                                retry: Duration::new(3600, 0),
+                },
+            ),
+            (
+                "host L64 10 2001:0DB8:1140:1000",
+                Record {
+                    name: Some("host".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::L64(L64 {
+                        preference: 10,
+                        locator: 0x2001_0db8_1140_1000,
+                    }),
+                },
+            ),
+            (
+                "host LP 10 l64-subnet1.example.com.",
+                Record {
+                    name: Some("host".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::LP(LP {
+                        preference: 10,
+                        fqdn: "l64-subnet1.example.com.".to_string(),
+                    }),
+                },
+            ),
+            (
+                // Examples from https://datatracker.ietf.org/doc/html/rfc7043#section-3.2
+                "host.example. 86400 IN EUI48 00-00-5e-00-53-2a",
+                Record {
+                    name: Some("host.example.".to_string()),
+                    ttl: Some(Duration::new(86400, 0)),
+                    class: Some(Class::Internet),
+                    resource: Resource::EUI48([0x00, 0x00, 0x5e, 0x00, 0x53, 0x2a]),
+                },
+            ),
+            (
+                "host.example. 86400 IN EUI64 00-00-5E-EF-10-00-00-2A",
+                Record {
+                    name: Some("host.example.".to_string()),
+                    ttl: Some(Duration::new(86400, 0)),
+                    class: Some(Class::Internet),
+                    resource: Resource::EUI64([0x00, 0x00, 0x5e, 0xef, 0x10, 0x00, 0x00, 0x2a]),
+                },
+            ),
+            (
+                // The owner is the hashed local part, see https://datatracker.ietf.org/doc/html/rfc8162#section-3
+                "c93f1e400f26708f98cb19d936620da35eec8f72e57f9eec01c1afd6._smimecert.example.com. SMIMEA 3 1 1 ( d2abde240d7cd3ee6b4b28c54df034b9 7983a1d16e8a410e4561cb106618e971 )",
+                Record {
+                    name: Some(
+                        "c93f1e400f26708f98cb19d936620da35eec8f72e57f9eec01c1afd6._smimecert.example.com."
+                            .to_string(),
+                    ),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::SMIMEA(TLSA {
+                        usage: 3,
+                        selector: 1,
+                        matching_type: 1,
+                        data: vec![
+                            0xd2, 0xab, 0xde, 0x24, 0x0d, 0x7c, 0xd3, 0xee, 0x6b, 0x4b, 0x28, 0xc5,
+                            0x4d, 0xf0, 0x34, 0xb9, 0x79, 0x83, 0xa1, 0xd1, 0x6e, 0x8a, 0x41, 0x0e,
+                            0x45, 0x61, 0xcb, 0x10, 0x66, 0x18, 0xe9, 0x71,
+                        ],
+                    }),
+                },
+            ),
+            (
+                // Child DNSSEC records, from https://datatracker.ietf.org/doc/html/rfc7344
+                "example.com. CDS 60485 5 1 ( 2BB183AF5F22588179A53B0A 98631FAD1A292118 )",
+                Record {
+                    name: Some("example.com.".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::CDS(DS {
+                        key_tag: 60485,
+                        algorithm: 5,
+                        digest_type: 1,
+                        digest: vec![
+                            0x2b, 0xb1, 0x83, 0xaf, 0x5f, 0x22, 0x58, 0x81, 0x79, 0xa5, 0x3b, 0x0a,
+                            0x98, 0x63, 0x1f, 0xad, 0x1a, 0x29, 0x21, 0x18,
+                        ],
+                    }),
+                },
+            ),
+            (
+                "example.com. CDNSKEY 257 3 8 cHVibGlj IGtleQ==",
+                Record {
+                    name: Some("example.com.".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::CDNSKEY(KEY {
+                        flags: 257,
+                        protocol: 3,
+                        algorithm: 8,
+                        public_key: b"public key".to_vec(),
+                    }),
+                },
+            ),
+            (
+                // The delete forms from https://datatracker.ietf.org/doc/html/rfc8078#section-4
+                "CDS 0 0 0 00",
+                Record {
+                    name: None,
+                    ttl: None,
+                    class: None,
+                    resource: Resource::CDS(DS {
+                        key_tag: 0,
+                        algorithm: 0,
+                        digest_type: 0,
+                        digest: vec![0],
+                    }),
+                },
+            ),
+            (
+                "CDNSKEY 0 3 0 AA==",
+                Record {
+                    name: None,
+                    ttl: None,
+                    class: None,
+                    resource: Resource::CDNSKEY(KEY {
+                        flags: 0,
+                        protocol: 3,
+                        algorithm: 0,
+                        public_key: vec![0],
+                    }),
+                },
+            ),
+            (
+                // Legacy DNSSEC records, from https://datatracker.ietf.org/doc/html/rfc2535
+                "example.com. KEY 256 3 1 cHVibGlj IGtleQ==",
+                Record {
+                    name: Some("example.com.".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::KEY(KEY {
+                        flags: 256,
+                        protocol: 3,
+                        algorithm: 1,
+                        public_key: b"public key".to_vec(),
+                    }),
+                },
+            ),
+            (
+                "host SIG A 1 3 86400 20030322173103 1045762263 2642 example.com. c2lnbmF0dXJl",
+                Record {
+                    name: Some("host".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::SIG(SIG {
+                        type_covered: Type::A,
+                        algorithm: 1,
+                        labels: 3,
+                        original_ttl: 86400,
+                        expiration: 1048354263,
+                        inception: 1045762263,
+                        key_tag: 2642,
+                        signer_name: "example.com.".to_string(),
+                        signature: b"signature".to_vec(),
+                    }),
+                },
+            ),
+            (
+                "@ AVC \"app-name:WOLFGANG|app-class:OAM\" business=yes",
+                Record {
+                    name: Some("@".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::AVC(TXT(vec![
+                        b"app-name:WOLFGANG|app-class:OAM".to_vec(),
+                        b"business=yes".to_vec(),
+                    ])),
+                },
+            ),
+            (
+                "@ DOA 0 1 2 \"\" aHR0cHM6Ly93d3cuaWFu YS5vcmcv",
+                Record {
+                    name: Some("@".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::DOA(DOA {
+                        enterprise: 0,
+                        doa_type: 1,
+                        location: 2,
+                        media_type: "".to_string(),
+                        data: b"https://www.iana.org/".to_vec(),
+                    }),
+                },
+            ),
+            (
+                "DOA 1234 5 1 image/png -",
+                Record {
+                    name: None,
+                    ttl: None,
+                    class: None,
+                    resource: Resource::DOA(DOA {
+                        enterprise: 1234,
+                        doa_type: 5,
+                        location: 1,
+                        media_type: "image/png".to_string(),
+                        data: vec![],
+                    }),
+                },
+            ),
+            (
+                "host. CLASS255 TSIG hmac-sha256. 1700000000 300 32 AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8= 12345 NOERROR 0",
+                Record {
+                    name: Some("host.".to_string()),
+                    ttl: None,
+                    class: Some(Class::Any),
+                    resource: Resource::TSIG(TSIG {
+                        algorithm: "hmac-sha256.".to_string(),
+                        time_signed: 1700000000,
+                        fudge: 300,
+                        mac: (0..32).collect(),
+                        original_id: 12345,
+                        error: 0,
+                        other_data: vec![],
+                    }),
+                },
+            ),
+            (
+                "host. TSIG hmac-sha256. 1700000000 300 0 1 badtime 6 AABlU/EA",
+                Record {
+                    name: Some("host.".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::TSIG(TSIG {
+                        algorithm: "hmac-sha256.".to_string(),
+                        time_signed: 1700000000,
+                        fudge: 300,
+                        mac: vec![],
+                        original_id: 1,
+                        error: 18,
+                        other_data: vec![0, 0, 0x65, 0x53, 0xf1, 0x00],
+                    }),
+                },
+            ),
+            (
+                "key. TKEY gss-tsig. 20231114221320 1700003600 3 0 4 AQIDBA== 0",
+                Record {
+                    name: Some("key.".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::TKEY(TKEY {
+                        algorithm: "gss-tsig.".to_string(),
+                        inception: 1700000000,
+                        expiration: 1700003600,
+                        mode: 3,
+                        error: 0,
+                        key: vec![1, 2, 3, 4],
+                        other_data: vec![],
+                    }),
+                },
+            ),
+            // Whitespace examples
+            (
+                "   VENERA A 10.1.0.52",
+                Record {
+                    name: Some("VENERA".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::A("10.1.0.52".parse().unwrap()),
+                },
+            ),
+            (
+                "VENERA A 10.1.0.52   ",
+                Record {
+                    name: Some("VENERA".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::A("10.1.0.52".parse().unwrap()),
+                },
+            ),
+            (
+                "   VENERA A 10.1.0.52   ",
+                Record {
+                    name: Some("VENERA".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::A("10.1.0.52".parse().unwrap()),
+                },
+            ),
+            // Any run of spaces and tabs separates the fields.
+            (
+                "A      A        1.2.3.4",
+                Record {
+                    name: Some("A".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::A("1.2.3.4".parse().unwrap()),
+                },
+            ),
+            (
+                "www \t \t3600\t\tIN    MX \t 10  \t  mail",
+                Record {
+                    name: Some("www".to_string()),
+                    ttl: Some(Duration::new(3600, 0)),
+                    class: Some(Class::Internet),
+                    resource: Resource::MX(MX {
+                        preference: 10,
+                        exchange: "mail".to_string(),
+                    }),
+                },
+            ),
+            // Comments
+            (
+                "VENERA A 10.1.0.52;Blah",
+                Record {
+                    name: Some("VENERA".to_string()),
+                    ttl: None,
+                    class: None,
+                    resource: Resource::A("10.1.0.52".parse().unwrap()),
+                },
+            ),
+        ];
+
+        for (input, want) in tests {
+            match Record::from_str(input) {
+                Ok(got) => assert_eq!(got, want),
+                Err(err) => panic!("'{}' Failed:\n{}", input, err),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_record_errors() {
+        let tests = vec![
+            // We don't allow blank entries
+            "",
+            // For sinle records, we don't allow new lines
+            "VENERA A 10.1.0.52\n",
+            "\nVENERA A 10.1.0.52\n",
+        ];
+
+        for input in tests {
+            match Record::from_str(input) {
+                Ok(_got) => panic!("'{}' incorrectly parsed correctly", input),
+                Err(_err) => (), // Expect a error. TODO Maybe check the error message,
+            }
+        }
+    }
+
+    // TODO Take test from https://datatracker.ietf.org/doc/html/rfc2308#section-10
+
+    // Test Full files
+    #[test]
+    fn test_parse() {
+        // TODO add some bad data examples
+        // "$ORIGIN @"
+        // "$TTL blah"
+
+        let tests = vec![
+            // The control entry types
+            ("$ORIGIN 1.example.org.", vec![Entry::Origin("1.example.org.".to_string())]),
+            ("$TTL 3600", vec![Entry::TTL(Duration::new(3600, 0))]),
+
+            // A zero TTL (no caching), and the largest allowed by rfc2181
+            ("$TTL 0", vec![Entry::TTL(Duration::new(0, 0))]),
+            ("$TTL 2147483647", vec![Entry::TTL(Duration::new(2147483647, 0))]),
+            ("example.com. 0 IN A 192.0.2.1",
+                vec![
+                    Entry::Record(Record {
+                        name: Some("example.com.".to_string()),
+                        ttl: Some(Duration::new(0, 0)),
+                        class: Some(Class::Internet),
+                        resource: Resource::A("192.0.2.1".parse().unwrap()),
+                    }),
+                ]),
+
+            // BIND style durations
+            ("$TTL 1h", vec![Entry::TTL(Duration::new(3600, 0))]),
+            ("$TTL 1H30m", vec![Entry::TTL(Duration::new(5400, 0))]),
+            ("$TTL 2d12h", vec![Entry::TTL(Duration::new(216000, 0))]),
+            ("$TTL 1w", vec![Entry::TTL(Duration::new(604800, 0))]),
+            ("$TTL 1m30", vec![Entry::TTL(Duration::new(90, 0))]),
+
+            // Wrapped with newlines
+            ("\n\n$ORIGIN 2.example.org.\n", vec![Entry::Origin("2.example.org.".to_string())]),
+
+            // Wrapped with various whitespace
+            ("\n \t \n \t \n \t $ORIGIN 3.example.org.  \n \t \n \t \n  ", vec![Entry::Origin("3.example.org.".to_string())]),
+
+            // TODO Include
+
+            // Examples modified from https://nsd.docs.nlnetlabs.nl/en/latest/reference/grammar.html
+            ("SOA    soa    soa    ( 1 2 3 4 5 )",
+                vec![
+                    Entry::Record(Record {
+                        resource: Resource::SOA(SOA {
+                            mname: "soa".to_string(),
+                            rname: "soa".to_string(),
+                            serial: 1,
+                            refresh: Duration::new(2, 0),
+                            retry: Duration::new(3, 0),
+                            expire: Duration::new(4, 0),
+                            minimum: Duration::new(5, 0),
+                        }),
+                        ..Default::default()
+                    }),
+                ]),
+
+            ("SOA    soa    soa    ( 1 2 ) ( 3 4 ) ( 5 )",
+                vec![
+                    Entry::Record(Record {
+                        resource: Resource::SOA(SOA {
+                            mname: "soa".to_string(),
+                            rname: "soa".to_string(), // TODO Fix the \\ thing
+                            serial: 1,
+                            refresh: Duration::new(2, 0),
+                            retry: Duration::new(3, 0),
+                            expire: Duration::new(4, 0),
+                            minimum: Duration::new(5, 0),
+                        }),
+                        ..Default::default()
+                    }),
+                ]),
+
+            // Compact together
+            ("SOA soa soa (1 2)(3 4)(5)",
+                vec![
+                    Entry::Record(Record {
+                        resource: Resource::SOA(SOA {
+                            mname: "soa".to_string(),
+                            rname: "soa".to_string(), // TODO Fix the \\ thing
+                            serial: 1,
+                            refresh: Duration::new(2, 0),
+                            retry: Duration::new(3, 0),
+                            expire: Duration::new(4, 0),
+                            minimum: Duration::new(5, 0),
+                        }),
+                        ..Default::default()
+                    }),
+                ]),
+
+            // Services may be named, and span multiple lines.
+            ("@ WKS 192.0.2.1 tcp ( smtp http\n 8080 )",
+                vec![
+                    Entry::Record(Record {
+                        name: Some("@".to_string()),
+                        resource: Resource::WKS(WKS {
+                            address: "192.0.2.1".parse().unwrap(),
+                            protocol: 6,
+                            ports: vec![25, 80, 8080],
+                        }),
+                        ..Default::default()
+                    }),
+                ]),
+
+            // Base64 data may span multiple lines.
+            // Example from https://datatracker.ietf.org/doc/html/rfc4701#section-3.6
+            ("chi6.example.com. DHCID ( AAIBY2/AuCccgoJbsaxcQc9TUapptP69l
+                                        OjxfNuVAA2kjEA= ) ; comment",
+                vec![
+                    Entry::Record(Record {
+                        name: Some("chi6.example.com.".to_string()),
+                        resource: Resource::DHCID(vec![
+                            0x00, 0x02, 0x01, 0x63, 0x6f, 0xc0, 0xb8, 0x27, 0x1c, 0x82, 0x82, 0x5b,
+                            0xb1, 0xac, 0x5c, 0x41, 0xcf, 0x53, 0x51, 0xaa, 0x69, 0xb4, 0xfe, 0xbd,
+                            0x94, 0xe8, 0xf1, 0x7c, 0xdb, 0x95, 0x00, 0x0d, 0xa4, 0x8c, 0x40,
+                        ]),
+                        ..Default::default()
+                    }),
+                ]),
+
+            // The owner name is the hashed local part of the email address.
+            // Example modified from https://datatracker.ietf.org/doc/html/rfc7929#section-2.3
+            ("c93f1e400f26708f98cb19d936620da35eec8f72e57f9eec01c1afd6._openpgpkey.example.com. OPENPGPKEY (
+                mQINBFVHm5sBEACZ3nUbh6nW3Jv1
+                0JBXO1WdqcT1A0+Vk0F4oUYx )",
+                vec![
+                    Entry::Record(Record {
+                        name: Some("c93f1e400f26708f98cb19d936620da35eec8f72e57f9eec01c1afd6._openpgpkey.example.com.".to_string()),
+                        resource: Resource::OPENPGPKEY(vec![
+                            0x99, 0x02, 0x0d, 0x04, 0x55, 0x47, 0x9b, 0x9b, 0x01, 0x10, 0x00, 0x99,
+                            0xde, 0x75, 0x1b, 0x87, 0xa9, 0xd6, 0xdc, 0x9b, 0xf5, 0xd0, 0x90, 0x57,
+                            0x3b, 0x55, 0x9d, 0xa9, 0xc4, 0xf5, 0x03, 0x4f, 0x95, 0x93, 0x41, 0x78,
+                            0xa1, 0x46, 0x31,
+                        ]),
+                        ..Default::default()
+                    }),
+                ]),
+
+            // Example from https://datatracker.ietf.org/doc/html/rfc8005#section-6
+            // The RFC wraps the public key over multiple lines, but it must not
+            // contain whitespace (see section 5), so it is on one line here.
+            ("www.example.com.      IN  HIP ( 2 200100107B1A74DF365639CC39F1D578
+                AwEAAbdxyhNuSutc5EMzxTs9LBPCIkOFH8cIvM4p9+LrV4e19WzK00+CI6zBCQTdtWsuxKbWIy87UOoJTwkUs7lBu+Upr1gsNrut79ryra+bSRGQb1slImA8YVJyuIDsj7kwzG7jnERNqnWxZ48AWkskmdHaVDP4BcelrTI3rMXdXF5D
+                rvs1.example.com.
+                rvs2.example.com. )",
+                vec![
+                    Entry::Record(Record {
+                        name: Some("www.example.com.".to_string()),
+                        class: Some(Class::Internet),
+                        resource: Resource::HIP(HIP {
+                            algorithm: 2,
+                            hit: hex::decode("200100107B1A74DF365639CC39F1D578").unwrap(),
+                            public_key: base64::decode("AwEAAbdxyhNuSutc5EMzxTs9LBPCIkOFH8cIvM4p9+LrV4e19WzK00+CI6zBCQTdtWsuxKbWIy87UOoJTwkUs7lBu+Upr1gsNrut79ryra+bSRGQb1slImA8YVJyuIDsj7kwzG7jnERNqnWxZ48AWkskmdHaVDP4BcelrTI3rMXdXF5D").unwrap(),
+                            rendezvous_servers: vec!["rvs1.example.com.".to_string(), "rvs2.example.com.".to_string()],
+                        }),
+                        ..Default::default()
+                    }),
+                ]),
+
+            // Relative, and single label, rendezvous servers are not mistaken
+            // for part of the public key.
+            ("www IN HIP 2 200100107B1A74DF365639CC39F1D578 AwEAAbdxyhNuSutc rvs",
+                vec![
+                    Entry::Record(Record {
+                        name: Some("www".to_string()),
+                        class: Some(Class::Internet),
+                        resource: Resource::HIP(HIP {
+                            algorithm: 2,
+                            hit: hex::decode("200100107B1A74DF365639CC39F1D578").unwrap(),
+                            public_key: base64::decode("AwEAAbdxyhNuSutc").unwrap(),
+                            rendezvous_servers: vec!["rvs".to_string()],
+                        }),
+                        ..Default::default()
+                    }),
+                ]),
+            ("www IN HIP 2 200100107B1A74DF365639CC39F1D578 AwEAAbdxyhNuSutc rvs1 rvs2.sub rvs3.example.com.",
+                vec![
+                    Entry::Record(Record {
+                        name: Some("www".to_string()),
+                        class: Some(Class::Internet),
+                        resource: Resource::HIP(HIP {
+                            algorithm: 2,
+                            hit: hex::decode("200100107B1A74DF365639CC39F1D578").unwrap(),
+                            public_key: base64::decode("AwEAAbdxyhNuSutc").unwrap(),
+                            rendezvous_servers: vec![
+                                "rvs1".to_string(),
+                                "rvs2.sub".to_string(),
+                                "rvs3.example.com.".to_string(),
+                            ],
+                        }),
+                        ..Default::default()
+                    }),
+                ]),
+
+            (RFC1035_EXAMPLE, vec![
+                Entry::Origin("ISI.EDU.".to_string()),
+                Entry::Record(Record {
+                    name: Some("@".to_string()),
+                    ttl: None,
+                    class: Some(Class::Internet),
+                    resource: Resource::SOA(SOA {
+                        mname: "VENERA".to_string(),
+                        rname: "Action\\.domains".to_string(), // TODO Fix the \\ thing
+                        serial: 20,
+                        refresh: Duration::new(7200, 0),
+                        retry: Duration::new(600, 0),
+                        expire: Duration::new(3600000, 0),
+                        minimum: Duration::new(60, 0),
+                    }),
+                }),
+                Entry::Record(Record {
+                    resource: Resource::NS("A.ISI.EDU.".to_string()),
+                    ..Default::default()
+                }),
+                Entry::Record(Record {
+                    resource: Resource::NS("VENERA".to_string()),
+                    ..Default::default()
+                }),
+                Entry::Record(Record {
+                    resource: Resource::NS("VAXA".to_string()),
+                    ..Default::default()
+                }),
+                Entry::Record(Record {
+                    resource: Resource::MX(MX{
+                        preference: 10,
+                        exchange: "VENERA".to_string()
+                    }),
+                    ..Default::default()
+                }),
+                Entry::Record(Record {
+                    resource: Resource::MX(MX{
+                        preference: 20,
+                        exchange: "VAXA".to_string(),
+                    }),
+                    ..Default::default()
+                }),
+                Entry::Record(Record {
+                    name: Some("A".to_string()),
+                    resource: Resource::A("26.3.0.103".parse().unwrap()),
+                    ..Default::default()
+                }),
+                Entry::Record(Record {
+                    name: Some("VENERA".to_string()),
+                    resource: Resource::A("10.1.0.52".parse().unwrap()),
+                    ..Default::default()
+                }),
+                Entry::Record(Record {
+                    resource: Resource::A("128.9.0.32".parse().unwrap()),
+                    ..Default::default()
+                }),
+                Entry::Record(Record {
+                    name: Some("VAXA".to_string()),
+                    resource: Resource::A("10.2.0.27".parse().unwrap()),
+                    ..Default::default()
+                }),
+                Entry::Record(Record {
+                    resource: Resource::A("128.9.0.33".parse().unwrap()),
+                    ..Default::default()
+                }),
+            ]),
+
+            // Examples from https://en.wikipedia.org/wiki/Zone_file
+            ("
+            $ORIGIN example.com.     ; designates the start of this zone file in the namespace
+            $TTL 3600                ; default expiration time (in seconds) of all RRs without their own TTL value
+            example.com.  IN  SOA   ns.example.com. username.example.com. ( 2020091025 7200 3600 1209600 3600 )
+            example.com.  IN  NS    ns                    ; ns.example.com is a nameserver for example.com
+            example.com.  IN  NS    ns.somewhere.example. ; ns.somewhere.example is a backup nameserver for example.com
+            example.com.  IN  MX    10 mail.example.com.  ; mail.example.com is the mailserver for example.com
+            @             IN  MX    20 mail2.example.com. ; equivalent to above line, '@' represents zone origin
+            @             IN  MX    50 mail3              ; equivalent to above line, but using a relative host name
+            example.com.  IN  A     192.0.2.1             ; IPv4 address for example.com
+                          IN  AAAA  2001:db8:10::1        ; IPv6 address for example.com
+            ns            IN  A     192.0.2.2             ; IPv4 address for ns.example.com
+                          IN  AAAA  2001:db8:10::2        ; IPv6 address for ns.example.com
+            www           IN  CNAME example.com.          ; www.example.com is an alias for example.com
+            wwwtest       IN  CNAME www                   ; wwwtest.example.com is another alias for www.example.com
+            mail          IN  A     192.0.2.3             ; IPv4 address for mail.example.com
+            mail2         IN  A     192.0.2.4             ; IPv4 address for mail2.example.com
+            mail3         IN  A     192.0.2.5             ; IPv4 address for mail3.example.com",
+            vec![
+                Entry::Origin("example.com.".to_string()),
+                Entry::TTL(Duration::new(3600, 0)),
+                Entry::Record(Record {
+                        name: Some("example.com.".to_string(),),
+                        ttl: None,
+                        class: Some(Class::Internet,),
+                        resource: Resource::SOA(
+                            SOA {
+                                mname: "ns.example.com.".to_string(),
+                                rname: "username.example.com.".to_string(),
+                                serial: 2020091025,
+                                refresh: Duration::new(7200, 0),
+                                retry: Duration::new(3600, 0),
                                 expire: Duration::new(1209600, 0),
                                 minimum: Duration::new(3600, 0),
                             },
@@ -617,111 +1505,1054 @@ mod tests {
                 ),
             ]),
 
-            ("$ORIGIN localhost.
-            @  86400  IN  SOA   @  root (
-                              1999010100 ; serial
-                                   10800 ; refresh (3 hours)
-                                     900 ; retry (15 minutes)
-                                  604800 ; expire (1 week)
-                                   86400 ; minimum (1 day)
-                                )
-            @  86400  IN  NS    @
-            @  86400  IN  A     127.0.0.1
-            @  86400  IN  AAAA  ::1", vec![
-                Entry::Origin("localhost.".to_string()),
-                Entry::Record(Record {
-                        name: Some("@".to_string(),),
-                        ttl: Some(Duration::new(86400, 0)),
-                        class: Some(Class::Internet),
-                        resource: Resource::SOA(
-                            SOA {
-                                mname: "@".to_string(),
-                                rname: "root".to_string(),
-                                serial: 1999010100,
-                                refresh: Duration::new(10800, 0),
-                                retry: Duration::new(900, 0),
-                                expire: Duration::new(604800, 0),
-                                minimum: Duration::new(86400, 0),
-                            },
-                        ),
-                    },
-                ),
+            ("$ORIGIN localhost.
+            @  86400  IN  SOA   @  root (
+                              1999010100 ; serial
+                                   10800 ; refresh (3 hours)
+                                     900 ; retry (15 minutes)
+                                  604800 ; expire (1 week)
+                                   86400 ; minimum (1 day)
+                                )
+            @  86400  IN  NS    @
+            @  86400  IN  A     127.0.0.1
+            @  86400  IN  AAAA  ::1", vec![
+                Entry::Origin("localhost.".to_string()),
+                Entry::Record(Record {
+                        name: Some("@".to_string(),),
+                        ttl: Some(Duration::new(86400, 0)),
+                        class: Some(Class::Internet),
+                        resource: Resource::SOA(
+                            SOA {
+                                mname: "@".to_string(),
+                                rname: "root".to_string(),
+                                serial: 1999010100,
+                                refresh: Duration::new(10800, 0),
+                                retry: Duration::new(900, 0),
+                                expire: Duration::new(604800, 0),
+                                minimum: Duration::new(86400, 0),
+                            },
+                        ),
+                    },
+                ),
+
+                Entry::Record(Record {
+                        name: Some("@".to_string(),),
+                        ttl: Some(Duration::new(86400, 0)),
+                        class: Some(Class::Internet),
+                        resource: Resource::NS("@".parse().unwrap()),
+                    },
+                ),
+                Entry::Record(Record {
+                        name: Some("@".to_string(),),
+                        ttl: Some(Duration::new(86400, 0)),
+                        class: Some(Class::Internet),
+                        resource: Resource::A("127.0.0.1".parse().unwrap()),
+                    },
+                ),
+                Entry::Record(Record {
+                        name: Some("@".to_string(),),
+                        ttl: Some(Duration::new(86400, 0)),
+                        class: Some(Class::Internet),
+                        resource: Resource::AAAA("::1".parse().unwrap()),
+                    },
+                ),
+                ]
+            ),
+
+            (";; reverse zone file for 127.0.0.1 and ::1
+            $TTL 1814400 ; 3 weeks
+            @  1814400  IN  SOA     localhost. root.localhost.  (
+                                  1999010100 ; serial
+                                       10800 ; refresh (3 hours)
+                                         900 ; retry (15 minutes)
+                                      604800 ; expire (1 week)
+                                       86400 ; minimum (1 day)
+                                    )
+            @  1814400  IN  NS      localhost.
+            1  1814400  IN  PTR     localhost.", vec![
+                Entry::TTL(Duration::new(1814400, 0)),
+                Entry::Record(Record {
+                        name: Some("@".to_string(),),
+                        ttl: Some(Duration::new(1814400, 0)),
+                        class: Some(Class::Internet),
+                        resource: Resource::SOA(
+                            SOA {
+                                mname: "localhost.".to_string(),
+                                rname: "root.localhost.".to_string(),
+                                serial: 1999010100,
+                                refresh: Duration::new(10800, 0),
+                                retry: Duration::new(900, 0),
+                                expire: Duration::new(604800, 0),
+                                minimum: Duration::new(86400, 0),
+                            },
+                        ),
+                    },
+                ),
+                Entry::Record(Record {
+                        name: Some("@".to_string()
+                            ),
+                        ttl: Some(Duration::new(1814400, 0)),
+                        class: Some(Class::Internet),
+                        resource: Resource::NS("localhost.".to_string()),
+                    },
+                ),
+                Entry::Record(Record {
+                        name: Some("1".to_string()),
+                        ttl: Some(Duration::new(1814400, 0)),
+                        class: Some(Class::Internet),
+                        resource: Resource::PTR("localhost.".to_string()),
+                    },
+                ),
+            ]),
+        ];
+
+        for (input, want) in tests {
+            match File::from_str(input) {
+                Ok(got) => assert_eq!(got.entries, want),
+                Err(err) => panic!("{} Failed:\n{}", input, err),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_line_endings() {
+        let want = File::from_str(RFC1035_EXAMPLE).expect("failed to parse").entries;
+
+        let tests = vec![
+            RFC1035_EXAMPLE.replace('\n', "\r\n"),
+            RFC1035_EXAMPLE.replace('\n', "\r"),
+        ];
+
+        for input in tests {
+            match File::from_str(&input) {
+                Ok(got) => assert_eq!(got.entries, want, "incorrect result for '{:?}'", input),
+                Err(err) => panic!("{:?} Failed:\n{}", input, err),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_error_line() {
+        // The error is on the 4th line, even though the SOA record spans multiple lines.
+        let input = "$ORIGIN example.com.\n@ SOA ns root (\n 1 2 3 4 5 )\nwww A 192.0.2.300\n";
+
+        for input in &[
+            input.to_string(),
+            input.replace('\n', "\r\n"),
+            input.replace('\n', "\r"),
+        ] {
+            match File::from_str(input) {
+                Ok(got) => panic!("{:?} incorrectly parsed correctly: {:?}", input, got),
+                Err(err) => {
+                    assert_eq!(err.line_col, LineColLocation::Span((4, 7), (4, 18)), "incorrect location for {:?}", input);
+                    assert!(err.to_string().contains("4 | www A 192.0.2.300"), "incorrect error for {:?}:\n{}", input, err);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_error_tabs() {
+        // The caret should line up with the error, even when tabs are used.
+        // This works because the tabs are kept in the underline.
+        let input = "$ORIGIN example.com.\n\twww\t\tIN\tA\t192.0.2.300\n";
+
+        let err = File::from_str(input).expect_err("incorrectly parsed correctly");
+        let got = err.to_string();
+        let lines: Vec<&str> = got.lines().collect();
+
+        assert!(lines[2].starts_with("2 | \twww\t\tIN\tA\t192.0.2.300"), "incorrect error:\n{}", got);
+        assert_eq!(lines[3], "  | \t   \t\t  \t \t^---------^", "incorrect error:\n{}", got);
+
+        // Each character before the caret, is the same width as the line above.
+        let source = lines[2].chars();
+        let underline = lines[3].chars().take_while(|c| *c != '^');
+        for (s, u) in source.zip(underline) {
+            assert_eq!(s == '\t', u == '\t', "misaligned caret:\n{}", got);
+        }
+    }
+
+    #[test]
+    fn test_parse_error_message() {
+        // The grammar rules that were expected are described, rather than named.
+        let input = "$ORIGIN example.com.\n$TTL 3600\nwww IN A example\n";
+
+        let err = File::from_str(input).expect_err("incorrectly parsed correctly");
+        assert_eq!(err.line_col, LineColLocation::Pos((3, 10)));
+        assert!(
+            err.to_string().ends_with("= expected a IPv4 address in A record RDATA"),
+            "incorrect error:\n{}",
+            err
+        );
+
+        let tests = vec![
+            // The owner is not mistaken for the type.
+            ("mx IN A example", "expected a IPv4 address in A record RDATA"),
+            ("  IN A example", "expected a IPv4 address in A record RDATA"),
+            ("@ IN MX 10", "invalid MX record RDATA"),
+            // Once the type is matched, the field that is wrong is named.
+            ("@ IN MX mail", "expected the MX preference (a number)"),
+            ("@ IN MX mail.example.com.", "expected the MX preference (a number)"),
+            ("@ 3600 MX ( 10 ) \"mail\"", "expected the MX exchange (a domain name)"),
+            ("@ IN SOA ns admin 1 2 3 4 x", "expected the SOA minimum (a TTL)"),
+            ("@ IN SOA ns admin (\n 1 2 3\n 4 x )", "expected the SOA minimum (a TTL)"),
+            ("_sip._tcp SRV 0 5 sip sip.example.com.", "expected the SRV port (a number)"),
+            ("@ URI 10 x \"https://example.com/\"", "expected the URI weight (a number)"),
+            ("@ CDS 60485 5 x 00", "expected the CDS digest type (a number)"),
+            ("@ IN A", "expected a TTL or a record type followed by its RDATA"),
+            ("$TTL x", "expected a TTL"),
+            ("@ 3600 IN CNAME a b", "expected the end of the record"),
+            ("@ X25 \"31106\" \"1\"", "expected the end of the record"),
+            ("@ X25 \"311\"", "invalid X25 address '311': expected at least 4 decimal digits"),
+            ("@ X25 \"3110-617\"", "invalid X25 address '3110-617': expected at least 4 decimal digits"),
+            ("@ NSAP 47.0005", "expected a NSAP address in NSAP record RDATA"),
+            ("@ ATMA 39.246", "invalid ATMA address '39.246': expected '+' followed by decimal digits, or a even number of hex digits"),
+            ("@ NSAP 0x47.000", "invalid NSAP address '0x47.000': expected a even number of hex digits after the '0x'"),
+        ];
+
+        for (input, want) in tests {
+            match File::from_str(input) {
+                Ok(got) => panic!("{:?} incorrectly parsed correctly: {:?}", input, got),
+                Err(err) => assert!(
+                    err.to_string().ends_with(&format!("= {}", want)),
+                    "incorrect error for {:?}:\n{}",
+                    input,
+                    err
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_svcb() {
+        // Examples based on https://datatracker.ietf.org/doc/html/rfc9460#section-10
+        let input = "$ORIGIN example.com.
+            $TTL 3600
+            @        IN  HTTPS  0 svc
+            svc      IN  HTTPS  1 . alpn=h2,h3 port=8443 ipv4hint=192.0.2.1,192.0.2.2
+            _8443._foo.api  IN  SVCB  2 svc4.example.net. (
+                                alpn=\"bar\" ; quoted
+                                key667=\"hello world\"
+                                no-default-alpn )";
+
+        let records = File::from_str(input)
+            .expect("failed to parse")
+            .into_records()
+            .expect("failed to process");
+
+        let resources: Vec<Resource> = records.into_iter().map(|r| r.resource).collect();
+        assert_eq!(
+            resources,
+            vec![
+                Resource::HTTPS(SVCB {
+                    priority: 0,
+                    target: "svc.example.com".to_string(),
+                    params: vec![],
+                }),
+                Resource::HTTPS(SVCB {
+                    priority: 1,
+                    target: ".".to_string(),
+                    params: vec![
+                        SvcParam::Alpn(vec!["h2".to_string(), "h3".to_string()]),
+                        SvcParam::Port(8443),
+                        SvcParam::Ipv4Hint(vec![
+                            "192.0.2.1".parse().unwrap(),
+                            "192.0.2.2".parse().unwrap()
+                        ]),
+                    ],
+                }),
+                Resource::SVCB(SVCB {
+                    priority: 2,
+                    target: "svc4.example.net".to_string(),
+                    params: vec![
+                        SvcParam::Alpn(vec!["bar".to_string()]),
+                        SvcParam::Unknown(667, b"hello world".to_vec()),
+                        SvcParam::NoDefaultAlpn,
+                    ],
+                }),
+            ]
+        );
+
+        // Invalid values are reported at the parameter.
+        let err = File::from_str("svc HTTPS 1 . port=http").expect_err("incorrectly parsed");
+        assert_eq!(err.line_col, LineColLocation::Span((1, 15), (1, 24)));
+    }
+
+    #[test]
+    fn test_parse_hash_comments() {
+        let input = "# Example zone\n$ORIGIN example.com. # the origin\nwww A 192.0.2.1 ; normal comment\n# ( not a paren\nmail A 192.0.2.2 #trailing";
+        let options = ZoneOptions {
+            hash_comments: true,
+            ..Default::default()
+        };
+
+        let want = File::new(None, vec![
+            Entry::Origin("example.com.".to_string()),
+            Entry::Record(Record {
+                name: Some("www".to_string()),
+                ttl: None,
+                class: None,
+                resource: Resource::A("192.0.2.1".parse().unwrap()),
+            }),
+            Entry::Record(Record {
+                name: Some("mail".to_string()),
+                ttl: None,
+                class: None,
+                resource: Resource::A("192.0.2.2".parse().unwrap()),
+            }),
+        ]);
+
+        match File::from_str_with_options(input, &options) {
+            Ok(got) => assert_eq!(got, want),
+            Err(err) => panic!("{} Failed:\n{}", input, err),
+        }
+
+        // Off by default, where '#' is a syntax error.
+        assert!(File::from_str(input).is_err());
+
+        // A '#' within a word, such as in the generic RDATA form, is not a comment.
+        let input = "www A \\# 4 c0000201 # comment\nwww TXT a#b";
+        let want = File::new(None, vec![
+            Entry::Record(Record {
+                name: Some("www".to_string()),
+                ttl: None,
+                class: None,
+                resource: Resource::A("192.0.2.1".parse().unwrap()),
+            }),
+            Entry::Record(Record {
+                name: Some("www".to_string()),
+                ttl: None,
+                class: None,
+                resource: Resource::TXT(TXT(vec![b"a#b".to_vec()])),
+            }),
+        ]);
+
+        match File::from_str_with_options(input, &options) {
+            Ok(got) => assert_eq!(got, want),
+            Err(err) => panic!("{} Failed:\n{}", input, err),
+        }
+    }
+
+    #[test]
+    fn test_parse_soa_parens() {
+        let tests = vec![
+            // A comment after each field, as in the rfc1035 example.
+            "@ IN SOA ns admin ( 1 ; SERIAL
+                                 2 ; REFRESH
+                                 3 ; RETRY
+                                 4 ; EXPIRE
+                                 5 ) ; MINIMUM",
+            // Comments, and blank lines, on their own lines.
+            "@ IN SOA ns admin ( ; the timers
+                                 ; are below
+
+                                 1 2 3 4 5
+                               )",
+            // Multiple parentheses groups, as found in some NSD examples.
+            "@ IN SOA ns admin ( 1 2 ) ( 3 4 5 )",
+            "@ IN SOA ( ns admin ) ( 1 2 ) ( 3 4 ) ( 5 )",
+            "@ IN SOA ns admin ( 1 2 3
+                               ) ( 4 5 )",
+            // No whitespace next to the parentheses, or tabs between the fields.
+            "@ IN SOA ns admin (1 2 3 4 5)",
+            "@ IN SOA ns admin (1\t2\t3\t4\t5)",
+        ];
+
+        let want = File::new(None, vec![
+            Entry::Record(Record {
+                name: Some("@".to_string()),
+                ttl: None,
+                class: Some(Class::Internet),
+                resource: Resource::SOA(SOA {
+                    mname: "ns".to_string(),
+                    rname: "admin".to_string(),
+                    serial: 1,
+                    refresh: Duration::new(2, 0),
+                    retry: Duration::new(3, 0),
+                    expire: Duration::new(4, 0),
+                    minimum: Duration::new(5, 0),
+                }),
+            }),
+        ]);
+
+        for input in tests {
+            match File::from_str(input) {
+                Ok(got) => assert_eq!(got, want, "incorrect result for {:?}", input),
+                Err(err) => panic!("{:?} Failed:\n{}", input, err),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_unbalanced_parens() {
+        // A SOA record missing its closing ')'.
+        let input = "$ORIGIN example.com.
+@   IN  SOA   ns admin ( 2020091025 7200 3600
+                         1209600 3600
+www IN  A     192.0.2.1";
+
+        let err = File::from_str(input).expect_err("incorrectly parsed");
+        assert_eq!(err.line_col, LineColLocation::Span((2, 24), (2, 25)));
+        assert!(
+            err.to_string().contains("unclosed '('"),
+            "incorrect error:\n{}",
+            err
+        );
+
+        let err = File::from_str("www IN A 192.0.2.1 )").expect_err("incorrectly parsed");
+        assert_eq!(err.line_col, LineColLocation::Span((1, 20), (1, 21)));
+        assert!(
+            err.to_string().contains("unexpected ')'"),
+            "incorrect error:\n{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_parse_limits() {
+        let options = ZoneOptions {
+            max_line_length: Some(40),
+            max_record_length: Some(60),
+            max_paren_depth: Some(1),
+            ..Default::default()
+        };
+
+        let input = "@   IN  SOA   ns admin ( 2020091025 7200
+                     3600 1209600 3600 )";
+        assert!(File::from_str(input).is_ok());
+        assert!(File::from_str_with_options("www IN A 192.0.2.1", &options).is_ok());
+
+        let tests = vec![
+            (
+                // Nested parentheses.
+                "@   IN  SOA   ns admin ( ( 1 2 ) 3 4 5 )",
+                LineColLocation::Span((1, 26), (1, 27)),
+                "'(' nested 2 deep, deeper than the limit of 1",
+            ),
+            (
+                // Still reported when the limit is not exceeded.
+                "www IN A ( 192.0.2.1 ) )",
+                LineColLocation::Span((1, 24), (1, 25)),
+                "unexpected ')' without a matching '('",
+            ),
+            (
+                "www IN TXT \"a long line, longer than the limit\"\nwww IN A 192.0.2.1",
+                LineColLocation::Span((1, 1), (1, 48)),
+                "line is 47 bytes long, longer than the limit of 40",
+            ),
+            (
+                // The last line, which has no newline.
+                "www IN A 192.0.2.1\nwww IN TXT \"a long line, longer than the limit\"",
+                LineColLocation::Span((2, 1), (2, 48)),
+                "line is 47 bytes long, longer than the limit of 40",
+            ),
+            (
+                // A record may be longer than a line.
+                input,
+                LineColLocation::Span((1, 1), (2, 41)),
+                "record is 81 bytes long, longer than the limit of 60",
+            ),
+        ];
+
+        for (input, want_location, want_message) in tests {
+            let err = File::from_str_with_options(input, &options).expect_err("incorrectly parsed");
+            assert_eq!(err.line_col, want_location, "incorrect location for {:?}", input);
+            assert!(
+                err.to_string().contains(want_message),
+                "incorrect error for {:?}:\n{}",
+                input,
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_directives() {
+        // Directives are never parsed as a record, even when they could look like one.
+        let tests = vec![
+            ("$ORIGIN example.com.", vec![Entry::Origin("example.com.".to_string())]),
+            ("$origin example.com.", vec![Entry::Origin("example.com.".to_string())]),
+            (
+                "$ORIGIN example.com.\n$TTL 60\nA A 192.0.2.1",
+                vec![
+                    Entry::Origin("example.com.".to_string()),
+                    Entry::TTL(Duration::new(60, 0)),
+                    Entry::Record(Record {
+                        name: Some("A".to_string()),
+                        ttl: None,
+                        class: None,
+                        resource: Resource::A("192.0.2.1".parse().unwrap()),
+                    }),
+                ],
+            ),
+        ];
+
+        for (input, want) in tests {
+            assert_eq!(
+                File::from_str(input),
+                Ok(File::new(None, want)),
+                "incorrect result for {:?}",
+                input
+            );
+        }
+
+        let tests = vec![
+            ("$INCLUDE other.zone", "unsupported directive '$INCLUDE'", (1, 1)),
+            ("www A 192.0.2.1\n$GENERATE 1-10 host$ A 192.0.2.$", "unsupported directive '$GENERATE'", (2, 1)),
+            ("$ORIGIN ", "expected a domain name", (1, 9)),
+            ("$TTL example.com.", "expected a TTL", (1, 6)),
+        ];
+
+        for (input, want, pos) in tests {
+            let err = File::from_str(input).expect_err("incorrectly parsed");
+            assert!(
+                err.to_string().contains(want),
+                "incorrect error for {:?}:\n{}",
+                input,
+                err
+            );
+            match err.line_col {
+                LineColLocation::Pos(got) | LineColLocation::Span(got, _) => {
+                    assert_eq!(got, pos, "incorrect position for {:?}", input)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_strict() {
+        let strict = ZoneOptions {
+            strict: true,
+            ..Default::default()
+        };
 
-                Entry::Record(Record {
-                        name: Some("@".to_string(),),
-                        ttl: Some(Duration::new(86400, 0)),
-                        class: Some(Class::Internet),
-                        resource: Resource::NS("@".parse().unwrap()),
-                    },
-                ),
-                Entry::Record(Record {
-                        name: Some("@".to_string(),),
-                        ttl: Some(Duration::new(86400, 0)),
-                        class: Some(Class::Internet),
-                        resource: Resource::A("127.0.0.1".parse().unwrap()),
-                    },
-                ),
-                Entry::Record(Record {
-                        name: Some("@".to_string(),),
-                        ttl: Some(Duration::new(86400, 0)),
-                        class: Some(Class::Internet),
-                        resource: Resource::AAAA("::1".parse().unwrap()),
-                    },
-                ),
-                ]
+        // BIND style durations are allowed, unless in strict mode.
+        let input = "$TTL 1h";
+        assert_eq!(
+            File::from_str(input),
+            Ok(File::new(None, vec![Entry::TTL(Duration::new(3600, 0))]))
+        );
+
+        let err = File::from_str_with_options(input, &strict).expect_err("incorrectly parsed");
+        assert_eq!(err.line_col, LineColLocation::Span((1, 6), (1, 8)));
+        assert!(
+            err.to_string().contains("duration units (such as '1h') are not allowed in strict mode for TTL"),
+            "incorrect error:\n{}",
+            err
+        );
+
+        let input = "@ IN SOA ns admin ( 1 2h 3 4 5 )";
+        let err = File::from_str_with_options(input, &strict).expect_err("incorrectly parsed");
+        assert!(
+            err.to_string().contains("strict mode for refresh"),
+            "incorrect error:\n{}",
+            err
+        );
+
+        // Even when enabled, '#' comments are rejected in strict mode.
+        let options = ZoneOptions {
+            hash_comments: true,
+            ..strict.clone()
+        };
+        let err = File::from_str_with_options("www A 192.0.2.1 # comment", &options)
+            .expect_err("incorrectly parsed");
+        assert_eq!(err.line_col, LineColLocation::Span((1, 17), (1, 26)));
+        assert!(
+            err.to_string().contains("'#' comments are not allowed in strict mode"),
+            "incorrect error:\n{}",
+            err
+        );
+
+        // A '#' within a word is not a comment, so is accepted in strict mode.
+        for input in ["www IN A \\# 4 c0000201", "www IN TYPE99 \\# 2 0161", "www IN TYPE65280 \\# 2 abcd", "www IN TXT a#b"] {
+            assert_eq!(
+                File::from_str_with_options(input, &strict),
+                File::from_str(input),
+                "incorrect result for '{}'",
+                input
+            );
+            assert!(File::from_str(input).is_ok(), "'{}' failed", input);
+        }
+
+        // While a RFC 1035 zone is fine.
+        assert_eq!(
+            File::from_str_with_options(RFC1035_EXAMPLE, &strict),
+            File::from_str(RFC1035_EXAMPLE)
+        );
+    }
+
+    #[test]
+    fn test_parse_allowed_types() {
+        let options = ZoneOptions {
+            allowed_types: Some([Type::A, Type::AAAA, Type::CNAME, Type::MX, Type::TXT].iter().copied().collect()),
+            ..Default::default()
+        };
+
+        let input = "www IN A 192.0.2.1\nwww IN AAAA 2001:db8::1\n@ IN MX 10 mail\nftp IN CNAME www";
+        assert!(File::from_str_with_options(input, &options).is_ok());
+
+        let tests = vec![
+            ("www IN A 192.0.2.1\n_http._tcp IN URI 10 1 \"https://example.com/\"", "record type 'URI' is not allowed", (2, 15)),
+            ("@ 3600 IN NS ns", "record type 'NS' is not allowed", (1, 11)),
+            // The generic form is checked too.
+            ("@ TYPE2 \\# 4 026e7300", "record type 'NS' is not allowed", (1, 3)),
+            ("@ TYPE65280 \\# 0", "record type 'TYPE65280' is not allowed", (1, 3)),
+        ];
+
+        for (input, want, pos) in tests {
+            // Without the option, every type is allowed.
+            assert!(File::from_str(input).is_ok(), "{:?} failed to parse", input);
+
+            match File::from_str_with_options(input, &options) {
+                Ok(got) => panic!("{:?} incorrectly parsed correctly: {:?}", input, got),
+                Err(err) => {
+                    assert!(err.to_string().contains(want), "incorrect error for {:?}:\n{}", input, err);
+                    match err.line_col {
+                        LineColLocation::Span(start, _) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                        LineColLocation::Pos(start) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_ttl_parser() {
+        fn ttl_parser(ttl: &str) -> Option<Duration> {
+            let secs = ttl.strip_prefix("TTL=")?.parse().ok()?;
+            Some(Duration::new(secs, 0))
+        }
+
+        let options = ZoneOptions {
+            ttl_parser: Some(ttl_parser),
+            ..Default::default()
+        };
+
+        let input = "$TTL TTL=60\nwww TTL=3600 IN A 192.0.2.1\nTTL=300 IN A 192.0.2.2\nmail 1h IN A 192.0.2.3";
+        let got = File::from_str_with_options(input, &options).expect("failed to parse");
+        let ttls: Vec<Option<Duration>> = got
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                Entry::TTL(ttl) => Some(*ttl),
+                Entry::Record(record) => record.ttl,
+                entry => panic!("unexpected entry {:?}", entry),
+            })
+            .collect();
+        assert_eq!(
+            ttls,
+            vec![
+                Some(Duration::new(60, 0)),
+                Some(Duration::new(3600, 0)),
+                Some(Duration::new(300, 0)),
+                // The standard form is still understood.
+                Some(Duration::new(3600, 0)),
+            ]
+        );
+
+        let default = ZoneOptions::default();
+        let tests = vec![
+            // Not understood by the custom parser.
+            ("www TTL=abc IN A 192.0.2.1", &options, "invalid TTL 'TTL=abc'", (1, 5)),
+            ("www TTL=2147483648 IN A 192.0.2.1", &options, "value out of range for TTL", (1, 5)),
+            // Without the custom parser.
+            ("www TTL=3600 IN A 192.0.2.1", &default, "invalid TTL 'TTL=3600'", (1, 5)),
+            ("$TTL TTL=3600", &default, "invalid TTL 'TTL=3600'", (1, 6)),
+        ];
+
+        for (input, options, want, pos) in tests {
+            match File::from_str_with_options(input, options) {
+                Ok(got) => panic!("{:?} incorrectly parsed correctly: {:?}", input, got),
+                Err(err) => {
+                    assert!(err.to_string().contains(want), "incorrect error for {:?}:\n{}", input, err);
+                    match err.line_col {
+                        LineColLocation::Span(start, _) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                        LineColLocation::Pos(start) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_strict_ipv6() {
+        let input = "www IN AAAA ::ffff:192.0.2.1";
+
+        // Embedded IPv4 notation is allowed by default.
+        assert_eq!(
+            File::from_str(input),
+            Ok(File::new(
+                None,
+                vec![Entry::Record(Record {
+                    name: Some("www".to_string()),
+                    ttl: None,
+                    class: Some(Class::Internet),
+                    resource: Resource::AAAA("::ffff:c000:201".parse().unwrap()),
+                })]
+            ))
+        );
+
+        let strict = ZoneOptions {
+            strict_ipv6: true,
+            ..Default::default()
+        };
+        let err = File::from_str_with_options(input, &strict).expect_err("incorrectly parsed");
+        assert_eq!(err.line_col, LineColLocation::Span((1, 13), (1, 29)));
+        assert!(
+            err.to_string().contains(
+                "embedded IPv4 notation is not allowed for AAAA record '::ffff:192.0.2.1'"
             ),
+            "incorrect error:\n{}",
+            err
+        );
 
-            (";; reverse zone file for 127.0.0.1 and ::1
-            $TTL 1814400 ; 3 weeks
-            @  1814400  IN  SOA     localhost. root.localhost.  (
-                                  1999010100 ; serial
-                                       10800 ; refresh (3 hours)
-                                         900 ; retry (15 minutes)
-                                      604800 ; expire (1 week)
-                                       86400 ; minimum (1 day)
-                                    )
-            @  1814400  IN  NS      localhost.
-            1  1814400  IN  PTR     localhost.", vec![
-                Entry::TTL(Duration::new(1814400, 0)),
-                Entry::Record(Record {
-                        name: Some("@".to_string(),),
-                        ttl: Some(Duration::new(1814400, 0)),
-                        class: Some(Class::Internet),
-                        resource: Resource::SOA(
-                            SOA {
-                                mname: "localhost.".to_string(),
-                                rname: "root.localhost.".to_string(),
-                                serial: 1999010100,
-                                refresh: Duration::new(10800, 0),
-                                retry: Duration::new(900, 0),
-                                expire: Duration::new(604800, 0),
-                                minimum: Duration::new(86400, 0),
-                            },
-                        ),
-                    },
-                ),
-                Entry::Record(Record {
-                        name: Some("@".to_string()
-                            ),
-                        ttl: Some(Duration::new(1814400, 0)),
-                        class: Some(Class::Internet),
-                        resource: Resource::NS("localhost.".to_string()),
-                    },
-                ),
-                Entry::Record(Record {
-                        name: Some("1".to_string()),
-                        ttl: Some(Duration::new(1814400, 0)),
-                        class: Some(Class::Internet),
-                        resource: Resource::PTR("localhost.".to_string()),
-                    },
-                ),
-            ]),
+        // While the hexadecimal form is fine.
+        assert!(File::from_str_with_options("www IN AAAA ::ffff:c000:201", &strict).is_ok());
+    }
+
+    #[test]
+    fn test_parse_generic() {
+        // Known types in the generic form, see https://datatracker.ietf.org/doc/html/rfc3597#section-5
+        let tests = vec![
+            ("A \\# 4 01020304", Resource::A("1.2.3.4".parse().unwrap())),
+            ("@ a \\# 4 ( 0102 0304 )", Resource::A("1.2.3.4".parse().unwrap())),
+            (
+                "@ MX \\# 20 000a 046d61696c 076578616d706c65 03636f6d 00",
+                Resource::MX(MX {
+                    preference: 10,
+                    exchange: "mail.example.com.".to_string(),
+                }),
+            ),
+            // The \# is not treated as text.
+            ("@ TXT \\# 6 0568656c6c6f", Resource::TXT(TXT(vec![b"hello".to_vec()]))),
+            ("@ TXT \\# 0", Resource::TXT(TXT(vec![]))),
+            ("@ TYPE1 \\# 4 c0000201", Resource::A("192.0.2.1".parse().unwrap())),
+            (
+                "@ ISDN \\# 6 033132330134",
+                Resource::ISDN(ISDN {
+                    address: "123".to_string(),
+                    subaddress: Some("4".to_string()),
+                }),
+            ),
+            ("@ X25 \\# 5 0431323334", Resource::X25("1234".to_string())),
+            ("@ ATMA \\# 4 01313233", Resource::ATMA(ATMA::E164("123".to_string()))),
+            (
+                "@ NSAP-PTR \\# 6 04686f737400",
+                Resource::NSAP_PTR("host.".to_string()),
+            ),
+            // Types that aren't supported are kept as is.
+            ("@ TYPE65280 \\# 2 abcd", Resource::Unknown(65280, vec![0xab, 0xcd])),
+            ("@ type65280 \\# 0", Resource::Unknown(65280, vec![])),
+            ("@ NINFO \\# 2 abcd", Resource::Unknown(56, vec![0xab, 0xcd])),
+        ];
+
+        for (input, want) in tests {
+            match Record::from_str(input) {
+                Ok(got) => assert_eq!(got.resource, want, "incorrect result for '{}'", input),
+                Err(err) => panic!("'{}' Failed:\n{}", input, err),
+            }
+        }
+
+        let tests = vec![
+            ("@ A \\# 4 010203", "RDATA length is 4, but 3 bytes were given"),
+            ("@ A \\# 3 010203", "invalid A RDATA"),
+            ("@ A \\# 5 0102030405", "invalid A RDATA"),
+            ("@ FOO \\# 4 01020304", "unknown record type 'FOO'"),
+            ("@ TYPE65536 \\# 0", "unknown record type 'TYPE65536'"),
+            ("@ TYPE1 \\# 3 010203", "invalid A RDATA"),
+        ];
+
+        for (input, want) in tests {
+            match Record::from_str(input) {
+                Ok(got) => panic!("{:?} incorrectly parsed correctly: {:?}", input, got),
+                Err(err) => assert!(err.to_string().contains(want), "incorrect error for {:?}:\n{}", input, err),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_eui_errors() {
+        let tests = vec![
+            ("@ EUI48 00-00-5e-00-53", "invalid EUI48 '00-00-5e-00-53': expected 6 hyphen separated pairs of hex digits, such as '00-00-00-00-00-00'"),
+            ("@ EUI48 00-00-5e-00-53-2a-00", "expected 6 hyphen separated pairs"),
+            ("@ EUI48 00-00-5e-00-53-2a0", "expected 6 hyphen separated pairs"),
+            ("@ EUI64 00-00-5e-00-53-2a", "invalid EUI64 '00-00-5e-00-53-2a': expected 8 hyphen separated pairs"),
         ];
 
         for (input, want) in tests {
             match File::from_str(input) {
-                Ok(got) => assert_eq!(got.entries, want),
-                Err(err) => panic!("{} Failed:\n{}", input, err),
+                Ok(got) => panic!("{:?} incorrectly parsed correctly: {:?}", input, got),
+                Err(err) => {
+                    assert!(err.to_string().contains(want), "incorrect error for {:?}:\n{}", input, err);
+                    assert_eq!(err.line_col, LineColLocation::Span((1, 9), (1, input.len() + 1)), "incorrect location for {:?}", input);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_out_of_range() {
+        let tests = vec![
+            // TTL larger than a u32
+            (
+                "example.com. 99999999999 IN A 192.0.2.1",
+                "value out of range for TTL",
+                (1, 14),
+            ),
+            ("$TTL 4294967296", "value out of range for TTL", (1, 6)),
+            ("$TTL 7102w", "value out of range for TTL", (1, 6)),
+            // TTL larger than 2^31 - 1, see rfc2181 section 8
+            ("$TTL 2147483648", "value out of range for TTL", (1, 6)),
+            (
+                "example.com. 2147483648 IN A 192.0.2.1",
+                "value out of range for TTL",
+                (1, 14),
+            ),
+            // SOA serial larger than a u32
+            (
+                "@ IN SOA ns root 4294967296 7200 600 3600000 60",
+                "value out of range for serial",
+                (1, 18),
+            ),
+            // MX preference is a u16
+            ("MX 65536 mail", "value out of range for MX preference", (1, 4)),
+            (
+                "@ IN MX 70000 mail.example.com.",
+                "value out of range for MX preference",
+                (1, 9),
+            ),
+            // DOA location is a u8
+            ("DOA 0 1 256 \"\" -", "value out of range for location", (1, 9)),
+            ("SMIMEA 3 256 1 00", "value out of range for selector", (1, 10)),
+        ];
+
+        for (input, want, pos) in tests {
+            match File::from_str(input) {
+                Ok(got) => panic!("{:?} incorrectly parsed correctly: {:?}", input, got),
+                Err(err) => {
+                    assert!(err.to_string().contains(want), "incorrect error for {:?}:\n{}", input, err);
+                    match err.line_col {
+                        LineColLocation::Span(start, _) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                        LineColLocation::Pos(start) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_duration_units() {
+        // Units are allowed for TTLs, and the SOA timers.
+        let input = "$TTL 1h\nwww 1d IN A 192.0.2.1\n@ IN SOA ns root 1 2h 30m 1w 1d";
+        assert!(File::from_str(input).is_ok(), "{:?} failed to parse", input);
+
+        // But not for any other number.
+        let tests = vec![
+            ("MX 10m mail.", "invalid MX preference '10m'", (1, 4)),
+            ("www IN MX 10s mail.", "invalid MX preference '10s'", (1, 11)),
+            ("@ IN SOA ns root 1h 7200 600 3600000 60", "invalid serial '1h'", (1, 18)),
+            ("URI 1h30m 1 \"x\"", "invalid priority '1h30m'", (1, 5)),
+        ];
+
+        for (input, want, pos) in tests {
+            match File::from_str(input) {
+                Ok(got) => panic!("{:?} incorrectly parsed correctly: {:?}", input, got),
+                Err(err) => {
+                    assert!(err.to_string().contains(want), "incorrect error for {:?}:\n{}", input, err);
+                    assert!(err.to_string().contains("duration units"), "incorrect error for {:?}:\n{}", input, err);
+                    match err.line_col {
+                        LineColLocation::Span(start, _) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                        LineColLocation::Pos(start) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_txt_chunks() {
+        // Each quoted string is kept as a separate character-string.
+        let input = "@ TXT \"v=DKIM1; k=rsa; \" \"p=MIGfMA0G\" \"CSqGSIb3\"";
+        let record = Record::from_str(input).expect("failed to parse");
+        let txt = match record.resource {
+            Resource::TXT(txt) => txt,
+            resource => panic!("{:?} parsed as {:?}", input, resource),
+        };
+        assert_eq!(
+            txt,
+            TXT::from(&["v=DKIM1; k=rsa; ", "p=MIGfMA0G", "CSqGSIb3"][..])
+        );
+        assert_eq!(txt.joined(), b"v=DKIM1; k=rsa; p=MIGfMA0GCSqGSIb3".to_vec());
+
+        // But each may only be 255 bytes long.
+        let input = format!("@ TXT \"a\" \"{}\"", "b".repeat(256));
+        let err = Record::from_str(&input).expect_err("incorrectly parsed");
+        assert!(
+            err.to_string()
+                .contains("character-string is 256 bytes long, but must be at most 255 bytes"),
+            "incorrect error:\n{}",
+            err
+        );
+        assert!(Record::from_str(&format!("@ TXT \"{}\"", "b".repeat(255))).is_ok());
+    }
+
+    #[test]
+    fn test_parse_class_case() {
+        let record = |name: Option<&str>, ttl: Option<u64>, class| Record {
+            name: name.map(|name| name.to_string()),
+            ttl: ttl.map(|ttl| Duration::new(ttl, 0)),
+            class: Some(class),
+            resource: Resource::A("1.2.3.4".parse().unwrap()),
+        };
+
+        // The class is case-insensitive, and may be surrounded by any whitespace.
+        let tests = vec![
+            ("www IN A 1.2.3.4", record(Some("www"), None, Class::Internet)),
+            ("www in A 1.2.3.4", record(Some("www"), None, Class::Internet)),
+            ("www In A 1.2.3.4", record(Some("www"), None, Class::Internet)),
+            ("www iN a 1.2.3.4", record(Some("www"), None, Class::Internet)),
+            ("www IN   A   1.2.3.4", record(Some("www"), None, Class::Internet)),
+            ("www\tin\t\tA \t 1.2.3.4", record(Some("www"), None, Class::Internet)),
+            ("www  3600  in  A  1.2.3.4", record(Some("www"), Some(3600), Class::Internet)),
+            ("www in 3600 A 1.2.3.4", record(Some("www"), Some(3600), Class::Internet)),
+            ("www ch A 1.2.3.4", record(Some("www"), None, Class::Chaos)),
+            ("www class1 A 1.2.3.4", record(Some("www"), None, Class::Internet)),
+            // Without a name, "in" is the class.
+            ("in A 1.2.3.4", record(None, None, Class::Internet)),
+            ("   in   A   1.2.3.4", record(None, None, Class::Internet)),
+            // But names that only start with a class are not mistaken for one.
+            ("internal in A 1.2.3.4", record(Some("internal"), None, Class::Internet)),
+            ("in.example.com. In A 1.2.3.4", record(Some("in.example.com."), None, Class::Internet)),
+        ];
+
+        for (input, want) in tests {
+            match Record::from_str(input) {
+                Ok(got) => assert_eq!(got, want, "incorrect result for {:?}", input),
+                Err(err) => panic!("{:?} failed:\n{}", input, err),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_unknown_class() {
+        let tests = vec![
+            ("www XX A 1.2.3.4", "unknown class 'XX', expected IN, CS, CH, HS or CLASS<n>", (1, 5)),
+            ("www 3600 XX A 1.2.3.4", "unknown class 'XX'", (1, 10)),
+            ("3600 XX A 1.2.3.4", "unknown class 'XX'", (1, 6)),
+            ("www CLASS5 A 1.2.3.4", "unsupported class 'CLASS5'", (1, 5)),
+            ("www CLASS65536 A 1.2.3.4", "unsupported class 'CLASS65536'", (1, 5)),
+        ];
+
+        for (input, want, pos) in tests {
+            match File::from_str(input) {
+                Ok(got) => panic!("{:?} incorrectly parsed correctly: {:?}", input, got),
+                Err(err) => {
+                    assert!(err.to_string().contains(want), "incorrect error for {:?}:\n{}", input, err);
+                    match err.line_col {
+                        LineColLocation::Span(start, _) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                        LineColLocation::Pos(start) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                    }
+                }
+            }
+        }
+
+        // A leading name that looks like a class is a domain, such as a ccTLD.
+        for input in ["DE A 192.0.2.1", "UK NS ns1.nic.uk."] {
+            match Record::from_str(input) {
+                Ok(got) => assert_eq!(got.name, Some(input[..2].to_string()), "incorrect result for {:?}", input),
+                Err(err) => panic!("{:?} failed:\n{}", input, err),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_record_type() {
+        // The type is case-insensitive.
+        let want = Resource::TXT(TXT::from("hello"));
+        for input in &["www IN TXT hello", "www IN txt hello", "www IN Txt hello"] {
+            match Record::from_str(input) {
+                Ok(got) => assert_eq!(got.resource, want, "incorrect result for '{}'", input),
+                Err(err) => panic!("'{}' Failed:\n{}", input, err),
+            }
+        }
+
+        let tests = vec![
+            ("www IN A6 0 2001:db8::1", "A6 is obsolete (RFC 6563)", (1, 8)),
+            ("www IN a6 0 2001:db8::1", "A6 is obsolete (RFC 6563)", (1, 8)),
+            ("www 3600 A6 0 2001:db8::1", "A6 is obsolete (RFC 6563)", (1, 10)),
+            ("IN 3600 A6 0 2001:db8::1", "A6 is obsolete (RFC 6563)", (1, 9)),
+            ("www A6 \\# 0", "A6 is obsolete (RFC 6563)", (1, 5)),
+            ("www IN MD mail.example.com.", "MD is obsolete (RFC 973)", (1, 8)),
+            ("www IN FOO bar", "unknown record type 'FOO'", (1, 8)),
+            ("www IN OPT 1", "OPT records may only be given in the generic '\\#' form", (1, 8)),
+            ("www IN NINFO hello", "NINFO records may only be given in the generic '\\#' form", (1, 8)),
+            // A supported type with invalid RDATA, is reported as such.
+            ("www IN A example", "expected a IPv4 address in A record RDATA", (1, 10)),
+        ];
+
+        for (input, want, pos) in tests {
+            match File::from_str(input) {
+                Ok(got) => panic!("{:?} incorrectly parsed correctly: {:?}", input, got),
+                Err(err) => {
+                    assert!(err.to_string().contains(want), "incorrect error for {:?}:\n{}", input, err);
+                    match err.line_col {
+                        LineColLocation::Span(start, _) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                        LineColLocation::Pos(start) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                    }
+                }
+            }
+        }
+    }
+
+
+    #[test]
+    fn test_parse_gpos_errors() {
+        let tests = vec![
+            // The example in rfc1712 section 4 gives the latitude first, which
+            // is out of range as the second value (the latitude).
+            ("@ GPOS \"-32.6882\" \"116.8652\" \"10.0\"", "invalid latitude '116.8652': expected a number between -90 and 90", (1, 19)),
+            ("@ GPOS 180.1 0 0", "invalid longitude '180.1': expected a number between -180 and 180", (1, 8)),
+            ("@ GPOS 0 -90.5 0", "invalid latitude '-90.5'", (1, 10)),
+            ("@ GPOS east 0 0", "invalid longitude 'east'", (1, 8)),
+            ("@ GPOS 0 0 inf", "invalid altitude 'inf': expected a number", (1, 12)),
+        ];
+
+        for (input, want, pos) in tests {
+            match File::from_str(input) {
+                Ok(got) => panic!("{:?} incorrectly parsed correctly: {:?}", input, got),
+                Err(err) => {
+                    assert!(err.to_string().contains(want), "incorrect error for {:?}:\n{}", input, err);
+                    match err.line_col {
+                        LineColLocation::Span(start, _) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                        LineColLocation::Pos(start) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_tsig_errors() {
+        let tests = vec![
+            ("@ TSIG hmac-sha256. 1700000000 300 32 AAECAwQF 1 NOERROR 0", "data is 6 bytes, but its size is given as 32", (1, 36)),
+            ("@ TSIG hmac-sha256. 1700000000 300 0 1 BADCOFFEE 0", "invalid error 'BADCOFFEE': expected a number, or a name such as 'BADSIG'", (1, 40)),
+            ("@ TSIG hmac-sha256. 281474976710656 300 0 1 NOERROR 0", "value out of range for time signed", (1, 21)),
+            ("@ TSIG hmac-sha256. 1700000000 65536 0 1 NOERROR 0", "value out of range for fudge", (1, 32)),
+            ("@ TSIG hmac-sha256. 1700000000 300 0 NOERROR 0", "expected a number in TSIG record RDATA", (1, 38)),
+            ("@ TKEY gss-tsig. 20231314221320 1700003600 3 0 0 0", "invalid time for inception", (1, 18)),
+            ("@ TKEY gss-tsig. 1700000000 1700003600 3 0 4 AQIDBA== 2 AQID", "data is 3 bytes, but its size is given as 2", (1, 55)),
+        ];
+
+        for (input, want, pos) in tests {
+            match File::from_str(input) {
+                Ok(got) => panic!("{:?} incorrectly parsed correctly: {:?}", input, got),
+                Err(err) => {
+                    assert!(err.to_string().contains(want), "incorrect error for {:?}:\n{}", input, err);
+                    match err.line_col {
+                        LineColLocation::Span(start, _) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                        LineColLocation::Pos(start) => assert_eq!(start, pos, "incorrect location for {:?}", input),
+                    }
+                }
             }
         }
     }
@@ -1,7 +1,13 @@
 // TODO Use https://github.com/Nadrieril/pest_consume
+use crate::zones::ZoneOptions;
 use pest::error::Error;
+use pest::error::ErrorVariant;
+use pest::error::InputLocation;
 use pest::iterators::Pair;
 use pest::Parser;
+use pest::Position;
+use pest::RuleType;
+use pest::Span;
 use std::result;
 
 #[derive(Parser)]
@@ -10,28 +16,89 @@ struct ZonePreprocessor;
 
 type Result<T> = result::Result<T, Error<Rule>>;
 
-fn parse_tokens(pair: Pair<Rule>) -> Result<String> {
-    assert_eq!(pair.as_rule(), Rule::tokens);
+// The pest error is large, but parsing stops at the first one, so it is not
+// worth boxing.
+#[allow(clippy::result_large_err)]
+fn parse_tokens(pair: Pair<Rule>, options: &ZoneOptions) -> Result<String> {
+    assert!(matches!(pair.as_rule(), Rule::tokens | Rule::hash_tokens));
 
     let mut result = String::new();
-    let mut opens = 0;
+
+    // The currently open parentheses, so any left unclosed can be reported.
+    let mut opens = Vec::new();
+
+    // The start of the current line, and record, to check their lengths.
+    let pair_end = pair.as_span().end_pos();
+    let mut line_start = pair.as_span().start_pos();
+    let mut record_start = line_start.clone();
 
     for pair in pair.into_inner() {
+        if pair.as_rule() == Rule::newline {
+            let end = pair.as_span().start_pos();
+            check_length("line", &line_start, &end, options.max_line_length)?;
+            line_start = pair.as_span().end_pos();
+
+            if opens.is_empty() {
+                check_length("record", &record_start, &end, options.max_record_length)?;
+                record_start = line_start.clone();
+            }
+        }
+
         match pair.as_rule() {
             Rule::open => {
-                opens += 1;
+                if let Some(limit) = options.max_paren_depth {
+                    if opens.len() >= limit {
+                        return Err(Error::new_from_span(
+                            ErrorVariant::CustomError {
+                                message: format!(
+                                    "'(' nested {} deep, deeper than the limit of {}",
+                                    opens.len() + 1,
+                                    limit
+                                ),
+                            },
+                            pair.as_span(),
+                        ));
+                    }
+                }
                 result.push_str(pair.as_str());
+                opens.push(pair.as_span());
             }
             Rule::close => {
-                opens -= 1;
+                if opens.pop().is_none() {
+                    return Err(Error::new_from_span(
+                        ErrorVariant::CustomError {
+                            message: "unexpected ')' without a matching '('".to_string(),
+                        },
+                        pair.as_span(),
+                    ));
+                }
                 result.push_str(pair.as_str());
             }
+            Rule::hash_comment if options.strict => {
+                return Err(Error::new_from_span(
+                    ErrorVariant::CustomError {
+                        message: "'#' comments are not allowed in strict mode".to_string(),
+                    },
+                    pair.as_span(),
+                ));
+            }
+            Rule::hash_comment => {
+                // The zone grammar doesn't know about '#' comments, so
+                // always replace them with spaces.
+                for _i in 0..pair.as_str().len() {
+                    result.push(' ');
+                }
+            }
             Rule::newline | Rule::comment => {
-                if opens > 0 {
+                if !opens.is_empty() {
                     // Replace newlines or comments with spaces
                     for _i in 0..pair.as_str().len() {
                         result.push(' ');
                     }
+                } else if pair.as_str() == "\r" {
+                    // Treat a lone carriage return the same as a newline, so
+                    // line numbers are reported correctly.
+                    result.push('\n');
                 } else {
                     result.push_str(pair.as_str());
                 }
@@ -40,18 +107,62 @@ fn parse_tokens(pair: Pair<Rule>) -> Result<String> {
         }
     }
 
+    // The record would otherwise continue to the end of the input, which is
+    // rarely what was intended.
+    if let Some(span) = opens.into_iter().next() {
+        return Err(Error::new_from_span(
+            ErrorVariant::CustomError {
+                message: "unclosed '(', the input ended before the matching ')'".to_string(),
+            },
+            span,
+        ));
+    }
+
+    check_length("line", &line_start, &pair_end, options.max_line_length)?;
+    check_length("record", &record_start, &pair_end, options.max_record_length)?;
+
     Ok(result)
 }
 
+/// Returns a error covering the text between the positions, if it is longer
+/// than the limit.
+// Only called from parse_tokens, which returns the same large error type.
+#[allow(clippy::result_large_err)]
+fn check_length(what: &str, start: &Position, end: &Position, limit: Option<usize>) -> Result<()> {
+    let length = end.pos() - start.pos();
+    match limit {
+        Some(limit) if length > limit => Err(Error::new_from_span(
+            ErrorVariant::CustomError {
+                message: format!(
+                    "{} is {} bytes long, longer than the limit of {}",
+                    what, length, limit
+                ),
+            },
+            start.span(end),
+        )),
+        _ => Ok(()),
+    }
+}
+
 /// Preprocess the input to handle braces. Specifically
 /// ( and ) allow a record to span multiple lines, so this
 /// replaces new lines with spaces when they are within braces.
-pub(crate) fn preprocess(input: &str) -> Result<String> {
+// The error is converted (and boxed) by the callers, as a ZoneParseError.
+#[allow(clippy::result_large_err)]
+pub(crate) fn preprocess(input: &str, options: &ZoneOptions) -> Result<String> {
+    // In strict mode '#' comments are still found, so a helpful error can be
+    // returned. Only a '#' starting a word is a comment, so "\#" is still allowed.
+    let rule = if options.hash_comments || options.strict {
+        Rule::hash_file
+    } else {
+        Rule::file
+    };
+
     let mut result = String::new();
-    let file = ZonePreprocessor::parse(Rule::file, input)?.next().unwrap(); // TODO
+    let file = ZonePreprocessor::parse(rule, input)?.next().unwrap(); // TODO
     for pair in file.into_inner() {
         match pair.as_rule() {
-            Rule::tokens => result.push_str(&parse_tokens(pair)?),
+            Rule::tokens | Rule::hash_tokens => result.push_str(&parse_tokens(pair, options)?),
             Rule::EOI => (), // Nothing
             _ => unreachable!("Unexpected rule: {:?}", pair.as_rule()),
         }
@@ -59,6 +170,107 @@ pub(crate) fn preprocess(input: &str) -> Result<String> {
     Ok(result)
 }
 
+/// Returns the input without any leading UTF-8 byte order mark (BOM), as
+/// written by some editors, which would otherwise be parsed as part of the
+/// first entry.
+pub(crate) fn strip_bom(input: &str) -> &str {
+    input.strip_prefix('\u{feff}').unwrap_or(input)
+}
+
+/// Replaces any lone carriage return with a newline. This does not change the
+/// length of the input.
+pub(crate) fn normalise_newlines(input: &str) -> String {
+    let mut result = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\r' && chars.peek() != Some(&'\n') {
+            result.push('\n');
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Takes a error from parsing the preprocessed input, and returns the same
+/// error but relative to the original input. This ensures the line numbers
+/// (and the line displayed) match what the user actually wrote, even when
+/// a record was spread over multiple lines.
+///
+/// This works because the preprocessor never changes the length of the input.
+pub(crate) fn relocate_error<R: RuleType>(err: Error<R>, input: &str) -> Error<R> {
+    let input = normalise_newlines(input);
+
+    match err.location {
+        InputLocation::Pos(pos) => match Position::new(&input, pos) {
+            Some(pos) => Error::new_from_pos(err.variant, pos),
+            None => err,
+        },
+        InputLocation::Span((start, end)) => match Span::new(&input, start, end) {
+            Some(span) => Error::new_from_span(err.variant, span),
+            None => err,
+        },
+    }
+}
+
+/// Takes a error found in the part of `input` starting `offset` bytes in, and
+/// returns the same error relative to the whole `input`.
+pub(crate) fn shift_error<R: RuleType>(err: Error<R>, input: &str, offset: usize) -> Error<R> {
+    let relocate = |pos: usize| (pos + offset).min(input.len());
+
+    match err.location {
+        InputLocation::Pos(pos) => match Position::new(input, relocate(pos)) {
+            Some(pos) => Error::new_from_pos(err.variant, pos),
+            None => err,
+        },
+        InputLocation::Span((start, end)) => {
+            match Span::new(input, relocate(start), relocate(end)) {
+                Some(span) => Error::new_from_span(err.variant, span),
+                None => err,
+            }
+        }
+    }
+}
+
+/// Takes a error found in `input` after a prefix of `offset` bytes was added,
+/// and returns the same error relative to the `input` without the prefix.
+/// Errors within the prefix are moved to the start of the `input`.
+pub(crate) fn offset_error<R: RuleType>(err: Error<R>, input: &str, offset: usize) -> Error<R> {
+    let relocate = |pos: usize| pos.saturating_sub(offset).min(input.len());
+
+    match err.location {
+        InputLocation::Pos(pos) => match Position::new(input, relocate(pos)) {
+            Some(pos) => Error::new_from_pos(err.variant, pos),
+            None => err,
+        },
+        InputLocation::Span((start, end)) => {
+            match Span::new(input, relocate(start), relocate(end)) {
+                Some(span) => Error::new_from_span(err.variant, span),
+                None => err,
+            }
+        }
+    }
+}
+
+/// Converts a error from the preprocessor, into the same error for the zone
+/// grammar, so only one type of error is returned.
+pub(crate) fn convert_error<R: RuleType>(err: Error<Rule>, input: &str) -> Error<R> {
+    let location = err.location.clone();
+
+    // Turn any parsing error into a custom error, so the rules are not needed.
+    let variant = match err.renamed_rules(|rule| format!("{:?}", rule)).variant {
+        ErrorVariant::CustomError { message } => ErrorVariant::CustomError { message },
+        ErrorVariant::ParsingError { .. } => unreachable!("renamed_rules returns a custom error"),
+    };
+
+    match location {
+        InputLocation::Pos(pos) => Error::new_from_pos(variant, Position::new(input, pos).unwrap()),
+        InputLocation::Span((start, end)) => {
+            Error::new_from_span(variant, Span::new(input, start, end).unwrap())
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -93,13 +305,75 @@ mod tests {
                 "SOA ; ( blah\nA 127.0.0.1",
                 "SOA ; ( blah\nA 127.0.0.1",
             ),
+            (
+                // ';' and '(' within a quoted string are not special
+                "URI 1 2 \"a;b(c\" ; comment\nA 127.0.0.1",
+                "URI 1 2 \"a;b(c\" ; comment\nA 127.0.0.1",
+            ),
+            (
+                "URI ( 1 2 \"a;b)c\" ; comment\n)",
+                "URI ( 1 2 \"a;b)c\"           )",
+            ),
+            (
+                // Windows line endings are kept, and are replaced when in a '('
+                "SOA (\r\n1 2 )\r\nA 127.0.0.1",
+                "SOA (  1 2 )\r\nA 127.0.0.1",
+            ),
+            (
+                // Lone carriage returns are treated as newlines
+                "SOA (\r1 2 )\rA 127.0.0.1",
+                "SOA ( 1 2 )\nA 127.0.0.1",
+            ),
         ];
 
         for (input, want) in tests {
-            match preprocess(input) {
+            match preprocess(input, &ZoneOptions::default()) {
                 Ok(got) => assert_eq!(got, want, "incorrect result for '{}'", input),
                 Err(err) => panic!("'{}' failed:\n{}", input, err),
             }
         }
     }
+
+    #[test]
+    fn test_preprocessor_hash_comments() {
+        let options = ZoneOptions {
+            hash_comments: true,
+            ..Default::default()
+        };
+
+        let tests = vec![
+            ("# blah\nA 127.0.0.1", "      \nA 127.0.0.1"),
+            ("A 127.0.0.1 # blah", "A 127.0.0.1       "),
+            ("SOA (# blah\n1 2 )", "SOA (       1 2 )"),
+            (
+                // '#' within a quoted string, or a ';' comment is not special
+                "TXT \"a#b\" ; c#d",
+                "TXT \"a#b\" ; c#d",
+            ),
+            (
+                // '(' within a comment shouldn't change the parsing
+                "SOA # ( blah\nA 127.0.0.1",
+                "SOA         \nA 127.0.0.1",
+            ),
+            (
+                // Only a '#' at the start of a word begins a comment.
+                "A \\# 4 c0000201 #blah",
+                "A \\# 4 c0000201      ",
+            ),
+            ("TXT a#b\t# blah", "TXT a#b\t      "),
+        ];
+
+        for (input, want) in tests {
+            match preprocess(input, &options) {
+                Ok(got) => assert_eq!(got, want, "incorrect result for '{}'", input),
+                Err(err) => panic!("'{}' failed:\n{}", input, err),
+            }
+        }
+
+        // Without the option, '#' is left alone.
+        assert_eq!(
+            preprocess("A 127.0.0.1 # blah", &ZoneOptions::default()),
+            Ok("A 127.0.0.1 # blah".to_string())
+        );
+    }
 }
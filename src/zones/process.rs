@@ -3,14 +3,60 @@
 use crate::resource::*;
 use crate::zones::Entry;
 use crate::zones::File;
+use crate::zones::Location;
+use crate::zones::ZoneOptions;
+use crate::zones::ZoneParseError;
 use crate::Class;
 use crate::Record;
 use crate::Resource;
 use core::time::Duration;
 
 impl File {
-    pub fn into_records(self) -> Result<Vec<Record>, ()> {
-        let mut results = Vec::<Record>::new();
+    pub fn into_records(self) -> Result<Vec<Record>, ZoneParseError> {
+        self.into_records_with_options(&ZoneOptions::default())
+    }
+
+    /// Same as [`File::into_records`] but using the supplied defaults, for
+    /// when the zone file does not define them.
+    pub fn into_records_with_options(
+        self,
+        options: &ZoneOptions,
+    ) -> Result<Vec<Record>, ZoneParseError> {
+        let records = self.into_located_records(options)?;
+        Ok(records.into_iter().map(|(record, _)| record).collect())
+    }
+
+    /// Same as [`File::into_records_with_options`] but also returns where
+    /// each record was defined.
+    pub(crate) fn into_located_records(
+        self,
+        options: &ZoneOptions,
+    ) -> Result<Vec<(Record, Location)>, ZoneParseError> {
+        let (records, mut errors) = self.resolve(options, false);
+        match errors.pop() {
+            Some(err) => Err(err),
+            None => Ok(records),
+        }
+    }
+
+    /// Same as [`File::into_located_records`] but skips any record that can't
+    /// be resolved (such as one missing a TTL), returning a error for each.
+    pub(crate) fn into_located_records_lenient(
+        self,
+        options: &ZoneOptions,
+    ) -> (Vec<(Record, Location)>, Vec<ZoneParseError>) {
+        self.resolve(options, true)
+    }
+
+    /// Resolves the entries into records. Unless `lenient`, this stops at the
+    /// first error, otherwise the failing entry is skipped.
+    fn resolve(
+        self,
+        options: &ZoneOptions,
+        lenient: bool,
+    ) -> (Vec<(Record, Location)>, Vec<ZoneParseError>) {
+        let mut results = Vec::new();
+        let mut errors = Vec::new();
 
         // Useful to refer to:
         // https://datatracker.ietf.org/doc/html/rfc1035#section-5.1
@@ -21,103 +67,196 @@ impl File {
         // TTL in RSet must match https://datatracker.ietf.org/doc/html/rfc2181#section-5.2
         // Duration times https://www-uxsup.csx.cam.ac.uk/pub/doc/redhat/redhat7.3/rhl-rg-en-7.3/s1-bind-configuration.html
 
-        let mut origin: Option<&str> = self.origin.as_deref();
-        let mut default_ttl: Option<&Duration> = None;
+        let mut origin: Option<String> = match &options.origin {
+            Some(origin) => match origin.strip_suffix('.') {
+                Some(origin) => Some(origin.to_owned()),
+                None => {
+                    errors.push(ZoneParseError::InvalidOrigin(origin.to_owned()));
+                    return (results, errors);
+                }
+            },
+            None => self.origin.clone(),
+        };
+        let mut default_ttl: Option<Duration> = options.default_ttl;
 
         let mut last_name: Option<String> = None;
-        let mut last_class: Option<&Class> = None;
+        let mut last_class: Option<Class> = options.default_class;
 
-        for entry in self.entries.iter() {
+        let mut resolve_entry = |entry: Entry| -> Result<Option<Record>, ZoneParseError> {
             match entry {
                 Entry::Origin(new_origin) => {
                     // Always trim the dot from the end.
-                    if let Some(new_origin) = new_origin.strip_suffix('.') {
-                        origin = Some(new_origin)
-                    } else {
-                        panic!("TODO Origin wasn't a absolute domain");
-                    }
+                    origin = Some(match new_origin.strip_suffix('.') {
+                        Some(new_origin) => new_origin.to_owned(),
+
+                        // Relative to the current origin, such as "sub" or "@".
+                        None if origin.is_some() => {
+                            Self::resolve_name(&new_origin, origin.as_deref())?
+                        }
+                        None => return Err(ZoneParseError::InvalidOrigin(new_origin)),
+                    });
                 }
                 Entry::TTL(ttl) => default_ttl = Some(ttl),
                 Entry::Record(record) => {
-                    let full_name: String = match record.name.as_ref() {
-                        Some(name) => Self::resolve_name(name, origin),
-                        None => {
-                            if last_name.is_none() {
-                                // TODO What's the behaviour if $origin is set?
-                                panic!("TODO Blank domain without a previous domain set");
-                            }
-                            last_name.unwrap().to_string()
+                    let full_name: String = match record.name {
+                        Some(name) if options.lowercase_names => {
+                            Self::resolve_name(&name, origin.as_deref())?.to_ascii_lowercase()
                         }
+                        Some(name) => Self::resolve_name(&name, origin.as_deref())?,
+                        // TODO What's the behaviour if $origin is set?
+                        None => match &last_name {
+                            Some(last_name) => last_name.to_owned(),
+                            None => return Err(ZoneParseError::MissingName),
+                        },
                     };
                     last_name = Some(full_name.to_owned());
 
-                    let ttl = record
-                        .ttl
-                        .as_ref()
-                        .or(default_ttl)
-                        .expect("TODO Blank ttl without a default TTL set"); // TODO Turn these into errors
+                    let ttl = match record.ttl.or(default_ttl) {
+                        Some(ttl) => ttl,
+                        None => return Err(ZoneParseError::MissingTtl(full_name)),
+                    };
 
-                    let class = record
-                        .class
-                        .as_ref()
-                        .or(last_class)
-                        .expect("TODO Blank Class without a previous Class set"); // TODO Turn these into errors
+                    let class = match record.class.or(last_class) {
+                        Some(class) => class,
+                        None => return Err(ZoneParseError::MissingClass(full_name)),
+                    };
 
                     last_class = Some(class);
 
-                    results.push(crate::Record {
+                    if options.strict_soa_names {
+                        Self::check_soa_names(&record.resource)?;
+                    }
+
+                    return Ok(Some(crate::Record {
                         name: full_name,
-                        class: *class,
-                        ttl: *ttl,
-                        resource: Self::resolve_resource(&record.resource, origin),
-                    })
+                        class,
+                        ttl,
+                        resource: Self::resolve_resource(
+                            record.resource,
+                            origin.as_deref(),
+                            options.lowercase_targets,
+                        )?,
+                    }));
+                }
+            }
+
+            Ok(None)
+        };
+
+        // The entries are consumed, so each resource is moved into its
+        // record, rather than cloned.
+        let mut locations = self.locations.into_iter();
+        for entry in self.entries {
+            let location = locations.next().unwrap_or_default();
+
+            if let (Entry::Record(_), Some(limit)) = (&entry, options.max_records) {
+                if results.len() >= limit {
+                    errors.push(ZoneParseError::TooManyRecords { limit });
+                    break;
+                }
+            }
+
+            match resolve_entry(entry) {
+                Ok(Some(record)) => results.push((record, location)),
+                Ok(None) => {}
+                Err(err) => {
+                    errors.push(err);
+                    if !lenient {
+                        break;
+                    }
                 }
             }
         }
 
-        Ok(results)
+        (results, errors)
     }
 
-    fn resolve_name(name: &str, origin: Option<&str>) -> String {
+    fn resolve_name(name: &str, origin: Option<&str>) -> Result<String, ZoneParseError> {
         // Absolute domain name
         if let Some(name) = name.strip_suffix('.') {
-            return name.to_string();
+            return Ok(name.to_string());
         }
 
         // Everything past here requires a origin
-        if origin.is_none() {
-            panic!("TODO Relative domain without a origin set");
-        }
+        let origin = match origin {
+            Some(origin) => origin,
+            None => return Err(ZoneParseError::MissingOrigin(name.to_owned())),
+        };
 
         if name == "@" {
-            return origin.unwrap().to_string();
+            return Ok(origin.to_string());
+        }
+
+        // The root origin ("."), which is stored as the empty name, so the
+        // relative name is already complete.
+        if origin.is_empty() {
+            return Ok(name.to_owned());
         }
 
         // Relative domain name
-        name.to_owned() + "." + origin.unwrap()
+        Ok(name.to_owned() + "." + origin)
     }
 
-    fn resolve_resource(resource: &Resource, origin: Option<&str>) -> Resource {
-        match resource {
-            // These types don't include a domain, so clone as is.
+    fn resolve_resource(
+        resource: Resource,
+        origin: Option<&str>,
+        lowercase: bool,
+    ) -> Result<Resource, ZoneParseError> {
+        let resolve = |name: &str| Self::resolve_target(name, origin, lowercase);
+
+        Ok(match resource {
+            // These types don't include a domain, so are kept as is.
             Resource::A(_)
             | Resource::AAAA(_)
             | Resource::TXT(_)
             | Resource::SPF(_)
+            | Resource::AVC(_)
+            | Resource::URI(_)
+            | Resource::APL(_)
+            | Resource::CERT(_)
+            | Resource::DHCID(_)
+            | Resource::OPENPGPKEY(_)
+            | Resource::CSYNC(_)
+            | Resource::WKS(_)
+            | Resource::GPOS(_)
+            | Resource::CDS(_)
+            | Resource::SMIMEA(_)
+            | Resource::NSAP(_)
+            | Resource::X25(_)
+            | Resource::ISDN(_)
+            | Resource::ATMA(_)
+            | Resource::CDNSKEY(_)
+            | Resource::NID(_)
+            | Resource::L32(_)
+            | Resource::L64(_)
+            | Resource::EUI48(_)
+            | Resource::EUI64(_)
+            | Resource::KEY(_)
+            | Resource::DOA(_)
+            | Resource::Unknown(_, _)
             | Resource::OPT
-            | Resource::ANY => resource.clone(),
+            | Resource::ANY => resource,
 
             // The rest need some kind of tweaking
-            Resource::CNAME(domain) => Resource::CNAME(Self::resolve_name(domain, origin)),
-            Resource::NS(domain) => Resource::NS(Self::resolve_name(domain, origin)),
-            Resource::PTR(domain) => Resource::PTR(Self::resolve_name(domain, origin)),
+            Resource::CNAME(domain) => Resource::CNAME(resolve(&domain)?),
+            Resource::NS(domain) => Resource::NS(resolve(&domain)?),
+            Resource::PTR(domain) => Resource::PTR(resolve(&domain)?),
+            Resource::NSAP_PTR(domain) => Resource::NSAP_PTR(resolve(&domain)?),
             Resource::MX(mx) => Resource::MX(MX {
                 preference: mx.preference,
-                exchange: Self::resolve_name(&mx.exchange, origin),
+                exchange: resolve(&mx.exchange)?,
+            }),
+            Resource::AFSDB(afsdb) => Resource::AFSDB(AFSDB {
+                subtype: afsdb.subtype,
+                hostname: resolve(&afsdb.hostname)?,
+            }),
+            Resource::RT(rt) => Resource::RT(RT {
+                preference: rt.preference,
+                host: resolve(&rt.host)?,
             }),
             Resource::SOA(soa) => Resource::SOA(SOA {
-                mname: Self::resolve_name(&soa.mname, origin),
-                rname: SOA::rname_to_email(&Self::resolve_name(&soa.rname, origin)).unwrap(),
+                mname: resolve(&soa.mname)?,
+                rname: Self::resolve_rname(&soa.rname, origin, lowercase)?,
                 serial: soa.serial,
                 refresh: soa.refresh,
                 retry: soa.retry,
@@ -128,8 +267,92 @@ impl File {
                 priority: srv.priority,
                 weight: srv.weight,
                 port: srv.port,
-                name: Self::resolve_name(&srv.name, origin),
+                name: resolve(&srv.name)?,
+            }),
+            Resource::LP(lp) => Resource::LP(LP {
+                preference: lp.preference,
+                fqdn: resolve(&lp.fqdn)?,
             }),
+            Resource::HIP(hip) => Resource::HIP(HIP {
+                rendezvous_servers: hip
+                    .rendezvous_servers
+                    .iter()
+                    .map(|server| resolve(server))
+                    .collect::<Result<Vec<String>, ZoneParseError>>()?,
+                ..hip
+            }),
+            Resource::SIG(sig) => Resource::SIG(SIG {
+                signer_name: resolve(&sig.signer_name)?,
+                ..sig
+            }),
+            Resource::TKEY(tkey) => Resource::TKEY(TKEY {
+                algorithm: resolve(&tkey.algorithm)?,
+                ..tkey
+            }),
+            Resource::TSIG(tsig) => Resource::TSIG(TSIG {
+                algorithm: resolve(&tsig.algorithm)?,
+                ..tsig
+            }),
+            Resource::SVCB(svcb) => Resource::SVCB(Self::resolve_svcb(svcb, origin, lowercase)?),
+            Resource::HTTPS(svcb) => Resource::HTTPS(Self::resolve_svcb(svcb, origin, lowercase)?),
+        })
+    }
+
+    fn resolve_svcb(
+        svcb: SVCB,
+        origin: Option<&str>,
+        lowercase: bool,
+    ) -> Result<SVCB, ZoneParseError> {
+        // A target of "." refers to the owner name, so is kept as is.
+        let target = match svcb.target.as_str() {
+            "." => svcb.target,
+            target => Self::resolve_target(target, origin, lowercase)?,
+        };
+
+        Ok(SVCB { target, ..svcb })
+    }
+
+    // Resolves a domain name within the RDATA, such as a CNAME's target,
+    // lowercasing it if asked to.
+    fn resolve_target(
+        name: &str,
+        origin: Option<&str>,
+        lowercase: bool,
+    ) -> Result<String, ZoneParseError> {
+        let name = Self::resolve_name(name, origin)?;
+        Ok(if lowercase {
+            name.to_ascii_lowercase()
+        } else {
+            name
+        })
+    }
+
+    // Returns a error if the resource is a SOA, with a relative mname or rname.
+    // "@" is allowed, as it clearly refers to the origin.
+    fn check_soa_names(resource: &Resource) -> Result<(), ZoneParseError> {
+        if let Resource::SOA(soa) = resource {
+            for (field, name) in [("mname", &soa.mname), ("rname", &soa.rname)] {
+                if name != "@" && !name.ends_with('.') {
+                    return Err(ZoneParseError::RelativeSoaName {
+                        field,
+                        name: name.to_owned(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    // Resolves the rname of a SOA record, and converts it into a email address.
+    fn resolve_rname(
+        rname: &str,
+        origin: Option<&str>,
+        lowercase: bool,
+    ) -> Result<String, ZoneParseError> {
+        let rname = Self::resolve_target(rname, origin, lowercase)?;
+        match SOA::rname_to_email(&rname) {
+            Ok(email) => Ok(email),
+            Err(_) => Err(ZoneParseError::InvalidRname(rname)),
         }
     }
 }
@@ -137,10 +360,20 @@ impl File {
 #[cfg(test)]
 mod tests {
     use crate::resource::*;
+    use crate::zones::parse_zone;
+    use crate::zones::parse_zone_grouped;
+    use crate::zones::parse_zone_with_locations;
+    use crate::zones::parse_zone_with_rdata;
+    use crate::zones::validate_zone;
+    use crate::zones::write_zone;
     use crate::zones::File;
+    use crate::zones::WriteOptions;
+    use crate::zones::ZoneOptions;
+    use crate::zones::ZoneParseError;
     use crate::Class;
     use crate::Record;
     use crate::Resource;
+    use crate::Type;
     use core::time::Duration;
     use pretty_assertions::assert_eq;
     use std::str::FromStr;
@@ -194,7 +427,53 @@ mod tests {
 				Record::new("ns.example.com", Class::Internet, Duration::new(3600, 0), Resource::AAAA("2001:db8:10::2".parse().unwrap())),
 				Record::new("www.example.com", Class::Internet, Duration::new(3600, 0), Resource::CNAME("example.com".parse().unwrap())),
 				Record::new("wwwtest.example.com", Class::Internet, Duration::new(3600, 0), Resource::CNAME("www.example.com".to_string())),
-            ])
+            ]),
+
+            // '@' is also allowed in place of any domain within the RDATA.
+            ("
+            $ORIGIN example.com.
+            $TTL 3600
+            @             IN  SOA   @ @ ( 1 7200 3600 1209600 3600 )
+            @             IN  NS    @
+            sub           IN  CNAME @
+            @             IN  MX    10 @
+            ptr           IN  PTR   @
+            ",
+            vec![
+            	Record::new("example.com", Class::Internet, Duration::new(3600, 0), Resource::SOA(SOA {
+	                mname: "example.com".to_string(),
+	                rname: "example@com".to_string(),
+	                serial: 1,
+	                refresh: Duration::new(7200, 0),
+	                retry: Duration::new(3600, 0),
+	                expire: Duration::new(1209600, 0),
+	                minimum: Duration::new(3600, 0),
+	            })),
+            	Record::new("example.com", Class::Internet, Duration::new(3600, 0), Resource::NS("example.com".to_string())),
+				Record::new("sub.example.com", Class::Internet, Duration::new(3600, 0), Resource::CNAME("example.com".to_string())),
+				Record::new("example.com", Class::Internet, Duration::new(3600, 0), Resource::MX(MX{
+					preference: 10,
+					exchange: "example.com".to_string()
+				})),
+				Record::new("ptr.example.com", Class::Internet, Duration::new(3600, 0), Resource::PTR("example.com".to_string())),
+            ]),
+
+            // $ORIGIN may change part way through, only affecting the records that follow.
+            ("
+            $TTL 3600
+            $ORIGIN example.com.
+            www           IN  A     192.0.2.1
+            mail          IN  CNAME www
+            $ORIGIN example.org.
+            www           IN  A     192.0.2.2
+            mail          IN  CNAME www
+            ",
+            vec![
+				Record::new("www.example.com", Class::Internet, Duration::new(3600, 0), Resource::A("192.0.2.1".parse().unwrap())),
+				Record::new("mail.example.com", Class::Internet, Duration::new(3600, 0), Resource::CNAME("www.example.com".to_string())),
+				Record::new("www.example.org", Class::Internet, Duration::new(3600, 0), Resource::A("192.0.2.2".parse().unwrap())),
+				Record::new("mail.example.org", Class::Internet, Duration::new(3600, 0), Resource::CNAME("www.example.org".to_string())),
+            ]),
     	];
 
         for (input, want) in tests {
@@ -203,7 +482,964 @@ mod tests {
                 .into_records()
             {
                 Ok(got) => assert_eq!(got, want),
-                Err(err) => panic!("{} Failed:\n{:?}", input, err), // TODO Make a error and no need to use "{:?}"
+                Err(err) => panic!("{} Failed:\n{}", input, err),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_zone_grouped() {
+        // Example from https://en.wikipedia.org/wiki/Zone_file
+        let input = "$ORIGIN example.com.
+            $TTL 3600
+            example.com.  IN  SOA   ns.example.com. username.example.com. ( 2020091025 7200 3600 1209600 3600 )
+            example.com.  IN  NS    ns
+            example.com.  IN  NS    ns.somewhere.example.
+            example.com.  IN  MX    10 mail.example.com.
+            @             IN  MX    20 mail2.example.com.
+            @             IN  MX    50 mail3
+            example.com.  IN  A     192.0.2.1
+                          IN  AAAA  2001:db8:10::1
+            ns            IN  A     192.0.2.2
+                          IN  AAAA  2001:db8:10::2
+            www           IN  CNAME example.com.
+            wwwtest       IN  CNAME www";
+
+        let zone = parse_zone_grouped(input).expect("failed to parse");
+
+        let types: Vec<Type> = zone["example.com"].iter().map(|r| r.r#type()).collect();
+        assert_eq!(
+            types,
+            vec![Type::SOA, Type::NS, Type::NS, Type::MX, Type::MX, Type::MX, Type::A, Type::AAAA]
+        );
+        assert_eq!(
+            zone["ns.example.com"],
+            vec![
+                Resource::A("192.0.2.2".parse().unwrap()),
+                Resource::AAAA("2001:db8:10::2".parse().unwrap()),
+            ]
+        );
+        assert_eq!(zone["www.example.com"], vec![Resource::CNAME("example.com".to_string())]);
+        assert_eq!(zone.len(), 4);
+    }
+
+    #[test]
+    fn test_parse_zone_with_rdata() {
+        let records = parse_zone_with_rdata(
+            "$ORIGIN example.com.
+            $TTL 3600
+            @     IN  MX  10 Mail
+            mail  IN  A   192.0.2.1",
+        )
+        .expect("failed to parse");
+
+        let rdata: Vec<&[u8]> = records.iter().map(|(_, rdata)| rdata.as_slice()).collect();
+        assert_eq!(
+            rdata,
+            vec![
+                // The preference, then the (lowercased) exchange.
+                &b"\x00\x0a\x04mail\x07example\x03com\x00"[..],
+                &[192, 0, 2, 1][..],
+            ]
+        );
+        assert_eq!(records[0].0.resource, Resource::MX(MX {
+            preference: 10,
+            exchange: "Mail.example.com".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_into_records_rfc1035() {
+        // Example from https://datatracker.ietf.org/doc/html/rfc1035#section-5.3
+        // The example has no TTLs, so one is provided via the options.
+        let input = "$ORIGIN ISI.EDU.
+            @   IN  SOA     VENERA      Action\\.domains (
+                                             20     ; SERIAL
+                                             7200   ; REFRESH
+                                             600    ; RETRY
+                                             3600000; EXPIRE
+                                             60)    ; MINIMUM
+
+                    NS      A.ISI.EDU.
+                    NS      VENERA
+                    NS      VAXA
+                    MX      10      VENERA
+                    MX      20      VAXA
+
+            A       A       26.3.0.103
+
+            VENERA  A       10.1.0.52
+                    A       128.9.0.32
+
+            VAXA    A       10.2.0.27
+                    A       128.9.0.33";
+
+        let options = ZoneOptions {
+            default_ttl: Some(Duration::new(3600, 0)),
+            ..Default::default()
+        };
+
+        let got = File::from_str(input)
+            .expect("failed to parse")
+            .into_records_with_options(&options)
+            .expect("failed to process");
+
+        let ttl = Duration::new(3600, 0);
+        assert_eq!(got, vec![
+            Record::new("ISI.EDU", Class::Internet, ttl, Resource::SOA(SOA {
+                mname: "VENERA.ISI.EDU".to_string(),
+                rname: "Action.domains@ISI.EDU".to_string(),
+                serial: 20,
+                refresh: Duration::new(7200, 0),
+                retry: Duration::new(600, 0),
+                expire: Duration::new(3600000, 0),
+                minimum: Duration::new(60, 0),
+            })),
+            Record::new("ISI.EDU", Class::Internet, ttl, Resource::NS("A.ISI.EDU".to_string())),
+            Record::new("ISI.EDU", Class::Internet, ttl, Resource::NS("VENERA.ISI.EDU".to_string())),
+            Record::new("ISI.EDU", Class::Internet, ttl, Resource::NS("VAXA.ISI.EDU".to_string())),
+            Record::new("ISI.EDU", Class::Internet, ttl, Resource::MX(MX {
+                preference: 10,
+                exchange: "VENERA.ISI.EDU".to_string(),
+            })),
+            Record::new("ISI.EDU", Class::Internet, ttl, Resource::MX(MX {
+                preference: 20,
+                exchange: "VAXA.ISI.EDU".to_string(),
+            })),
+            Record::new("A.ISI.EDU", Class::Internet, ttl, Resource::A("26.3.0.103".parse().unwrap())),
+            Record::new("VENERA.ISI.EDU", Class::Internet, ttl, Resource::A("10.1.0.52".parse().unwrap())),
+            Record::new("VENERA.ISI.EDU", Class::Internet, ttl, Resource::A("128.9.0.32".parse().unwrap())),
+            Record::new("VAXA.ISI.EDU", Class::Internet, ttl, Resource::A("10.2.0.27".parse().unwrap())),
+            Record::new("VAXA.ISI.EDU", Class::Internet, ttl, Resource::A("128.9.0.33".parse().unwrap())),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_zone_from_reader() {
+        use crate::zones::parse_zone;
+        use crate::zones::parse_zone_from_reader;
+        use std::io::Cursor;
+
+        // Example from https://datatracker.ietf.org/doc/html/rfc1035#section-5.3
+        // with a $TTL added, as the original relies on the TTL being implied.
+        let input = "$ORIGIN ISI.EDU.
+            $TTL 3600
+            @   IN  SOA     VENERA      Action\\.domains (
+                                             20     ; SERIAL
+                                             7200   ; REFRESH
+                                             600    ; RETRY
+                                             3600000; EXPIRE
+                                             60)    ; MINIMUM
+
+                    NS      A.ISI.EDU.
+                    NS      VENERA
+                    NS      VAXA
+                    MX      10      VENERA
+                    MX      20      VAXA
+
+            A       A       26.3.0.103
+
+            VENERA  A       10.1.0.52
+                    A       128.9.0.32
+
+            VAXA    A       10.2.0.27
+                    A       128.9.0.33";
+
+        let want = parse_zone(input).expect("failed to parse");
+        assert_eq!(want.len(), 11);
+
+        let got = parse_zone_from_reader(Cursor::new(input)).expect("failed to parse reader");
+        assert_eq!(got, want);
+
+        // Invalid UTF-8 is a error, instead of a panic.
+        let mut invalid = input.as_bytes().to_vec();
+        invalid.extend(b"\nbad A \xff");
+        match parse_zone_from_reader(Cursor::new(invalid)) {
+            Err(ZoneParseError::ReadError(err)) => assert!(err.contains("utf-8"), "incorrect error: {}", err),
+            got => panic!("incorrect result for invalid UTF-8: {:?}", got),
+        }
+    }
+
+    #[test]
+    fn test_parse_zone_bom() {
+        use crate::zones::try_parse_zone;
+        use pest::error::LineColLocation;
+
+        // Example from https://datatracker.ietf.org/doc/html/rfc1035#section-5.3
+        // with a $TTL added, as the original relies on the TTL being implied.
+        let input = "$ORIGIN ISI.EDU.
+            $TTL 3600
+            @   IN  SOA     VENERA      Action\\.domains (
+                                             20     ; SERIAL
+                                             7200   ; REFRESH
+                                             600    ; RETRY
+                                             3600000; EXPIRE
+                                             60)    ; MINIMUM
+
+                    NS      A.ISI.EDU.
+                    NS      VENERA
+                    NS      VAXA
+                    MX      10      VENERA
+                    MX      20      VAXA
+
+            A       A       26.3.0.103
+
+            VENERA  A       10.1.0.52
+                    A       128.9.0.32
+
+            VAXA    A       10.2.0.27
+                    A       128.9.0.33";
+
+        let want = parse_zone(input).expect("failed to parse");
+        assert_eq!(want.len(), 11);
+
+        // A leading BOM, blank lines, or whitespace, are all ignored.
+        let tests = vec![
+            format!("\u{feff}{}", input),
+            format!("\u{feff}\n\n  \t\n{}", input),
+            format!("\n  ; comment\n   {}", input),
+        ];
+        for input in tests {
+            assert_eq!(parse_zone(&input).expect("failed to parse"), want, "incorrect result for {:?}", input);
+            assert_eq!(try_parse_zone(input.as_bytes()).expect("failed to parse"), want, "incorrect result for {:?}", input);
+        }
+
+        // Errors on the first line are still at the right column.
+        let err = File::from_str("\u{feff}www IN A 192.0.2.300").expect_err("incorrectly parsed correctly");
+        assert_eq!(err.line_col, LineColLocation::Span((1, 10), (1, 21)));
+    }
+
+    #[test]
+    fn test_parse_zone_class_like_owner() {
+        use crate::zones::parse_zone;
+
+        // A owner that looks like a class, such as a ccTLD, is still a name.
+        let records = parse_zone("$ORIGIN example.com.\n$TTL 3600\nwww IN A 192.0.2.1\nDE A 192.0.2.2")
+            .expect("failed to parse");
+        assert_eq!(records[1].name, "DE.example.com");
+
+        let records = parse_zone("$ORIGIN .\n$TTL 3600\n. IN NS a.root-servers.net.\nUK NS ns1.nic.uk.")
+            .expect("failed to parse");
+        assert_eq!(records[1].name, "UK");
+    }
+
+    #[test]
+    fn test_parse_zone_lenient() {
+        use crate::zones::parse_zone_lenient;
+        use pest::error::LineColLocation;
+
+        let input = "$ORIGIN example.com.
+$TTL 3600
+a  IN  A  192.0.2.1
+b  IN  A  192.0.2.300
+c  IN  A  192.0.2.3";
+
+        let (records, errors) = parse_zone_lenient(input);
+        assert_eq!(
+            records,
+            vec![
+                Record::new("a.example.com", Class::Internet, Duration::new(3600, 0), Resource::A("192.0.2.1".parse().unwrap())),
+                Record::new("c.example.com", Class::Internet, Duration::new(3600, 0), Resource::A("192.0.2.3".parse().unwrap())),
+            ]
+        );
+        assert_eq!(errors.len(), 1);
+        match &errors[0] {
+            ZoneParseError::SyntaxError(err) => assert_eq!(err.line_col, LineColLocation::Span((4, 11), (4, 22))),
+            err => panic!("incorrect error: {:?}", err),
+        }
+
+        // A bad record spread over multiple lines is skipped as a whole, and
+        // each bad record is reported on its own line.
+        let input = "$ORIGIN example.com.
+$TTL 3600
+a  IN  MX  ( 10
+             mail.example.com. extra )
+b  IN  A   192.0.2.2
+c  IN  AAAA  192.0.2.3
+d  IN  A   192.0.2.4";
+
+        let (records, errors) = parse_zone_lenient(input);
+        let names: Vec<&str> = records.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["b.example.com", "d.example.com"]);
+
+        let lines: Vec<usize> = errors
+            .iter()
+            .map(|err| match err {
+                ZoneParseError::SyntaxError(err) => match err.line_col {
+                    LineColLocation::Pos((line, _)) => line,
+                    LineColLocation::Span((line, _), _) => line,
+                },
+                err => panic!("incorrect error: {:?}", err),
+            })
+            .collect();
+        assert_eq!(lines, vec![4, 6]);
+
+        // A backslash at the end of a line doesn't hide the newline.
+        let input = "$ORIGIN example.com.
+$TTL 3600
+a  IN  TXT  foo\\
+b  IN  A    192.0.2.300
+c  IN  A    192.0.2.3";
+
+        let (records, errors) = parse_zone_lenient(input);
+        let names: Vec<&str> = records.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["c.example.com"]);
+
+        let lines: Vec<usize> = errors
+            .iter()
+            .map(|err| match err {
+                ZoneParseError::SyntaxError(err) => match err.line_col {
+                    LineColLocation::Pos((line, _)) => line,
+                    LineColLocation::Span((line, _), _) => line,
+                },
+                err => panic!("incorrect error: {:?}", err),
+            })
+            .collect();
+        assert_eq!(lines, vec![3, 4]);
+
+        // A unterminated quote only affects its own line.
+        let input = "$ORIGIN example.com.
+$TTL 3600
+a  IN  TXT  \"good\"
+b  IN  TXT  \"unterminated
+c  IN  A    192.0.2.3";
+
+        let (records, errors) = parse_zone_lenient(input);
+        let names: Vec<&str> = records.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["a.example.com", "c.example.com"]);
+        assert_eq!(errors.len(), 1);
+
+        // As does a error in a zone using only carriage returns.
+        let input = "$ORIGIN example.com.\r$TTL 3600\ra IN A 192.0.2.1\rb IN A 192.0.2.300\rc IN A 192.0.2.3";
+
+        let (records, errors) = parse_zone_lenient(input);
+        let names: Vec<&str> = records.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["a.example.com", "c.example.com"]);
+        match &errors[..] {
+            [ZoneParseError::SyntaxError(err)] => assert_eq!(err.line_col, LineColLocation::Span((4, 8), (4, 19))),
+            errors => panic!("incorrect errors: {:?}", errors),
+        }
+
+        // A record that can't be resolved is skipped, keeping the others.
+        let input = "$ORIGIN example.com.
+a  3600  IN  A  192.0.2.1
+b        IN  A  192.0.2.2
+$TTL 3600
+c        IN  A  192.0.2.3";
+
+        let (records, errors) = parse_zone_lenient(input);
+        let names: Vec<&str> = records.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["a.example.com", "c.example.com"]);
+        assert_eq!(errors, vec![ZoneParseError::MissingTtl("b.example.com".to_string())]);
+
+        // A valid zone has no errors.
+        let (records, errors) = parse_zone_lenient("$TTL 3600\nexample.com. IN A 192.0.2.1");
+        assert_eq!(records.len(), 1);
+        assert_eq!(errors, vec![]);
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_parse_zone_from_reader_gzip() {
+        use crate::zones::parse_zone;
+        use crate::zones::parse_zone_from_reader;
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        // Example from https://datatracker.ietf.org/doc/html/rfc1035#section-5.3
+        // with a $TTL added, as the original relies on the TTL being implied.
+        let input = "$ORIGIN ISI.EDU.
+            $TTL 3600
+            @   IN  SOA     VENERA      Action\\.domains (
+                                             20     ; SERIAL
+                                             7200   ; REFRESH
+                                             600    ; RETRY
+                                             3600000; EXPIRE
+                                             60)    ; MINIMUM
+
+                    NS      A.ISI.EDU.
+                    NS      VENERA
+                    NS      VAXA
+                    MX      10      VENERA
+                    MX      20      VAXA
+
+            A       A       26.3.0.103
+
+            VENERA  A       10.1.0.52
+                    A       128.9.0.32
+
+            VAXA    A       10.2.0.27
+                    A       128.9.0.33";
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(input.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let want = parse_zone(input).expect("failed to parse");
+        assert_eq!(want.len(), 11);
+
+        let got = parse_zone_from_reader(&compressed[..]).expect("failed to parse compressed");
+        assert_eq!(got, want);
+
+        // Uncompressed input should continue to work.
+        let got = parse_zone_from_reader(input.as_bytes()).expect("failed to parse uncompressed");
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn test_into_records_relative_origin() {
+        // A relative $ORIGIN is relative to the current origin.
+        let input = "$ORIGIN example.com.
+            $TTL 3600
+            www  IN  A      192.0.2.1
+            $ORIGIN sub
+            www  IN  A      192.0.2.2
+            @    IN  MX     10 mail
+            $ORIGIN @
+            ftp  IN  CNAME  www
+            $ORIGIN other.example.
+            www  IN  A      192.0.2.3";
+
+        let got = File::from_str(input)
+            .expect("failed to parse")
+            .into_records()
+            .expect("failed to process");
+
+        assert_eq!(got, vec![
+            Record::new("www.example.com", Class::Internet, Duration::new(3600, 0), Resource::A("192.0.2.1".parse().unwrap())),
+            Record::new("www.sub.example.com", Class::Internet, Duration::new(3600, 0), Resource::A("192.0.2.2".parse().unwrap())),
+            Record::new("sub.example.com", Class::Internet, Duration::new(3600, 0), Resource::MX(MX {
+                preference: 10,
+                exchange: "mail.sub.example.com".to_string(),
+            })),
+            Record::new("ftp.sub.example.com", Class::Internet, Duration::new(3600, 0), Resource::CNAME("www.sub.example.com".to_string())),
+            Record::new("www.other.example", Class::Internet, Duration::new(3600, 0), Resource::A("192.0.2.3".parse().unwrap())),
+        ]);
+    }
+
+    #[test]
+    fn test_into_records_inherit_name() {
+        // Lines starting with whitespace use the previous owner name.
+        let input = "$ORIGIN ISI.EDU.
+            $TTL 3600
+            @   IN  SOA     VENERA      Action\\.domains ( 20 7200 600 3600000 60 )
+                    NS      A.ISI.EDU.
+                    NS      VENERA
+                    NS      VAXA
+
+            VENERA  A       10.1.0.52
+                    A       128.9.0.32
+            $TTL 60
+                    A       128.9.0.33
+            VAXA    A       10.2.0.27";
+
+        let records = File::from_str(input)
+            .expect("failed to parse")
+            .into_records()
+            .expect("failed to process");
+
+        let names = |r#type: Type| -> Vec<&str> {
+            records
+                .iter()
+                .filter(|r| r.r#type() == r#type)
+                .map(|r| r.name.as_str())
+                .collect()
+        };
+
+        assert_eq!(names(Type::SOA), vec!["ISI.EDU"]);
+        assert_eq!(names(Type::NS), vec!["ISI.EDU", "ISI.EDU", "ISI.EDU"]);
+        assert_eq!(
+            names(Type::A),
+            vec!["VENERA.ISI.EDU", "VENERA.ISI.EDU", "VENERA.ISI.EDU", "VAXA.ISI.EDU"]
+        );
+    }
+
+    #[test]
+    fn test_into_records_ttl_units() {
+        let want = File::from_str("$ORIGIN example.com.\n$TTL 3600\nwww IN A 192.0.2.1\n@ 1h IN A 192.0.2.2")
+            .expect("failed to parse")
+            .into_records()
+            .expect("failed to process");
+
+        assert_eq!(want[0].ttl, Duration::new(3600, 0));
+        assert_eq!(want[1].ttl, Duration::new(3600, 0));
+
+        for input in &[
+            "$ORIGIN example.com.\n$TTL 1h\nwww IN A 192.0.2.1\n@ 3600 IN A 192.0.2.2",
+            "$ORIGIN example.com.\n$TTL 60m\nwww IN A 192.0.2.1\n@ 1H IN A 192.0.2.2",
+        ] {
+            let got = File::from_str(input)
+                .expect("failed to parse")
+                .into_records()
+                .expect("failed to process");
+
+            assert_eq!(got, want, "incorrect result for {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_into_records_with_options() {
+        let input = "www IN A 192.0.2.1\nmail 60 A 192.0.2.2";
+        let options = ZoneOptions {
+            origin: Some("example.com.".to_string()),
+            default_ttl: Some(Duration::new(300, 0)),
+            default_class: Some(Class::Hesiod),
+            ..Default::default()
+        };
+
+        let got = File::from_str(input)
+            .expect("failed to parse")
+            .into_records_with_options(&options)
+            .expect("failed to process");
+
+        assert_eq!(got, vec![
+            Record::new("www.example.com", Class::Internet, Duration::new(300, 0), Resource::A("192.0.2.1".parse().unwrap())),
+            Record::new("mail.example.com", Class::Internet, Duration::new(60, 0), Resource::A("192.0.2.2".parse().unwrap())),
+        ]);
+    }
+
+    #[test]
+    fn test_into_records_lowercase_names() {
+        let input = "$ORIGIN Example.COM.
+            $TTL 3600
+            WWW.Example.Com. IN CNAME Web.Example.Com.
+            Mail             IN A     192.0.2.1
+                             IN A     192.0.2.2
+            @                IN MX    10 Mail";
+
+        let options = ZoneOptions {
+            lowercase_names: true,
+            ..Default::default()
+        };
+
+        let got = File::from_str(input)
+            .expect("failed to parse")
+            .into_records_with_options(&options)
+            .expect("failed to process");
+
+        // Only the owner names are lowercased, not the names in the RDATA.
+        assert_eq!(got, vec![
+            Record::new("www.example.com", Class::Internet, Duration::new(3600, 0), Resource::CNAME("Web.Example.Com".to_string())),
+            Record::new("mail.example.com", Class::Internet, Duration::new(3600, 0), Resource::A("192.0.2.1".parse().unwrap())),
+            Record::new("mail.example.com", Class::Internet, Duration::new(3600, 0), Resource::A("192.0.2.2".parse().unwrap())),
+            Record::new("example.com", Class::Internet, Duration::new(3600, 0), Resource::MX(MX {
+                preference: 10,
+                exchange: "Mail.Example.COM".to_string(),
+            })),
+        ]);
+
+        // By default the names are kept as written.
+        let got = File::from_str(input)
+            .expect("failed to parse")
+            .into_records()
+            .expect("failed to process");
+        assert_eq!(got[0].name, "WWW.Example.Com");
+        assert_eq!(got[3].name, "Example.COM");
+
+        // The names in the RDATA can be lowercased too.
+        let options = ZoneOptions {
+            lowercase_names: true,
+            lowercase_targets: true,
+            ..Default::default()
+        };
+
+        let got = File::from_str(input)
+            .expect("failed to parse")
+            .into_records_with_options(&options)
+            .expect("failed to process");
+        assert_eq!(got[0].resource, Resource::CNAME("web.example.com".to_string()));
+        assert_eq!(got[3].resource, Resource::MX(MX {
+            preference: 10,
+            exchange: "mail.example.com".to_string(),
+        }));
+
+    }
+
+    #[test]
+    fn test_into_records_strict_soa_names() {
+        let options = ZoneOptions {
+            strict_soa_names: true,
+            ..Default::default()
+        };
+
+        let accepted = vec![
+            "$ORIGIN example.com.\n$TTL 3600\n@ IN SOA ns.example.com. admin.example.com. 1 2 3 4 5",
+            "$ORIGIN example.com.\n$TTL 3600\n@ IN SOA @ admin.example.com. 1 2 3 4 5",
+        ];
+        for input in accepted {
+            let got = File::from_str(input)
+                .expect("failed to parse")
+                .into_records_with_options(&options);
+            assert!(got.is_ok(), "{:?} failed: {:?}", input, got);
+        }
+
+        let tests = vec![
+            (
+                "$ORIGIN example.com.\n$TTL 3600\n@ IN SOA ns.example.com admin.example.com. 1 2 3 4 5",
+                ZoneParseError::RelativeSoaName {
+                    field: "mname",
+                    name: "ns.example.com".to_string(),
+                },
+            ),
+            (
+                "$ORIGIN example.com.\n$TTL 3600\n@ IN SOA ns.example.com. admin 1 2 3 4 5",
+                ZoneParseError::RelativeSoaName {
+                    field: "rname",
+                    name: "admin".to_string(),
+                },
+            ),
+        ];
+        for (input, want) in tests {
+            let file = File::from_str(input).expect("failed to parse");
+
+            // Relative names are only rejected with the option.
+            assert!(file.clone().into_records().is_ok(), "{:?} failed", input);
+            assert_eq!(file.into_records_with_options(&options), Err(want), "incorrect result for {:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_into_records_root_origin() {
+        // A fragment of the root zone.
+        let input = "$ORIGIN .
+            $TTL 86400
+            @                   IN  SOA  a.root-servers.net. nstld.verisign-grs.com. ( 2023010100 1800 900 604800 86400 )
+            @                   IN  NS   a.root-servers.net.
+            com         172800  IN  NS   a.gtld-servers.net.
+            a.gtld-servers.net  172800  IN  A  192.5.6.30
+            .                   IN  MX   0 mail.example";
+
+        let got = File::from_str(input)
+            .expect("failed to parse")
+            .into_records()
+            .expect("failed to process");
+
+        // The root is the empty name, as names are stored without the trailing dot.
+        assert_eq!(got, vec![
+            Record::new("", Class::Internet, Duration::new(86400, 0), Resource::SOA(SOA {
+                mname: "a.root-servers.net".to_string(),
+                rname: "nstld@verisign-grs.com".to_string(),
+                serial: 2023010100,
+                refresh: Duration::new(1800, 0),
+                retry: Duration::new(900, 0),
+                expire: Duration::new(604800, 0),
+                minimum: Duration::new(86400, 0),
+            })),
+            Record::new("", Class::Internet, Duration::new(86400, 0), Resource::NS("a.root-servers.net".to_string())),
+            Record::new("com", Class::Internet, Duration::new(172800, 0), Resource::NS("a.gtld-servers.net".to_string())),
+            Record::new("a.gtld-servers.net", Class::Internet, Duration::new(172800, 0), Resource::A("192.5.6.30".parse().unwrap())),
+            Record::new("", Class::Internet, Duration::new(86400, 0), Resource::MX(MX {
+                preference: 0,
+                exchange: "mail.example".to_string(),
+            })),
+        ]);
+
+        // Written back with absolute names.
+        let output = write_zone(&got, &WriteOptions::default());
+        assert!(output.starts_with(". 86400 IN SOA a.root-servers.net. nstld.verisign-grs.com. "), "{}", output);
+        assert!(output.contains("\ncom. 172800 IN NS a.gtld-servers.net.\n"), "{}", output);
+        assert_eq!(parse_zone(&output).unwrap(), got);
+
+        let located = parse_zone_with_locations(input).expect("failed to parse");
+        assert_eq!(validate_zone(&located), Ok(()));
+    }
+
+    #[test]
+    fn test_into_records_afsdb_rt() {
+        let input = "$ORIGIN example.com.
+            $TTL 3600
+            @     IN  AFSDB  1 afsdb
+            host  IN  RT     10 relay.example.org.";
+
+        let got = File::from_str(input)
+            .expect("failed to parse")
+            .into_records()
+            .expect("failed to process");
+
+        assert_eq!(got, vec![
+            Record::new("example.com", Class::Internet, Duration::new(3600, 0), Resource::AFSDB(AFSDB {
+                subtype: 1,
+                hostname: "afsdb.example.com".to_string(),
+            })),
+            Record::new("host.example.com", Class::Internet, Duration::new(3600, 0), Resource::RT(RT {
+                preference: 10,
+                host: "relay.example.org".to_string(),
+            })),
+        ]);
+    }
+
+    #[test]
+    fn test_into_records_chaos() {
+        // CHAOS class records, as servers use to report their version.
+        let input = "$TTL 0
+            version.bind.   CH  TXT  \"9.16.1\"
+            hostname.bind.  CH  TXT  \"ns1\"
+            authors.bind.   CH  TXT  \"a\" \"b\"";
+
+        let got = File::from_str(input)
+            .expect("failed to parse")
+            .into_records()
+            .expect("failed to process");
+
+        assert_eq!(got, vec![
+            Record::new("version.bind", Class::Chaos, Duration::new(0, 0), Resource::TXT(TXT::from("9.16.1"))),
+            Record::new("hostname.bind", Class::Chaos, Duration::new(0, 0), Resource::TXT(TXT::from("ns1"))),
+            Record::new("authors.bind", Class::Chaos, Duration::new(0, 0), Resource::TXT(TXT::from(&["a", "b"][..]))),
+        ]);
+    }
+
+    #[test]
+    fn test_parse_record() {
+        use crate::zones::parse_record;
+
+        let options = ZoneOptions {
+            origin: Some("example.com.".to_string()),
+            default_ttl: Some(Duration::new(300, 0)),
+            default_class: Some(Class::Internet),
+            ..Default::default()
+        };
+
+        let tests = vec![
+            (
+                "www A 192.0.2.1",
+                Record::new("www.example.com", Class::Internet, Duration::new(300, 0), Resource::A("192.0.2.1".parse().unwrap())),
+            ),
+            (
+                "@ 60 IN MX 10 mail",
+                Record::new("example.com", Class::Internet, Duration::new(60, 0), Resource::MX(MX {
+                    preference: 10,
+                    exchange: "mail.example.com".to_string(),
+                })),
+            ),
+            (
+                "host.example.org. CH 3600 A 192.0.2.2",
+                Record::new("host.example.org", Class::Chaos, Duration::new(3600, 0), Resource::A("192.0.2.2".parse().unwrap())),
+            ),
+        ];
+
+        for (input, want) in tests {
+            assert_eq!(parse_record(input, &options), Ok(want), "incorrect result for '{}'", input);
+        }
+
+        // Without the options there is nothing to resolve against.
+        assert_eq!(
+            parse_record("www 300 IN A 192.0.2.1", &ZoneOptions::default()),
+            Err(ZoneParseError::MissingOrigin("www".to_string()))
+        );
+        assert_eq!(
+            parse_record("A 192.0.2.1", &options),
+            Err(ZoneParseError::MissingName)
+        );
+    }
+
+    #[test]
+    fn test_parse_zone_limits() {
+        use crate::zones::parse_zone_with_options;
+
+        let input = "$ORIGIN example.com.
+            $TTL 3600
+            www   IN  A  192.0.2.1
+            mail  IN  A  192.0.2.2";
+
+        let options = ZoneOptions {
+            max_records: Some(2),
+            max_bytes: Some(input.len()),
+            ..Default::default()
+        };
+        assert_eq!(parse_zone_with_options(input, &options).map(|r| r.len()), Ok(2));
+
+        let options = ZoneOptions {
+            max_records: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_zone_with_options(input, &options),
+            Err(ZoneParseError::TooManyRecords { limit: 1 })
+        );
+
+        let options = ZoneOptions {
+            max_bytes: Some(64),
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_zone_with_options(input, &options),
+            Err(ZoneParseError::TooLarge {
+                size: input.len(),
+                limit: 64,
+            })
+        );
+    }
+
+    #[test]
+    fn test_try_parse_zone() {
+        use crate::zones::try_parse_zone;
+
+        let input = b"$TTL 3600\nexample.com. IN A 192.0.2.1";
+        assert_eq!(try_parse_zone(input).map(|r| r.len()), Ok(1));
+
+        // Malformed input should return a error, and never panic.
+        let tests: Vec<&[u8]> = vec![
+            b"\xff\xfe\xfd",
+            b"example.com. IN A \xc3\x28",
+            b"(",
+            b")",
+            b"$ORIGIN",
+            b"$TTL 99999999999",
+            b"@ IN SOA ( ( ( ",
+            b"\\",
+            b"\0\0\0",
+        ];
+
+        for input in tests {
+            assert!(try_parse_zone(input).is_err(), "{:?} incorrectly parsed correctly", input);
+        }
+    }
+
+    #[test]
+    fn test_into_records_ttl_class_order() {
+        use crate::zones::parse_zone;
+
+        // Every order of the TTL and class, with and without a owner name.
+        // Records without a name use the previous one, and without a TTL use
+        // the $TTL.
+        let input = "$ORIGIN example.com.
+            $TTL 60
+            @    IN    A     192.0.2.1
+                 3600  IN    A     192.0.2.2
+                 IN    3600  A     192.0.2.3
+                 3600  A     192.0.2.4
+                 IN    A     192.0.2.5
+            www  3600  IN    A     192.0.2.6
+            www  IN    3600  A     192.0.2.7
+            www  3600  A     192.0.2.8
+            www  IN    A     192.0.2.9";
+
+        let got: Vec<(String, u64, String)> = parse_zone(input)
+            .expect("failed to parse")
+            .iter()
+            .map(|r| (r.name.to_owned(), r.ttl.as_secs(), r.resource.to_string()))
+            .collect();
+
+        let want = vec![
+            ("example.com", 60, "192.0.2.1"),
+            ("example.com", 3600, "192.0.2.2"),
+            ("example.com", 3600, "192.0.2.3"),
+            ("example.com", 3600, "192.0.2.4"),
+            ("example.com", 60, "192.0.2.5"),
+            ("www.example.com", 3600, "192.0.2.6"),
+            ("www.example.com", 3600, "192.0.2.7"),
+            ("www.example.com", 3600, "192.0.2.8"),
+            ("www.example.com", 60, "192.0.2.9"),
+        ];
+
+        assert_eq!(
+            got,
+            want.into_iter()
+                .map(|(name, ttl, ip)| (name.to_string(), ttl, ip.to_string()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_parse_zone_comments_only() {
+        use crate::zones::parse_zone;
+        use crate::zones::parse_zone_with_options;
+
+        let tests = vec![
+            "",
+            "; A zone with only comments",
+            "; A zone with only comments\n;\n; and no records\n",
+            "\n\n   \n\t\n",
+            "  ; indented\n\t; comments\r\n\r\n",
+            // Example from https://en.wikipedia.org/wiki/Zone_file
+            "$ORIGIN example.com.     ; designates the start of this zone file in the namespace\n\
+             $TTL 3600                ; default expiration time (in seconds) of all RRs without their own TTL value\n",
+        ];
+
+        for input in tests {
+            assert_eq!(parse_zone(input), Ok(Vec::new()), "incorrect result for {:?}", input);
+        }
+
+        let options = ZoneOptions {
+            hash_comments: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            parse_zone_with_options("# only\n; comments\n", &options),
+            Ok(Vec::new())
+        );
+
+        // A comment may also start the first line, before the records.
+        let records =
+            parse_zone("; example.com\n$ORIGIN example.com.\n$TTL 3600\nwww IN A 192.0.2.1")
+                .expect("failed to parse");
+        assert_eq!(records.len(), 1);
+    }
+
+    #[test]
+    fn test_into_records_errors() {
+        let tests = vec![
+            (
+                "www.example.com. IN A 192.0.2.1",
+                ZoneOptions::default(),
+                ZoneParseError::MissingTtl("www.example.com".to_string()),
+            ),
+            (
+                "www.example.com. 60 A 192.0.2.1",
+                ZoneOptions::default(),
+                ZoneParseError::MissingClass("www.example.com".to_string()),
+            ),
+            (
+                "www.example.com. A 192.0.2.1",
+                ZoneOptions {
+                    default_class: Some(Class::Internet),
+                    ..Default::default()
+                },
+                ZoneParseError::MissingTtl("www.example.com".to_string()),
+            ),
+            (
+                "www.example.com. 60 IN A 192.0.2.1",
+                ZoneOptions {
+                    origin: Some("example.com".to_string()),
+                    ..Default::default()
+                },
+                ZoneParseError::InvalidOrigin("example.com".to_string()),
+            ),
+            (
+                "$ORIGIN example.com\nwww 60 IN A 192.0.2.1",
+                ZoneOptions::default(),
+                ZoneParseError::InvalidOrigin("example.com".to_string()),
+            ),
+            (
+                "  60 IN A 192.0.2.1",
+                ZoneOptions::default(),
+                ZoneParseError::MissingName,
+            ),
+            (
+                "www 60 IN A 192.0.2.1",
+                ZoneOptions::default(),
+                ZoneParseError::MissingOrigin("www".to_string()),
+            ),
+            (
+                "www.example.com. 60 IN CNAME www",
+                ZoneOptions::default(),
+                ZoneParseError::MissingOrigin("www".to_string()),
+            ),
+            (
+                "$ORIGIN com.\n@ 60 IN SOA ns.example.com. @ 1 2 3 4 5",
+                ZoneOptions::default(),
+                ZoneParseError::InvalidRname("com".to_string()),
+            ),
+        ];
+
+        for (input, options, want) in tests {
+            match File::from_str(input)
+                .expect("failed to parse")
+                .into_records_with_options(&options)
+            {
+                Ok(got) => panic!("'{}' incorrectly processed correctly: {:?}", input, got),
+                Err(err) => assert_eq!(err, want, "incorrect error for '{}'", input),
             }
         }
     }
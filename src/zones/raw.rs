@@ -0,0 +1,286 @@
+// Parses a zone file while keeping its original text, so records can be
+// edited and written back with as small a change as possible.
+
+use crate::zones::parse_zone_with_options;
+use crate::zones::tokens_with_options;
+use crate::zones::Token;
+use crate::zones::TokenType;
+use crate::zones::ZoneOptions;
+use crate::zones::ZoneParseError;
+use crate::Record;
+
+/// A zone file, keeping the original text of each record, and everything
+/// between them (directives, comments and blank lines). See [`parse_zone_raw`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RawZone {
+    pub(crate) parts: Vec<RawPart>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum RawPart {
+    /// Text that isn't part of a record, written as is.
+    Text(String),
+    Record(Box<RawRecord>),
+}
+
+/// A record, along with the text it was parsed from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RawRecord {
+    /// The record, which may be changed before being written with
+    /// [`write_zone_raw`].
+    ///
+    /// [`write_zone_raw`]: crate::zones::write_zone_raw
+    pub record: Record,
+
+    /// The comment following the record, including the ";" (or "#"), for
+    /// example "; web server". Comments within a record split over multiple lines
+    /// are part of the source instead.
+    pub comment: Option<String>,
+
+    /// The record as it was parsed, to tell if the record was changed.
+    pub(crate) original: Record,
+
+    /// The text of the record, without the following comment.
+    pub(crate) source: String,
+
+    /// The whitespace between the source and the comment.
+    pub(crate) gap: String,
+
+    /// True if the source is missing the name or class, so relies on the
+    /// record before it. A missing TTL comes from `$TTL` instead.
+    pub(crate) inherits: bool,
+}
+
+impl RawZone {
+    /// Returns the records, in the order they appear.
+    pub fn records(&self) -> impl Iterator<Item = &RawRecord> {
+        self.parts.iter().filter_map(|part| match part {
+            RawPart::Record(record) => Some(record.as_ref()),
+            RawPart::Text(_) => None,
+        })
+    }
+
+    /// Returns the records, in the order they appear, so they can be changed.
+    pub fn records_mut(&mut self) -> impl Iterator<Item = &mut RawRecord> {
+        self.parts.iter_mut().filter_map(|part| match part {
+            RawPart::Record(record) => Some(record.as_mut()),
+            RawPart::Text(_) => None,
+        })
+    }
+}
+
+impl RawRecord {
+    /// Returns the text the record was parsed from, without the following
+    /// comment, for example "www  IN  A  192.0.2.1".
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns true if the record has changed since it was parsed.
+    pub fn is_changed(&self) -> bool {
+        self.record != self.original
+    }
+}
+
+/// Same as [`parse_zone`] but keeps the original text of each record, and any
+/// comment following it, so the zone can be edited and written back with
+/// [`write_zone_raw`]. Records that were not changed are written exactly as
+/// they were parsed.
+///
+/// ```
+/// use rustdns::zones::{parse_zone_raw, write_zone_raw};
+///
+/// let input = "$ORIGIN example.com.\n$TTL 3600\nwww  IN  A  192.0.2.1  ; web server\n";
+/// let mut zone = parse_zone_raw(input).unwrap();
+/// assert_eq!(write_zone_raw(&zone), input);
+///
+/// let www = zone.records_mut().next().unwrap();
+/// assert_eq!(www.comment.as_deref(), Some("; web server"));
+/// www.record.resource = rustdns::Resource::A("192.0.2.2".parse().unwrap());
+///
+/// assert_eq!(
+///     write_zone_raw(&zone),
+///     "$ORIGIN example.com.\n$TTL 3600\nwww.example.com. 3600 IN A 192.0.2.2  ; web server\n"
+/// );
+/// ```
+///
+/// [`parse_zone`]: crate::zones::parse_zone
+/// [`write_zone_raw`]: crate::zones::write_zone_raw
+pub fn parse_zone_raw(input: &str) -> Result<RawZone, ZoneParseError> {
+    parse_zone_raw_with_options(input, &ZoneOptions::default())
+}
+
+/// Same as [`parse_zone_raw`] but using the supplied [`ZoneOptions`], for
+/// example to allow '#' comments.
+pub fn parse_zone_raw_with_options(
+    input: &str,
+    options: &ZoneOptions,
+) -> Result<RawZone, ZoneParseError> {
+    let mut records = parse_zone_with_options(input, options)?.into_iter();
+    let tokens: Vec<Token> = tokens_with_options(input, options)?.collect();
+
+    let mut parts = Vec::new();
+
+    // The start of the text not yet added to a part.
+    let mut text_start = 0;
+
+    // Each newline ends a directive or record.
+    for line in tokens.split(|token| token.r#type == TokenType::Newline) {
+        let has = |r#type| line.iter().any(|token| token.r#type == r#type);
+        if !has(TokenType::Type) {
+            continue;
+        }
+
+        // The record starts at the beginning of the line, to keep any
+        // indentation, and ends at its last token before any comment.
+        let line_start = input[..line[0].range.start]
+            .rfind(['\r', '\n'])
+            .map_or(0, |i| i + 1);
+        let source_end = line
+            .iter()
+            .filter(|token| token.r#type != TokenType::Comment)
+            .map(|token| token.range.end)
+            .max()
+            .unwrap();
+        let comment = line
+            .iter()
+            .find(|token| token.r#type == TokenType::Comment && token.range.start >= source_end);
+
+        if text_start < line_start {
+            parts.push(RawPart::Text(input[text_start..line_start].to_string()));
+        }
+
+        let record = records
+            .next()
+            .expect("there should be a record for each line with a type");
+
+        parts.push(RawPart::Record(Box::new(RawRecord {
+            original: record.clone(),
+            record,
+            comment: comment.map(|token| token.text.to_string()),
+            source: input[line_start..source_end].to_string(),
+            gap: match comment {
+                Some(token) => input[source_end..token.range.start].to_string(),
+                None => String::new(),
+            },
+            inherits: !(has(TokenType::Name) && has(TokenType::Class)),
+        })));
+
+        text_start = comment.map_or(source_end, |token| token.range.end);
+    }
+
+    if text_start < input.len() {
+        parts.push(RawPart::Text(input[text_start..].to_string()));
+    }
+
+    Ok(RawZone { parts })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zones::parse_zone;
+    use crate::zones::parse_zone_raw;
+    use crate::zones::parse_zone_raw_with_options;
+    use crate::zones::write_zone_raw;
+    use crate::zones::ZoneOptions;
+    use crate::Resource;
+    use pretty_assertions::assert_eq;
+
+    static INPUT: &str = "; Example zone
+$ORIGIN example.com.
+$TTL 3600
+
+@       IN  SOA   ns admin (
+                  2020091025 ; serial
+                  7200 3600 1209600 3600 )
+@       IN  NS    ns          ; primary
+ns      IN  A     192.0.2.1
+www     IN  A     192.0.2.2   ; web server
+        IN  AAAA  2001:db8::2
+mail    IN  MX    10 mail     ;no space
+";
+
+    #[test]
+    fn test_parse_zone_raw() {
+        let zone = parse_zone_raw(INPUT).expect("failed to parse");
+
+        let records: Vec<_> = zone.records().map(|raw| raw.record.clone()).collect();
+        assert_eq!(records, parse_zone(INPUT).unwrap());
+
+        let got: Vec<(&str, Option<&str>)> = zone
+            .records()
+            .map(|raw| (raw.source(), raw.comment.as_deref()))
+            .collect();
+        assert_eq!(
+            got,
+            vec![
+                (
+                    "@       IN  SOA   ns admin (\n                  2020091025 ; serial\n                  7200 3600 1209600 3600 )",
+                    None
+                ),
+                ("@       IN  NS    ns", Some("; primary")),
+                ("ns      IN  A     192.0.2.1", None),
+                ("www     IN  A     192.0.2.2", Some("; web server")),
+                ("        IN  AAAA  2001:db8::2", None),
+                ("mail    IN  MX    10 mail", Some(";no space")),
+            ]
+        );
+
+        // Unchanged, the output is the same as the input.
+        assert_eq!(write_zone_raw(&zone), INPUT);
+        let crlf = INPUT.replace('\n', "\r\n");
+        assert_eq!(write_zone_raw(&parse_zone_raw(&crlf).unwrap()), crlf);
+    }
+
+    #[test]
+    fn test_write_zone_raw_changed() {
+        let mut zone = parse_zone_raw(INPUT).expect("failed to parse");
+
+        let www = zone.records_mut().nth(3).unwrap();
+        www.record.name = "web.example.com".to_string();
+        www.record.resource = Resource::A("192.0.2.3".parse().unwrap());
+        assert!(www.is_changed());
+
+        let mail = zone.records_mut().nth(5).unwrap();
+        mail.comment = Some("; changed comment".to_string());
+        assert!(!mail.is_changed());
+
+        // The AAAA record, which had no name of its own, is also written in
+        // full so it keeps its name.
+        let want = "; Example zone
+$ORIGIN example.com.
+$TTL 3600
+
+@       IN  SOA   ns admin (
+                  2020091025 ; serial
+                  7200 3600 1209600 3600 )
+@       IN  NS    ns          ; primary
+ns      IN  A     192.0.2.1
+web.example.com. 3600 IN A 192.0.2.3   ; web server
+www.example.com. 3600 IN AAAA 2001:db8::2
+mail    IN  MX    10 mail     ; changed comment
+";
+        let got = write_zone_raw(&zone);
+        assert_eq!(got, want);
+
+        let mut records = parse_zone(INPUT).unwrap();
+        records[3].name = "web.example.com".to_string();
+        records[3].resource = Resource::A("192.0.2.3".parse().unwrap());
+        assert_eq!(parse_zone(&got).unwrap(), records);
+    }
+
+    #[test]
+    fn test_parse_zone_raw_hash_comments() {
+        let input = "# Example zone\n$TTL 3600\nwww.example.com. IN A 192.0.2.1 # web server\n";
+        let options = ZoneOptions {
+            hash_comments: true,
+            ..Default::default()
+        };
+
+        let zone = parse_zone_raw_with_options(input, &options).expect("failed to parse");
+        let www = zone.records().next().unwrap();
+        assert_eq!(www.source(), "www.example.com. IN A 192.0.2.1");
+        assert_eq!(www.comment.as_deref(), Some("# web server"));
+        assert_eq!(write_zone_raw(&zone), input);
+    }
+}
@@ -0,0 +1,183 @@
+// Parses the RDATA of a single record type, for tools that already know the
+// type, and so don't need to search through all the record types.
+
+use crate::zones::parser::Rule;
+use crate::zones::parser::ZoneParser;
+use crate::zones::preprocessor::offset_error;
+use crate::zones::ZoneOptions;
+use crate::zones::ZoneParseError;
+use crate::Resource;
+use crate::Type;
+
+/// Parses the RDATA of a record of the given type, in zone file format. The
+/// generic `\# len hex` form ([rfc3597]) is also accepted.
+///
+/// ```
+/// use rustdns::zones::parse_rdata;
+/// use rustdns::{Resource, Type};
+///
+/// let a = parse_rdata(Type::A, "192.0.2.1").unwrap();
+/// assert_eq!(a, Resource::A("192.0.2.1".parse().unwrap()));
+///
+/// let a = parse_rdata(Type::A, r"\# 4 c0000201").unwrap();
+/// assert_eq!(a, Resource::A("192.0.2.1".parse().unwrap()));
+/// ```
+///
+/// As there is no zone to provide context, any relative domain names are
+/// returned as is.
+///
+/// [rfc3597]: https://datatracker.ietf.org/doc/html/rfc3597
+pub fn parse_rdata(r#type: Type, input: &str) -> Result<Resource, ZoneParseError> {
+    // The grammar expects the type before the RDATA, so add it, and then
+    // remove it from any errors.
+    let prefix = format!("{} ", r#type);
+    let resource = format!("{}{}", prefix, input);
+    let relocate = |err| offset_error(err, input, prefix.len());
+
+    let options = ZoneOptions::default();
+    let inputs =
+        ZoneParser::parse_input(Rule::single_resource, &resource, &options).map_err(relocate)?;
+    let input = inputs.single().map_err(relocate)?;
+
+    Ok(ZoneParser::single_resource(input).map_err(relocate)?)
+}
+
+macro_rules! rdata_parsers {
+    ($($name:ident => $type:ident,)*) => {
+        $(
+            #[doc = concat!("Parses the RDATA of a ", stringify!($type), " record. See [`parse_rdata`].")]
+            pub fn $name(input: &str) -> Result<Resource, ZoneParseError> {
+                parse_rdata(Type::$type, input)
+            }
+        )*
+    };
+}
+
+rdata_parsers! {
+    parse_a => A,
+    parse_aaaa => AAAA,
+    parse_cname => CNAME,
+    parse_ns => NS,
+    parse_mx => MX,
+    parse_wks => WKS,
+    parse_afsdb => AFSDB,
+    parse_x25 => X25,
+    parse_isdn => ISDN,
+    parse_rt => RT,
+    parse_nsap => NSAP,
+    parse_nsap_ptr => NSAP_PTR,
+    parse_gpos => GPOS,
+    parse_atma => ATMA,
+    parse_ptr => PTR,
+    parse_soa => SOA,
+    parse_txt => TXT,
+    parse_spf => SPF,
+    parse_srv => SRV,
+    parse_uri => URI,
+    parse_apl => APL,
+    parse_cert => CERT,
+    parse_dhcid => DHCID,
+    parse_hip => HIP,
+    parse_smimea => SMIMEA,
+    parse_cds => CDS,
+    parse_cdnskey => CDNSKEY,
+    parse_openpgpkey => OPENPGPKEY,
+    parse_csync => CSYNC,
+    parse_svcb => SVCB,
+    parse_https => HTTPS,
+    parse_nid => NID,
+    parse_l32 => L32,
+    parse_l64 => L64,
+    parse_lp => LP,
+    parse_eui48 => EUI48,
+    parse_eui64 => EUI64,
+    parse_key => KEY,
+    parse_sig => SIG,
+    parse_avc => AVC,
+    parse_doa => DOA,
+    parse_tkey => TKEY,
+    parse_tsig => TSIG,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zones::parse_mx;
+    use crate::zones::parse_rdata;
+    use crate::zones::parse_soa;
+    use crate::zones::ZoneParseError;
+    use crate::Resource;
+    use crate::Type;
+    use crate::MX;
+    use crate::SOA;
+    use core::time::Duration;
+    use pest::error::LineColLocation;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_rdata() {
+        assert_eq!(
+            parse_soa("soa soa 1 2 3 4 5"),
+            Ok(Resource::SOA(SOA {
+                mname: "soa".to_string(),
+                rname: "soa".to_string(),
+                serial: 1,
+                refresh: Duration::new(2, 0),
+                retry: Duration::new(3, 0),
+                expire: Duration::new(4, 0),
+                minimum: Duration::new(5, 0),
+            }))
+        );
+
+        assert_eq!(
+            parse_mx("10 mail.example.com."),
+            Ok(Resource::MX(MX {
+                preference: 10,
+                exchange: "mail.example.com.".to_string(),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_parse_rdata_errors() {
+        // The errors should be relative to the RDATA, without the type.
+        let tests = vec![
+            (
+                Type::MX,
+                "70000 mail",
+                "value out of range for MX preference",
+                (1, 1),
+            ),
+            (Type::A, "192.0.2.1 extra", "", (1, 11)),
+            (Type::A, "192.0.2", "", (1, 1)),
+        ];
+
+        for (r#type, input, want, pos) in tests {
+            match parse_rdata(r#type, input) {
+                Ok(got) => panic!("{:?} incorrectly parsed correctly: {:?}", input, got),
+                Err(ZoneParseError::SyntaxError(err)) => {
+                    assert!(
+                        err.to_string().contains(want),
+                        "incorrect error for {:?}:\n{}",
+                        input,
+                        err
+                    );
+                    assert!(
+                        err.to_string().contains(&format!("1 | {}\n", input)),
+                        "incorrect line for {:?}:\n{}",
+                        input,
+                        err
+                    );
+                    match err.line_col {
+                        LineColLocation::Span(start, _) => {
+                            assert_eq!(start, pos, "incorrect location for {:?}", input)
+                        }
+                        LineColLocation::Pos(start) => {
+                            assert_eq!(start, pos, "incorrect location for {:?}", input)
+                        }
+                    }
+                }
+                Err(err) => panic!("{:?} unexpected error: {}", input, err),
+            }
+        }
+    }
+}
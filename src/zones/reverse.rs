@@ -0,0 +1,225 @@
+// Builds the PTR records for a reverse zone, from a list of addresses and the
+// host names they belong to.
+
+use crate::util::reverse;
+use crate::zones::ValidationError;
+use crate::Class;
+use crate::Record;
+use crate::Resource;
+use std::net::IpAddr;
+use std::time::Duration;
+
+/// Returns a PTR record for each address, named with its reverse DNS name
+/// (in-addr.arpa or ip6.arpa), pointing to the host name. The records are
+/// returned in the same order as the entries.
+///
+/// Every address must be within the network, given as a address and prefix
+/// length, such as 192.0.2.0/24. Use [`validate_reverse_zone`] to check
+/// records from other sources.
+///
+/// ```
+/// use rustdns::zones::build_ptr_zone;
+/// use rustdns::Resource;
+/// use std::time::Duration;
+///
+/// let network = "192.0.2.0".parse().unwrap();
+/// let entries = [("192.0.2.1".parse().unwrap(), "www.example.com.")];
+///
+/// let records = build_ptr_zone(network, 24, Duration::from_secs(3600), &entries).unwrap();
+/// assert_eq!(records[0].name, "1.2.0.192.in-addr.arpa");
+/// assert_eq!(records[0].resource, Resource::PTR("www.example.com".to_string()));
+/// ```
+///
+/// [`validate_reverse_zone`]: crate::zones::validate_reverse_zone
+pub fn build_ptr_zone(
+    network: IpAddr,
+    prefix: u8,
+    ttl: Duration,
+    entries: &[(IpAddr, &str)],
+) -> Result<Vec<Record>, ValidationError> {
+    let max_prefix = match network {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    };
+    if prefix > max_prefix {
+        return Err(ValidationError::InvalidPrefix { network, prefix });
+    }
+
+    entries
+        .iter()
+        .map(|(address, host)| {
+            if !in_network(*address, network, prefix) {
+                return Err(ValidationError::OutsideNetwork {
+                    address: *address,
+                    network,
+                    prefix,
+                });
+            }
+
+            // Names are stored without the trailing dot, the same as a
+            // processed zone file.
+            Ok(Record::new(
+                reverse(*address).trim_end_matches('.'),
+                Class::Internet,
+                ttl,
+                Resource::PTR(host.trim_end_matches('.').to_string()),
+            ))
+        })
+        .collect()
+}
+
+// Returns true if the first `prefix` bits of the address and network match.
+fn in_network(address: IpAddr, network: IpAddr, prefix: u8) -> bool {
+    match (address, network) {
+        (IpAddr::V4(address), IpAddr::V4(network)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            u32::from(address) & mask == u32::from(network) & mask
+        }
+        (IpAddr::V6(address), IpAddr::V6(network)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            u128::from(address) & mask == u128::from(network) & mask
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zones::build_ptr_zone;
+    use crate::zones::validate_reverse_zone;
+    use crate::zones::ValidationError;
+    use crate::Class;
+    use crate::Record;
+    use crate::Resource;
+    use core::time::Duration;
+    use pretty_assertions::assert_eq;
+
+    fn ptr(name: &str, host: &str) -> Record {
+        Record::new(
+            name,
+            Class::Internet,
+            Duration::new(3600, 0),
+            Resource::PTR(host.to_string()),
+        )
+    }
+
+    #[test]
+    fn test_build_ptr_zone_ipv4() {
+        let entries = [
+            ("192.0.2.1".parse().unwrap(), "www.example.com."),
+            ("192.0.2.25".parse().unwrap(), "mail.example.com"),
+            ("192.0.2.255".parse().unwrap(), "broadcast.example.com."),
+        ];
+
+        let records = build_ptr_zone(
+            "192.0.2.0".parse().unwrap(),
+            24,
+            Duration::new(3600, 0),
+            &entries,
+        )
+        .expect("failed to build");
+
+        assert_eq!(
+            records,
+            vec![
+                ptr("1.2.0.192.in-addr.arpa", "www.example.com"),
+                ptr("25.2.0.192.in-addr.arpa", "mail.example.com"),
+                ptr("255.2.0.192.in-addr.arpa", "broadcast.example.com"),
+            ]
+        );
+        assert_eq!(validate_reverse_zone(&records), Ok(()));
+
+        let entries = [("192.0.3.1".parse().unwrap(), "www.example.com.")];
+        assert_eq!(
+            build_ptr_zone(
+                "192.0.2.0".parse().unwrap(),
+                24,
+                Duration::new(3600, 0),
+                &entries
+            ),
+            Err(ValidationError::OutsideNetwork {
+                address: "192.0.3.1".parse().unwrap(),
+                network: "192.0.2.0".parse().unwrap(),
+                prefix: 24,
+            })
+        );
+    }
+
+    #[test]
+    fn test_build_ptr_zone_ipv6() {
+        let entries = [
+            ("2001:db8::1".parse().unwrap(), "www.example.com."),
+            ("2001:db8::ab".parse().unwrap(), "mail.example.com."),
+        ];
+
+        let records = build_ptr_zone(
+            "2001:db8::".parse().unwrap(),
+            120,
+            Duration::new(3600, 0),
+            &entries,
+        )
+        .expect("failed to build");
+
+        assert_eq!(
+            records,
+            vec![
+                ptr(
+                    "1.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa",
+                    "www.example.com"
+                ),
+                ptr(
+                    "b.a.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa",
+                    "mail.example.com"
+                ),
+            ]
+        );
+        assert_eq!(validate_reverse_zone(&records), Ok(()));
+
+        let tests = vec![
+            // Outside the /120.
+            (
+                "2001:db8::1:1",
+                120,
+                ValidationError::OutsideNetwork {
+                    address: "2001:db8::1:1".parse().unwrap(),
+                    network: "2001:db8::".parse().unwrap(),
+                    prefix: 120,
+                },
+            ),
+            // A IPv4 address is never within a IPv6 network.
+            (
+                "192.0.2.1",
+                0,
+                ValidationError::OutsideNetwork {
+                    address: "192.0.2.1".parse().unwrap(),
+                    network: "2001:db8::".parse().unwrap(),
+                    prefix: 0,
+                },
+            ),
+            (
+                "2001:db8::1",
+                129,
+                ValidationError::InvalidPrefix {
+                    network: "2001:db8::".parse().unwrap(),
+                    prefix: 129,
+                },
+            ),
+        ];
+
+        for (address, prefix, want) in tests {
+            let entries = [(address.parse().unwrap(), "www.example.com.")];
+            assert_eq!(
+                build_ptr_zone(
+                    "2001:db8::".parse().unwrap(),
+                    prefix,
+                    Duration::new(3600, 0),
+                    &entries
+                ),
+                Err(want),
+                "incorrect result for {}/{}",
+                address,
+                prefix
+            );
+        }
+    }
+}
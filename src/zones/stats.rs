@@ -0,0 +1,86 @@
+use crate::Record;
+use crate::Type;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Stats about a parsed zone file, as returned by [`parse_zone_with_stats`].
+///
+/// [`parse_zone_with_stats`]: crate::zones::parse_zone_with_stats
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ZoneStats {
+    /// The number of lines in the input.
+    pub lines: usize,
+
+    /// The number of records found, for each record type.
+    pub records: HashMap<Type, usize>,
+
+    /// The time taken to parse and process the zone.
+    pub duration: Duration,
+}
+
+impl ZoneStats {
+    pub(crate) fn new(input: &str, records: &[Record], duration: Duration) -> ZoneStats {
+        let mut counts = HashMap::new();
+        for record in records {
+            *counts.entry(record.r#type()).or_insert(0) += 1;
+        }
+
+        ZoneStats {
+            lines: input.lines().count(),
+            records: counts,
+            duration,
+        }
+    }
+
+    /// Returns the total number of records.
+    pub fn total_records(&self) -> usize {
+        self.records.values().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zones::parse_zone_with_stats;
+    use crate::Type;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_zone_with_stats() {
+        // Example from https://datatracker.ietf.org/doc/html/rfc1035#section-5.3
+        // with a $TTL added, as the original relies on the TTL being implied.
+        let input = "$ORIGIN ISI.EDU.
+            $TTL 3600
+            @   IN  SOA     VENERA      Action\\.domains (
+                                             20     ; SERIAL
+                                             7200   ; REFRESH
+                                             600    ; RETRY
+                                             3600000; EXPIRE
+                                             60)    ; MINIMUM
+
+                    NS      A.ISI.EDU.
+                    NS      VENERA
+                    NS      VAXA
+                    MX      10      VENERA
+                    MX      20      VAXA
+
+            A       A       26.3.0.103
+
+            VENERA  A       10.1.0.52
+                    A       128.9.0.32
+
+            VAXA    A       10.2.0.27
+                    A       128.9.0.33";
+
+        let (records, stats) = parse_zone_with_stats(input).expect("failed to parse");
+
+        assert_eq!(records.len(), 11);
+        assert_eq!(stats.total_records(), 11);
+        assert_eq!(stats.lines, 22);
+
+        assert_eq!(stats.records.get(&Type::SOA), Some(&1));
+        assert_eq!(stats.records.get(&Type::NS), Some(&3));
+        assert_eq!(stats.records.get(&Type::MX), Some(&2));
+        assert_eq!(stats.records.get(&Type::A), Some(&5));
+        assert_eq!(stats.records.get(&Type::AAAA), None);
+    }
+}
@@ -0,0 +1,498 @@
+// Exposes the tokens of a zone file, for tools (such as formatters) that
+// need to work with the text, instead of the resolved records.
+
+use crate::zones::parser::Rule;
+use crate::zones::parser::ZoneParser;
+use crate::zones::preprocessor::convert_error;
+use crate::zones::preprocessor::preprocess;
+use crate::zones::preprocessor::relocate_error;
+use crate::zones::preprocessor::strip_bom;
+use crate::zones::ZoneOptions;
+use crate::zones::ZoneParseError;
+use pest::iterators::Pair;
+use std::ops::Range;
+
+/// The type of a [`Token`].
+#[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
+pub enum TokenType {
+    /// A directive, such as "$ORIGIN" or "$TTL".
+    Directive,
+
+    /// The owner name of a record.
+    Name,
+
+    /// The TTL of a record.
+    Ttl,
+
+    /// The class of a record, such as "IN".
+    Class,
+
+    /// The type of a record, such as "A" or "SOA".
+    Type,
+
+    /// The arguments to a directive, or the RDATA of a record. For example
+    /// "192.0.2.1". RDATA split into multiple parts is returned as multiple
+    /// tokens.
+    Data,
+
+    /// A comment, including the leading ";" (or "#", if
+    /// [`ZoneOptions::hash_comments`] is set).
+    Comment,
+
+    /// A "(" used to continue a record over multiple lines.
+    OpenParen,
+
+    /// A ")" used to continue a record over multiple lines.
+    CloseParen,
+
+    /// The end of a line. Newlines within parentheses are not returned, so
+    /// each Newline ends a directive or record.
+    Newline,
+}
+
+/// A single token within a zone file. Whitespace between tokens is not
+/// included.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Token<'a> {
+    pub r#type: TokenType,
+
+    /// The text of the token, as it appears in the input.
+    pub text: &'a str,
+
+    /// The byte range of the token within the input.
+    pub range: Range<usize>,
+}
+
+/// An iterator over the [`Token`]s of a zone file, as returned by [`tokens`].
+#[derive(Clone, Debug)]
+pub struct Tokens<'a> {
+    tokens: std::vec::IntoIter<Token<'a>>,
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.tokens.next()
+    }
+}
+
+/// Returns the tokens within a zone file, in the order they appear. As the
+/// whole input is checked first, a syntax error is returned instead of
+/// any tokens.
+///
+/// ```
+/// use rustdns::zones::{tokens, TokenType};
+///
+/// let types: Vec<TokenType> = tokens("www IN A 192.0.2.1").unwrap().map(|t| t.r#type).collect();
+/// assert_eq!(types, vec![TokenType::Name, TokenType::Class, TokenType::Type, TokenType::Data]);
+/// ```
+pub fn tokens(input: &str) -> Result<Tokens<'_>, ZoneParseError> {
+    tokens_with_options(input, &ZoneOptions::default())
+}
+
+/// Same as [`tokens`] but using the supplied [`ZoneOptions`] to control the
+/// parsing. For example, with [`ZoneOptions::hash_comments`] any '#'
+/// comments are returned as [`TokenType::Comment`].
+///
+/// ```
+/// use rustdns::zones::{tokens_with_options, TokenType, ZoneOptions};
+///
+/// let options = ZoneOptions {
+///     hash_comments: true,
+///     ..Default::default()
+/// };
+/// let last = tokens_with_options("www IN A 192.0.2.1 # web", &options).unwrap().last().unwrap();
+/// assert_eq!((last.r#type, last.text), (TokenType::Comment, "# web"));
+/// ```
+pub fn tokens_with_options<'a>(
+    input: &'a str,
+    options: &ZoneOptions,
+) -> Result<Tokens<'a>, ZoneParseError> {
+    // Any BOM is skipped, but the ranges are still within the whole input.
+    let bom = input.len() - strip_bom(input).len();
+    let input = strip_bom(input);
+
+    let preprocessed = preprocess(input, options).map_err(|err| convert_error(err, input))?;
+
+    // Errors are relative to the preprocessed input, so move them back.
+    let relocate = |err| relocate_error(err, input);
+
+    let inputs = ZoneParser::parse_input(Rule::file, &preprocessed, options).map_err(relocate)?;
+    let file = inputs.single().map_err(relocate)?.into_pair();
+
+    // The preprocessor never changes the length of the input, so the
+    // positions found in the preprocessed input are valid in the original.
+    let mut tokens = Vec::new();
+    for pair in file.into_inner() {
+        add_tokens(input, pair, &mut tokens);
+    }
+
+    // Finally add the comments, parentheses and newlines that were not part
+    // of the parse tree, by looking at the gaps between the tokens.
+    let mut all = Vec::new();
+    let mut end = 0;
+    let mut depth = 0;
+    for token in tokens {
+        add_gap_tokens(input, end..token.range.start, &mut depth, options, &mut all);
+        end = token.range.end;
+        all.push(token);
+    }
+    add_gap_tokens(input, end..input.len(), &mut depth, options, &mut all);
+
+    for token in &mut all {
+        token.range = token.range.start + bom..token.range.end + bom;
+    }
+
+    Ok(Tokens {
+        tokens: all.into_iter(),
+    })
+}
+
+fn token(input: &str, r#type: TokenType, range: Range<usize>) -> Token<'_> {
+    Token {
+        r#type,
+        text: &input[range.clone()],
+        range,
+    }
+}
+
+// Adds the tokens for a pair found at the top level of the file.
+fn add_tokens<'a>(input: &'a str, pair: Pair<Rule>, tokens: &mut Vec<Token<'a>>) {
+    let span = pair.as_span();
+    match pair.as_rule() {
+        Rule::origin | Rule::ttl => {
+            add_keyword(input, TokenType::Directive, &pair, tokens);
+            for pair in pair.into_inner() {
+                add_data(input, pair, tokens);
+            }
+        }
+        Rule::unknown_directive => {
+            // Not parsed any further, so each word after the keyword is data.
+            add_keyword(input, TokenType::Directive, &pair, tokens);
+
+            let text = pair.as_str();
+            let mut end = text.find(char::is_whitespace).unwrap_or(text.len());
+            for word in text.split_whitespace().skip(1) {
+                let start = end + text[end..].find(word).unwrap();
+                end = start + word.len();
+
+                let range = span.start() + start..span.start() + end;
+                tokens.push(token(input, TokenType::Data, range));
+            }
+        }
+        Rule::record => {
+            for pair in pair.into_inner() {
+                let span = pair.as_span();
+                let range = span.start()..span.end();
+                match pair.as_rule() {
+                    Rule::domain => tokens.push(token(input, TokenType::Name, range)),
+                    Rule::duration | Rule::custom_ttl => {
+                        tokens.push(token(input, TokenType::Ttl, range))
+                    }
+                    Rule::class | Rule::unknown_class => {
+                        tokens.push(token(input, TokenType::Class, range))
+                    }
+
+                    // Otherwise one of the resources.
+                    _ => {
+                        let generic = matches!(
+                            pair.as_rule(),
+                            Rule::resource_generic | Rule::resource_unmatched
+                        );
+
+                        add_keyword(input, TokenType::Type, &pair, tokens);
+                        for pair in pair.into_inner() {
+                            // The type of a generic, or unmatched, record was added as the keyword.
+                            if generic && pair.as_rule() == Rule::record_type {
+                                continue;
+                            }
+                            add_data(input, pair, tokens);
+                        }
+                    }
+                }
+            }
+        }
+        Rule::EOI => (),
+        rule => unreachable!("unexpected rule {:?} '{}'", rule, span.as_str()),
+    }
+}
+
+// Adds the keyword at the start of a pair, such as the "$ORIGIN" in
+// "$ORIGIN example.com.", or the "A" in "A 192.0.2.1".
+fn add_keyword<'a>(
+    input: &'a str,
+    r#type: TokenType,
+    pair: &Pair<Rule>,
+    tokens: &mut Vec<Token<'a>>,
+) {
+    let start = pair.as_span().start();
+    let len = pair
+        .as_str()
+        .find(|c: char| c.is_whitespace() || c == '(' || c == ')')
+        .unwrap_or_else(|| pair.as_str().len());
+
+    tokens.push(token(input, r#type, start..start + len));
+}
+
+fn add_data<'a>(input: &'a str, pair: Pair<Rule>, tokens: &mut Vec<Token<'a>>) {
+    let span = pair.as_span();
+    tokens.push(token(input, TokenType::Data, span.start()..span.end()));
+}
+
+// Adds any comments, parentheses, or newlines found in the range, which
+// otherwise only contains whitespace. The depth is the number of currently
+// open parentheses, within which newlines are skipped. A '#' is only found
+// here if it starts a comment, as otherwise it is part of a token.
+fn add_gap_tokens<'a>(
+    input: &'a str,
+    range: Range<usize>,
+    depth: &mut usize,
+    options: &ZoneOptions,
+    tokens: &mut Vec<Token<'a>>,
+) {
+    let gap = &input[range.clone()];
+
+    let mut chars = gap.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let start = range.start + i;
+        match c {
+            '(' => {
+                *depth += 1;
+                tokens.push(token(input, TokenType::OpenParen, start..start + 1));
+            }
+            ')' => {
+                *depth = depth.saturating_sub(1);
+                tokens.push(token(input, TokenType::CloseParen, start..start + 1));
+            }
+            '\r' if matches!(chars.peek(), Some((_, '\n'))) => {
+                chars.next();
+                if *depth == 0 {
+                    tokens.push(token(input, TokenType::Newline, start..start + 2));
+                }
+            }
+            '\n' | '\r' if *depth > 0 => (),
+            '\n' | '\r' => tokens.push(token(input, TokenType::Newline, start..start + 1)),
+            '#' if !options.hash_comments => (),
+            ';' | '#' => {
+                let len = gap[i..].find(['\r', '\n']).unwrap_or(gap.len() - i);
+                tokens.push(token(input, TokenType::Comment, start..start + len));
+                while matches!(chars.peek(), Some((j, _)) if *j < i + len) {
+                    chars.next();
+                }
+            }
+            _ => (), // Whitespace
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zones::tokens;
+    use crate::zones::tokens_with_options;
+    use crate::zones::TokenType;
+    use crate::zones::TokenType::*;
+    use crate::zones::ZoneOptions;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_tokens() {
+        let input = "$ORIGIN example.com.
+@   3600 IN SOA ns admin ( 2020091025 ; serial
+                           7200 3600 1209600 3600 )
+www      IN A   192.0.2.1 ; web server";
+
+        let got: Vec<(TokenType, &str)> = tokens(input)
+            .expect("failed to tokenize")
+            .map(|t| (t.r#type, t.text))
+            .collect();
+
+        assert_eq!(
+            got,
+            vec![
+                (Directive, "$ORIGIN"),
+                (Data, "example.com."),
+                (Newline, "\n"),
+                (Name, "@"),
+                (Ttl, "3600"),
+                (Class, "IN"),
+                (Type, "SOA"),
+                (Data, "ns"),
+                (Data, "admin"),
+                (OpenParen, "("),
+                (Data, "2020091025"),
+                (Comment, "; serial"),
+                (Data, "7200"),
+                (Data, "3600"),
+                (Data, "1209600"),
+                (Data, "3600"),
+                (CloseParen, ")"),
+                (Newline, "\n"),
+                (Name, "www"),
+                (Class, "IN"),
+                (Type, "A"),
+                (Data, "192.0.2.1"),
+                (Comment, "; web server"),
+            ]
+        );
+
+        // The ranges should match the text.
+        for token in tokens(input).unwrap() {
+            assert_eq!(&input[token.range.clone()], token.text);
+        }
+    }
+
+    #[test]
+    fn test_tokens_hash_comments() {
+        let input = "# Example zone\nwww IN A \\# 4 c0000201 #web server\nmail IN TXT a#b";
+        let options = ZoneOptions {
+            hash_comments: true,
+            ..Default::default()
+        };
+
+        let got: Vec<(TokenType, &str)> = tokens_with_options(input, &options)
+            .expect("failed to tokenize")
+            .map(|t| (t.r#type, t.text))
+            .collect();
+
+        assert_eq!(
+            got,
+            vec![
+                (Comment, "# Example zone"),
+                (Newline, "\n"),
+                (Name, "www"),
+                (Class, "IN"),
+                (Type, "A"),
+                (Data, "\\#"),
+                (Data, "4"),
+                (Data, "c0000201"),
+                (Comment, "#web server"),
+                (Newline, "\n"),
+                (Name, "mail"),
+                (Class, "IN"),
+                (Type, "TXT"),
+                (Data, "a#b"),
+            ]
+        );
+
+        // Without the option, the zone is a syntax error.
+        assert!(tokens(input).is_err());
+    }
+
+    #[test]
+    fn test_tokens_bom() {
+        // The BOM is skipped, but the ranges still match the whole input.
+        let input = "\u{feff}www IN A 192.0.2.1";
+
+        let got: Vec<(TokenType, &str)> = tokens(input)
+            .expect("failed to tokenize")
+            .map(|t| (t.r#type, t.text))
+            .collect();
+
+        assert_eq!(
+            got,
+            vec![(Name, "www"), (Class, "IN"), (Type, "A"), (Data, "192.0.2.1")]
+        );
+
+        for token in tokens(input).unwrap() {
+            assert_eq!(&input[token.range.clone()], token.text);
+        }
+    }
+
+    #[test]
+    fn test_tokens_unknown_directive() {
+        // Unsupported directives are still tokenized, so they can be highlighted.
+        let input = "$INCLUDE  other.zone example.com. ; included\nwww IN A 192.0.2.1";
+
+        let got: Vec<(TokenType, &str)> = tokens(input)
+            .expect("failed to tokenize")
+            .map(|t| (t.r#type, t.text))
+            .collect();
+
+        assert_eq!(
+            got,
+            vec![
+                (Directive, "$INCLUDE"),
+                (Data, "other.zone"),
+                (Data, "example.com."),
+                (Comment, "; included"),
+                (Newline, "\n"),
+                (Name, "www"),
+                (Class, "IN"),
+                (Type, "A"),
+                (Data, "192.0.2.1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokens_records() {
+        let input = "$TTL 3600
+@   IN SOA ns admin (
+        2020091025 ; serial
+        7200 3600 1209600 3600 )
+@   IN NS  ns
+ns  IN A   192.0.2.1
+www IN A   \\# 4 c0000202
+";
+
+        // Splitting on newlines should give one line per directive or record,
+        // with the SOA kept together.
+        let mut lines = vec![Vec::new()];
+        for token in tokens(input).expect("failed to tokenize") {
+            match token.r#type {
+                Newline => lines.push(Vec::new()),
+                OpenParen | CloseParen | Comment => (),
+                _ => lines.last_mut().unwrap().push(token.text),
+            }
+        }
+
+        assert_eq!(
+            lines,
+            vec![
+                vec!["$TTL", "3600"],
+                vec![
+                    "@",
+                    "IN",
+                    "SOA",
+                    "ns",
+                    "admin",
+                    "2020091025",
+                    "7200",
+                    "3600",
+                    "1209600",
+                    "3600"
+                ],
+                vec!["@", "IN", "NS", "ns"],
+                vec!["ns", "IN", "A", "192.0.2.1"],
+                vec!["www", "IN", "A", "\\#", "4", "c0000202"],
+                vec![],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokens_whitespace() {
+        // A run of spaces and tabs is a single separator, and is not returned.
+        let input = "A      A        1.2.3.4\n\t \twww \t\t IN\t  A \t192.0.2.1";
+
+        let got: Vec<(TokenType, &str)> = tokens(input)
+            .expect("failed to tokenize")
+            .map(|t| (t.r#type, t.text))
+            .collect();
+
+        assert_eq!(
+            got,
+            vec![
+                (Name, "A"),
+                (Type, "A"),
+                (Data, "1.2.3.4"),
+                (Newline, "\n"),
+                (Name, "www"),
+                (Class, "IN"),
+                (Type, "A"),
+                (Data, "192.0.2.1"),
+            ]
+        );
+    }
+}
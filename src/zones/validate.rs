@@ -0,0 +1,599 @@
+// Validates a processed Zone File, looking for records that are well-formed
+// but are not valid together, or in a specific kind of zone.
+
+use crate::util::reverse_to_ip;
+use crate::zones::lint::is_subdomain;
+use crate::zones::Location;
+use crate::Record;
+use crate::Resource;
+use crate::Type;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::net::IpAddr;
+use thiserror::Error;
+
+/// Problems found when validating a zone.
+#[derive(Error, Clone, Debug, PartialEq)]
+pub enum ValidationError {
+    #[error("'{0}' is not a valid reverse DNS name")]
+    InvalidReverseName(String),
+
+    #[error("'{address}' is not within the network {network}/{prefix}")]
+    OutsideNetwork {
+        address: IpAddr,
+        network: IpAddr,
+        prefix: u8,
+    },
+
+    #[error("invalid prefix length for the network {network}/{prefix}")]
+    InvalidPrefix { network: IpAddr, prefix: u8 },
+
+    /// A CNAME must be the only record at a name, see [rfc1034 section 3.6.2].
+    ///
+    /// [rfc1034 section 3.6.2]: https://datatracker.ietf.org/doc/html/rfc1034#section-3.6.2
+    #[error("'{name}' has a CNAME (line {}) and a {other_type} record (line {})", cname.line, other.line)]
+    CnameConflict {
+        name: String,
+        cname: Location,
+        other_type: Type,
+        other: Location,
+    },
+
+    /// Only the NS records (and any DS, NSEC or RRSIG records) may be at a
+    /// delegation, and only glue address records below it, as the names
+    /// belong to the child zone, see [rfc1034 section 4.2.1].
+    ///
+    /// [rfc1034 section 4.2.1]: https://datatracker.ietf.org/doc/html/rfc1034#section-4.2.1
+    #[error("'{name}' has a {record_type} record (line {}) within the delegation '{delegation}' (line {})", location.line, ns.line)]
+    InsideDelegation {
+        name: String,
+        record_type: Type,
+        location: Location,
+        delegation: String,
+        ns: Location,
+    },
+
+    /// A zone must have a SOA record at its origin, see [rfc1035 section 5.2].
+    ///
+    /// [rfc1035 section 5.2]: https://datatracker.ietf.org/doc/html/rfc1035#section-5.2
+    #[error("zone has no SOA record")]
+    MissingSoa,
+
+    #[error("zone has {0} SOA records, but must have exactly one")]
+    MultipleSoa(usize),
+
+    #[error("SOA record '{name}' is not at the zone's origin '{origin}'")]
+    SoaNotAtOrigin { name: String, origin: String },
+
+    #[error("'{name}' is outside the zone '{origin}'")]
+    OutsideZone { name: String, origin: String },
+}
+
+/// Checks the records are valid together. Currently this checks there is
+/// exactly one SOA record, every other record is at or below the SOA's name
+/// (the apex), and no CNAME shares its name with any other record, including
+/// another CNAME. The RRSIG and NSEC records that secure a CNAME are allowed
+/// to share its name ([rfc4035 section 2.5]).
+///
+/// NS records below the apex delegate a name to another zone, so the only
+/// other records allowed at, or below, that name are the DS, NSEC and RRSIG
+/// records at the delegation, and the glue A and AAAA records.
+///
+/// The SOA may appear anywhere in the records, not only first, as some
+/// generated zones list `$TTL` and NS records before it.
+///
+/// The records and their locations are as returned by [`parse_zone_with_locations`].
+///
+/// [`parse_zone_with_locations`]: crate::zones::parse_zone_with_locations
+/// [rfc4035 section 2.5]: https://datatracker.ietf.org/doc/html/rfc4035#section-2.5
+pub fn validate_zone(records: &[(Record, Location)]) -> Result<(), ValidationError> {
+    let soa = find_soa(records.iter().map(|(record, _)| record))?;
+
+    let apex = soa.name.trim_end_matches('.');
+    let apex_lower = apex.to_ascii_lowercase();
+    for (record, _) in records {
+        let name = record.name.trim_end_matches('.').to_ascii_lowercase();
+        if !is_subdomain(&name, &apex_lower) {
+            return Err(ValidationError::OutsideZone {
+                name: record.name.to_owned(),
+                origin: apex.to_owned(),
+            });
+        }
+    }
+
+    // Group the records by name, keeping the order they were defined.
+    let mut names: HashMap<String, Vec<&(Record, Location)>> = HashMap::new();
+    for record in records {
+        names
+            .entry(record.0.name.to_ascii_lowercase())
+            .or_default()
+            .push(record);
+    }
+
+    for entry in records {
+        let (record, location) = entry;
+        if record.r#type() != Type::CNAME {
+            continue;
+        }
+
+        let others = &names[&record.name.to_ascii_lowercase()];
+        if let Some((other, other_location)) = others.iter().find(|other| {
+            !std::ptr::eq(**other, entry) && !CNAME_EXEMPT.contains(&other.0.resource.type_code())
+        }) {
+            return Err(ValidationError::CnameConflict {
+                name: record.name.to_owned(),
+                cname: *location,
+                other_type: other.r#type(),
+                other: *other_location,
+            });
+        }
+    }
+
+    // The names delegated to another zone, along with the NS record.
+    let delegations: Vec<(String, Location)> = records
+        .iter()
+        .filter(|(record, _)| record.r#type() == Type::NS)
+        .map(|(record, location)| {
+            (
+                record.name.trim_end_matches('.').to_ascii_lowercase(),
+                *location,
+            )
+        })
+        .filter(|(name, _)| *name != apex_lower)
+        .collect();
+
+    for (record, location) in records {
+        let name = record.name.trim_end_matches('.').to_ascii_lowercase();
+        for (delegation, ns) in &delegations {
+            if !is_subdomain(&name, delegation) {
+                continue;
+            }
+
+            let allowed = match record.r#type() {
+                Type::A | Type::AAAA => true,
+                Type::NS => name == *delegation,
+                _ => name == *delegation && DELEGATION_TYPES.contains(&record.resource.type_code()),
+            };
+            if !allowed {
+                return Err(ValidationError::InsideDelegation {
+                    name: record.name.to_owned(),
+                    record_type: record.r#type(),
+                    location: *location,
+                    delegation: delegation.to_owned(),
+                    ns: *ns,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// The RRSIG (46) and NSEC (47) types, which may share a name with a CNAME.
+const CNAME_EXEMPT: [u16; 2] = [46, 47];
+
+// The DS (43), RRSIG (46) and NSEC (47) types, which may be at a delegation
+// along with the NS records.
+const DELEGATION_TYPES: [u16; 3] = [43, 46, 47];
+
+/// Returns the zone's only SOA record, wherever it appears in the records.
+pub(crate) fn find_soa<'a>(
+    records: impl Iterator<Item = &'a Record>,
+) -> Result<&'a Record, ValidationError> {
+    let soas: Vec<&Record> = records
+        .filter(|record| record.r#type() == Type::SOA)
+        .collect();
+
+    match soas.as_slice() {
+        [] => Err(ValidationError::MissingSoa),
+        [soa] => Ok(soa),
+        _ => Err(ValidationError::MultipleSoa(soas.len())),
+    }
+}
+
+/// Returns the CNAME, MX, NS and SRV targets that are within the zone, but
+/// have no records, along with the location of the record pointing to them.
+/// This finds typos such as a MX for "mial" instead of "mail".
+///
+/// The zone is the name of each SOA record. Targets outside the zone are
+/// ignored, as are targets within a delegated subdomain, as their records
+/// are in another zone. A target matched by a wildcard, such as
+/// "*.example.com", is not dangling.
+///
+/// The records and their locations are as returned by [`parse_zone_with_locations`].
+///
+/// ```
+/// use rustdns::zones::{dangling_targets, parse_zone_with_locations};
+///
+/// let records = parse_zone_with_locations("$ORIGIN example.com.
+///     $TTL 3600
+///     @     IN  SOA  ns admin ( 1 7200 3600 1209600 3600 )
+///     @     IN  MX   10 mial
+///     mail  IN  A    192.0.2.1").unwrap();
+///
+/// let dangling = dangling_targets(&records);
+/// assert_eq!(dangling[0].0, "mial.example.com");
+/// assert_eq!(dangling[0].1.line, 4);
+/// ```
+///
+/// [`parse_zone_with_locations`]: crate::zones::parse_zone_with_locations
+pub fn dangling_targets(records: &[(Record, Location)]) -> Vec<(String, Location)> {
+    let mut names = HashSet::new();
+    let mut apexes = Vec::new();
+    let mut delegations = Vec::new();
+    for (record, _) in records {
+        let name = record.name.to_ascii_lowercase();
+        match record.resource {
+            Resource::SOA(_) => apexes.push(name.clone()),
+            Resource::NS(_) => delegations.push(name.clone()),
+            _ => (),
+        }
+        names.insert(name);
+    }
+
+    // NS records at the apex are for the zone itself, not a delegation.
+    delegations.retain(|name| !apexes.contains(name));
+
+    let defined = |target: &str| {
+        if names.contains(target) {
+            return true;
+        }
+
+        let mut parent = target;
+        while let Some((_, rest)) = parent.split_once('.') {
+            parent = rest;
+            if names.contains(&format!("*.{}", parent)) {
+                return true;
+            }
+        }
+        false
+    };
+
+    let mut dangling = Vec::new();
+    for (record, location) in records {
+        let target = match &record.resource {
+            Resource::CNAME(name) | Resource::NS(name) => name,
+            Resource::MX(mx) => &mx.exchange,
+            Resource::SRV(srv) => &srv.name,
+            _ => continue,
+        };
+
+        let lower = target.trim_end_matches('.').to_ascii_lowercase();
+        let in_zone = apexes.iter().any(|apex| is_subdomain(&lower, apex));
+        let delegated = delegations
+            .iter()
+            .any(|delegation| is_subdomain(&lower, delegation));
+
+        if in_zone && !delegated && !defined(&lower) {
+            dangling.push((target.to_owned(), *location));
+        }
+    }
+
+    dangling
+}
+
+/// Checks that every PTR record in this reverse zone has a owner name that
+/// is a valid reverse DNS name, for example "1.2.0.192.in-addr.arpa".
+///
+/// See [`crate::util::reverse`] to create such a name from a IP address.
+pub fn validate_reverse_zone(records: &[Record]) -> Result<(), ValidationError> {
+    for record in records {
+        if let Resource::PTR(_) = record.resource {
+            if reverse_to_ip(&record.name).is_none() {
+                return Err(ValidationError::InvalidReverseName(record.name.to_owned()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zones::dangling_targets;
+    use crate::zones::parse_zone;
+    use crate::zones::parse_zone_with_locations;
+    use crate::zones::validate_reverse_zone;
+    use crate::zones::validate_zone;
+    use crate::zones::Location;
+    use crate::zones::ValidationError;
+    use crate::Type;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_validate_zone() {
+        let records = parse_zone_with_locations(
+            "$ORIGIN example.com.
+            $TTL 3600
+            @    IN  SOA    ns admin ( 1 7200 3600 1209600 3600 )
+            @    IN  NS     ns
+            ns   IN  A      192.0.2.1
+            www  IN  CNAME  ns",
+        )
+        .expect("failed to parse");
+
+        assert_eq!(validate_zone(&records), Ok(()));
+
+        let records = parse_zone_with_locations(
+            "$ORIGIN example.com.
+            $TTL 3600
+            @    IN  SOA    ns admin ( 1 7200 3600 1209600 3600 )
+            www  IN  CNAME  ns
+            ns   IN  A      192.0.2.1
+            www  IN  A      192.0.2.2",
+        )
+        .expect("failed to parse");
+
+        let err = validate_zone(&records).expect_err("incorrectly validated");
+        assert_eq!(
+            err,
+            ValidationError::CnameConflict {
+                name: "www.example.com".to_string(),
+                cname: Location {
+                    line: 4,
+                    column: 13
+                },
+                other_type: Type::A,
+                other: Location {
+                    line: 6,
+                    column: 13
+                },
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "'www.example.com' has a CNAME (line 4) and a A record (line 6)"
+        );
+
+        // Nor can there be two CNAMEs, but the RRSIG and NSEC records
+        // securing a CNAME are allowed.
+        let records = parse_zone_with_locations(
+            "$ORIGIN example.com.
+            $TTL 3600
+            @    IN  SOA    ns admin ( 1 7200 3600 1209600 3600 )
+            www  IN  CNAME  ns
+            www  IN  TYPE46 \\# 0
+            www  IN  TYPE47 \\# 0
+            www  IN  CNAME  mail",
+        )
+        .expect("failed to parse");
+
+        assert_eq!(
+            validate_zone(&records),
+            Err(ValidationError::CnameConflict {
+                name: "www.example.com".to_string(),
+                cname: Location {
+                    line: 4,
+                    column: 13
+                },
+                other_type: Type::CNAME,
+                other: Location {
+                    line: 7,
+                    column: 13
+                },
+            })
+        );
+        assert_eq!(validate_zone(&records[..3]), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_zone_delegations() {
+        // Only the NS, DS and glue records are at or below a delegation.
+        let records = parse_zone_with_locations(
+            "$ORIGIN example.com.
+            $TTL 3600
+            @          IN  SOA  ns admin ( 1 7200 3600 1209600 3600 )
+            @          IN  NS   ns
+            ns         IN  A    192.0.2.1
+            sub        IN  NS   ns.sub
+            sub        IN  TYPE43 \\# 4 dead0801
+            ns.sub     IN  A    192.0.2.2
+            ns.sub     IN  AAAA 2001:db8::2",
+        )
+        .expect("failed to parse");
+
+        assert_eq!(validate_zone(&records), Ok(()));
+
+        let tests = vec![
+            (
+                "sub     IN  MX  10 mail",
+                ValidationError::InsideDelegation {
+                    name: "sub.example.com".to_string(),
+                    record_type: Type::MX,
+                    location: Location {
+                        line: 5,
+                        column: 17,
+                    },
+                    delegation: "sub.example.com".to_string(),
+                    ns: Location {
+                        line: 4,
+                        column: 17,
+                    },
+                },
+            ),
+            (
+                "www.sub IN  TXT \"hello\"",
+                ValidationError::InsideDelegation {
+                    name: "www.sub.example.com".to_string(),
+                    record_type: Type::TXT,
+                    location: Location {
+                        line: 5,
+                        column: 17,
+                    },
+                    delegation: "sub.example.com".to_string(),
+                    ns: Location {
+                        line: 4,
+                        column: 17,
+                    },
+                },
+            ),
+            (
+                "a.sub   IN  NS  ns.a.sub",
+                ValidationError::InsideDelegation {
+                    name: "a.sub.example.com".to_string(),
+                    record_type: Type::NS,
+                    location: Location {
+                        line: 5,
+                        column: 17,
+                    },
+                    delegation: "sub.example.com".to_string(),
+                    ns: Location {
+                        line: 4,
+                        column: 17,
+                    },
+                },
+            ),
+        ];
+
+        for (record, want) in tests {
+            let input = format!(
+                "$ORIGIN example.com.
+                $TTL 3600
+                @       IN  SOA ns admin ( 1 7200 3600 1209600 3600 )
+                sub     IN  NS  ns.sub
+                {}",
+                record
+            );
+            let records = parse_zone_with_locations(&input).expect("failed to parse");
+            assert_eq!(
+                validate_zone(&records),
+                Err(want),
+                "incorrect result for {:?}",
+                record
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_zone_soa() {
+        // The SOA doesn't need to be first.
+        let records = parse_zone_with_locations(
+            "$TTL 3600
+            ; Generated zone, the name servers are listed first.
+            example.com.      IN  NS   ns.example.com.
+            example.com.      IN  SOA  ns.example.com. admin.example.com. ( 1 7200 3600 1209600 3600 )
+            ns.example.com.   IN  A    192.0.2.1",
+        )
+        .expect("failed to parse");
+
+        assert_eq!(validate_zone(&records), Ok(()));
+
+        let tests = vec![
+            (
+                "$ORIGIN example.com.
+                $TTL 3600
+                @  IN  NS  ns",
+                ValidationError::MissingSoa,
+            ),
+            (
+                "$ORIGIN example.com.
+                $TTL 3600
+                @  IN  SOA  ns admin ( 1 7200 3600 1209600 3600 )
+                @  IN  SOA  ns admin ( 2 7200 3600 1209600 3600 )",
+                ValidationError::MultipleSoa(2),
+            ),
+            // The SOA must be at the apex, above every other record.
+            (
+                "$ORIGIN example.com.
+                $TTL 3600
+                @    IN  NS   ns
+                sub  IN  SOA  ns admin ( 1 7200 3600 1209600 3600 )",
+                ValidationError::OutsideZone {
+                    name: "example.com".to_string(),
+                    origin: "sub.example.com".to_string(),
+                },
+            ),
+        ];
+
+        for (input, want) in tests {
+            let records = parse_zone_with_locations(input).expect("failed to parse");
+            assert_eq!(
+                validate_zone(&records),
+                Err(want),
+                "incorrect result for {:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_dangling_targets() {
+        let records = parse_zone_with_locations(
+            "$ORIGIN example.com.
+            $TTL 3600
+            @       IN  SOA    ns admin ( 1 7200 3600 1209600 3600 )
+            @       IN  NS     ns
+            @       IN  NS     ns.other.example.
+            @       IN  MX     10 mial
+            @       IN  MX     20 mail.exmaple.com.
+            sub     IN  NS     ns.sub
+            ns      IN  A      192.0.2.1
+            mail    IN  A      192.0.2.2
+            www     IN  CNAME  web
+            ftp     IN  CNAME  host.sub
+            *.dyn   IN  A      192.0.2.3
+            home    IN  CNAME  router.dyn
+            _sip._tcp  IN  SRV  0 5 5060 sip",
+        )
+        .expect("failed to parse");
+
+        // The misspelt "mail.exmaple.com" is outside the zone, so can't be
+        // checked, and "host.sub" is delegated, as is the glue "ns.sub".
+        assert_eq!(
+            dangling_targets(&records),
+            vec![
+                (
+                    "mial.example.com".to_string(),
+                    Location {
+                        line: 6,
+                        column: 13
+                    }
+                ),
+                (
+                    "web.example.com".to_string(),
+                    Location {
+                        line: 11,
+                        column: 13
+                    }
+                ),
+                (
+                    "sip.example.com".to_string(),
+                    Location {
+                        line: 15,
+                        column: 13
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_reverse_zone() {
+        let records = parse_zone(
+            "$ORIGIN 2.0.192.in-addr.arpa.
+            $TTL 3600
+            @  IN  SOA  ns.example.com. admin.example.com. ( 1 7200 3600 1209600 3600 )
+            @  IN  NS   ns.example.com.
+            1  3600  IN  PTR  www.example.com.
+            2  3600  IN  PTR  mail.example.com.",
+        )
+        .expect("failed to parse");
+
+        assert_eq!(validate_reverse_zone(&records), Ok(()));
+
+        let records = parse_zone(
+            "$ORIGIN 2.0.192.in-addr.arpa.
+            $TTL 3600
+            1    3600  IN  PTR  www.example.com.
+            www  3600  IN  PTR  www.example.com.",
+        )
+        .expect("failed to parse");
+
+        assert_eq!(
+            validate_reverse_zone(&records),
+            Err(ValidationError::InvalidReverseName(
+                "www.2.0.192.in-addr.arpa".to_string()
+            ))
+        );
+    }
+}
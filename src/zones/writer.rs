@@ -0,0 +1,400 @@
+// Writes Records back out in the Zone File format.
+
+use crate::resource::*;
+use crate::zones::lint::duplicate_key;
+use crate::zones::raw::RawPart;
+use crate::zones::RawZone;
+use crate::Record;
+use crate::Resource;
+use chrono::NaiveDate;
+use std::collections::HashSet;
+use std::fmt::Write;
+
+/// Options to control how a zone file is written. See [`write_zone`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct WriteOptions {
+    /// If set, a `$ORIGIN` entry is written at the start of the zone. Must be
+    /// a absolute domain, e.g "example.com.".
+    pub origin: Option<String>,
+
+    /// Write names relative to the `origin`, for example "www.example.com."
+    /// is written as "www", and "example.com." as "@". This has no effect if
+    /// the `origin` is not set.
+    pub relativize: bool,
+
+    /// If set, the serial of any SOA record is increased, as needed after the
+    /// zone was changed, using this as today's date. See
+    /// [`SOA::next_serial_date`] for how the next serial is chosen.
+    pub bump_serial: Option<NaiveDate>,
+
+    /// Skip any record identical to one already written, with the same name,
+    /// class, TTL and RDATA. Names are compared case-insensitively, as
+    /// reported by [`LintKind::DuplicateRecord`].
+    ///
+    /// [`LintKind::DuplicateRecord`]: crate::zones::LintKind::DuplicateRecord
+    pub dedup: bool,
+}
+
+/// Writes the records in the zone file format, one record per line.
+///
+/// ```
+/// use rustdns::zones::{parse_zone, write_zone, WriteOptions};
+///
+/// let records = parse_zone("$ORIGIN example.com.\n$TTL 3600\nwww IN A 192.0.2.1").unwrap();
+///
+/// let options = WriteOptions {
+///     origin: Some("example.com.".to_string()),
+///     relativize: true,
+///     ..Default::default()
+/// };
+/// assert_eq!(write_zone(&records, &options), "$ORIGIN example.com.\nwww 3600 IN A 192.0.2.1\n");
+/// ```
+pub fn write_zone(records: &[Record], options: &WriteOptions) -> String {
+    let writer = Writer::new(options);
+    let mut output = String::new();
+
+    if let Some(origin) = &options.origin {
+        writeln!(output, "$ORIGIN {}", origin).unwrap();
+    }
+
+    let mut seen = HashSet::new();
+    for record in records {
+        if options.dedup && !seen.insert(duplicate_key(record)) {
+            continue;
+        }
+
+        writeln!(output, "{}", writer.record(record)).unwrap();
+    }
+
+    output
+}
+
+/// Writes a zone parsed by [`parse_zone_raw`], keeping the original text of
+/// any record that was not changed, and everything between the records.
+///
+/// A changed record is written on one line, with absolute names and its TTL
+/// and class, followed by its comment. Later records that relied on it for
+/// their name or class are written the same way, so they don't change.
+///
+/// [`parse_zone_raw`]: crate::zones::parse_zone_raw
+pub fn write_zone_raw(zone: &RawZone) -> String {
+    let writer = Writer::new(&WriteOptions::default());
+    let mut output = String::new();
+
+    let mut rewritten = false;
+    for part in &zone.parts {
+        let raw = match part {
+            RawPart::Text(text) => {
+                output += text;
+                continue;
+            }
+            RawPart::Record(raw) => raw,
+        };
+
+        rewritten = raw.is_changed() || (rewritten && raw.inherits);
+        if rewritten {
+            output += &writer.record(&raw.record);
+        } else {
+            output += &raw.source;
+        }
+
+        if let Some(comment) = &raw.comment {
+            output += if raw.gap.is_empty() { " " } else { &raw.gap };
+            output += comment;
+        }
+    }
+
+    output
+}
+
+struct Writer {
+    // The origin without the trailing dot, if relativizing.
+    origin: Option<String>,
+
+    // Today's date, if the SOA serial should be bumped.
+    bump_serial: Option<NaiveDate>,
+}
+
+impl Writer {
+    fn new(options: &WriteOptions) -> Writer {
+        let origin = match (&options.origin, options.relativize) {
+            (Some(origin), true) => Some(origin.trim_end_matches('.').to_ascii_lowercase()),
+            _ => None,
+        };
+
+        Writer {
+            origin,
+            bump_serial: options.bump_serial,
+        }
+    }
+
+    /// Returns the record in the zone file format, on one line.
+    fn record(&self, record: &Record) -> String {
+        format!(
+            "{name} {ttl} {class} {type} {resource}",
+            name = self.name(&record.name),
+            ttl = record.ttl.as_secs(),
+            class = record.class,
+            r#type = record.resource.type_name(),
+            resource = self.resource(&record.resource),
+        )
+    }
+
+    /// Returns the name in a form suitable for the zone file. This is the
+    /// inverse of resolving a name against the origin.
+    fn name(&self, name: &str) -> String {
+        let name = name.trim_end_matches('.');
+
+        if let Some(origin) = &self.origin {
+            let lower = name.to_ascii_lowercase();
+            if lower == *origin {
+                return "@".to_string();
+            }
+
+            if let Some(relative) = lower.strip_suffix(origin.as_str()) {
+                if relative.ends_with('.') {
+                    return name[..relative.len() - 1].to_string();
+                }
+            }
+        }
+
+        // Otherwise a absolute name.
+        name.to_string() + "."
+    }
+
+    /// Returns the resource in a form suitable for the zone file, with any
+    /// names written the same way as the owner names.
+    fn resource(&self, resource: &Resource) -> Resource {
+        match resource {
+            Resource::CNAME(domain) => Resource::CNAME(self.name(domain)),
+            Resource::NS(domain) => Resource::NS(self.name(domain)),
+            Resource::PTR(domain) => Resource::PTR(self.name(domain)),
+            Resource::NSAP_PTR(domain) => Resource::NSAP_PTR(self.name(domain)),
+            Resource::MX(mx) => Resource::MX(MX {
+                preference: mx.preference,
+                exchange: self.name(&mx.exchange),
+            }),
+            Resource::AFSDB(afsdb) => Resource::AFSDB(AFSDB {
+                subtype: afsdb.subtype,
+                hostname: self.name(&afsdb.hostname),
+            }),
+            Resource::RT(rt) => Resource::RT(RT {
+                preference: rt.preference,
+                host: self.name(&rt.host),
+            }),
+            Resource::SOA(soa) => {
+                // The rname is stored as a email address, so convert back.
+                let rname = match SOA::email_to_rname(&soa.rname) {
+                    Ok(rname) => rname,
+                    Err(_) => soa.rname.to_owned(),
+                };
+
+                let serial = match self.bump_serial {
+                    Some(today) => soa.next_serial_date(today),
+                    None => soa.serial,
+                };
+
+                Resource::SOA(SOA {
+                    mname: self.name(&soa.mname),
+                    rname: self.name(&rname),
+                    serial,
+                    ..soa.clone()
+                })
+            }
+            Resource::SRV(srv) => Resource::SRV(SRV {
+                name: self.name(&srv.name),
+                ..srv.clone()
+            }),
+            Resource::LP(lp) => Resource::LP(LP {
+                preference: lp.preference,
+                fqdn: self.name(&lp.fqdn),
+            }),
+            Resource::HIP(hip) => Resource::HIP(HIP {
+                rendezvous_servers: hip
+                    .rendezvous_servers
+                    .iter()
+                    .map(|server| self.name(server))
+                    .collect(),
+                ..hip.clone()
+            }),
+            Resource::SIG(sig) => Resource::SIG(SIG {
+                signer_name: self.name(&sig.signer_name),
+                ..sig.clone()
+            }),
+            Resource::SVCB(svcb) => Resource::SVCB(self.svcb(svcb)),
+            Resource::HTTPS(svcb) => Resource::HTTPS(self.svcb(svcb)),
+
+            // The rest don't contain a domain, so are written as is.
+            _ => resource.clone(),
+        }
+    }
+
+    fn svcb(&self, svcb: &SVCB) -> SVCB {
+        SVCB {
+            target: self.name(&svcb.target),
+            ..svcb.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zones::parse_zone;
+    use crate::zones::parse_zone_with_options;
+    use crate::zones::write_zone;
+    use crate::zones::WriteOptions;
+    use crate::zones::ZoneOptions;
+    use chrono::NaiveDate;
+    use pretty_assertions::assert_eq;
+
+    static INPUT: &str = "$ORIGIN example.com.
+        $TTL 3600
+        @             IN  SOA   ns dns\\.admin ( 2020091025 7200 3600 1209600 3600 )
+        @             IN  NS    ns
+        @             IN  NS    ns.somewhere.example.
+        @             IN  MX    10 mail
+        www           IN  A     192.0.2.1
+        ns            IN  AAAA  2001:db8:10::2
+        wwwtest       IN  CNAME www
+        other.org.    IN  CNAME www
+        ";
+
+    #[test]
+    fn test_write_zone() {
+        let records = parse_zone(INPUT).expect("failed to parse");
+
+        let tests = vec![
+            (
+                WriteOptions::default(),
+                "example.com. 3600 IN SOA ns.example.com. dns\\.admin.example.com. 2020091025 7200 3600 1209600 3600
+example.com. 3600 IN NS ns.example.com.
+example.com. 3600 IN NS ns.somewhere.example.
+example.com. 3600 IN MX 10 mail.example.com.
+www.example.com. 3600 IN A 192.0.2.1
+ns.example.com. 3600 IN AAAA 2001:db8:10::2
+wwwtest.example.com. 3600 IN CNAME www.example.com.
+other.org. 3600 IN CNAME www.example.com.
+",
+            ),
+            (
+                WriteOptions {
+                    origin: Some("example.com.".to_string()),
+                    relativize: true,
+                    ..Default::default()
+                },
+                "$ORIGIN example.com.
+@ 3600 IN SOA ns dns\\.admin 2020091025 7200 3600 1209600 3600
+@ 3600 IN NS ns
+@ 3600 IN NS ns.somewhere.example.
+@ 3600 IN MX 10 mail
+www 3600 IN A 192.0.2.1
+ns 3600 IN AAAA 2001:db8:10::2
+wwwtest 3600 IN CNAME www
+other.org. 3600 IN CNAME www
+",
+            ),
+        ];
+
+        for (options, want) in tests {
+            assert_eq!(
+                write_zone(&records, &options),
+                want,
+                "incorrect result for {:?}",
+                options
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_zone_dedup() {
+        let records = parse_zone(
+            "$ORIGIN example.com.
+            $TTL 3600
+            www  IN  A  192.0.2.1
+            www  IN  A  192.0.2.2
+            WWW  IN  A  192.0.2.1
+            www  60  A  192.0.2.1",
+        )
+        .expect("failed to parse");
+
+        let options = WriteOptions {
+            dedup: true,
+            ..Default::default()
+        };
+
+        // Only the third record is a duplicate, as the last has a different TTL.
+        assert_eq!(
+            write_zone(&records, &options),
+            "www.example.com. 3600 IN A 192.0.2.1
+www.example.com. 3600 IN A 192.0.2.2
+www.example.com. 60 IN A 192.0.2.1
+"
+        );
+    }
+
+    #[test]
+    fn test_write_zone_bump_serial() {
+        let tests = vec![
+            // The same day, so the counter is incremented.
+            ("2020091025", NaiveDate::from_ymd_opt(2020, 9, 10).unwrap(), "2020091026"),
+            // A later day, so the counter is reset.
+            ("2020091025", NaiveDate::from_ymd_opt(2020, 9, 11).unwrap(), "2020091100"),
+            ("2020091025", NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(), "2021010100"),
+            // No more changes that day, so roll over into the next.
+            ("2020091099", NaiveDate::from_ymd_opt(2020, 9, 10).unwrap(), "2020091100"),
+            // Not a date, so it's just incremented.
+            ("4000000000", NaiveDate::from_ymd_opt(2020, 9, 10).unwrap(), "4000000001"),
+        ];
+
+        for (serial, today, want) in tests {
+            let input = format!(
+                "$ORIGIN example.com.\n$TTL 3600\n@ IN SOA ns admin {} 7200 3600 1209600 3600\nwww IN A 192.0.2.1",
+                serial
+            );
+            let records = parse_zone(&input).expect("failed to parse");
+
+            let options = WriteOptions {
+                bump_serial: Some(today),
+                ..Default::default()
+            };
+            assert_eq!(
+                write_zone(&records, &options),
+                format!(
+                    "example.com. 3600 IN SOA ns.example.com. admin.example.com. {} 7200 3600 1209600 3600
+www.example.com. 3600 IN A 192.0.2.1
+",
+                    want
+                ),
+                "incorrect result for {} on {}",
+                serial,
+                today
+            );
+
+            // Without the option, the serial is unchanged.
+            assert!(write_zone(&records, &WriteOptions::default()).contains(serial));
+        }
+    }
+
+    #[test]
+    fn test_write_zone_round_trip() {
+        let records = parse_zone(INPUT).expect("failed to parse");
+
+        for relativize in [false, true] {
+            let options = WriteOptions {
+                origin: Some("example.com.".to_string()),
+                relativize,
+                ..Default::default()
+            };
+            let output = write_zone(&records, &options);
+
+            let zone_options = ZoneOptions {
+                origin: Some("example.com.".to_string()),
+                ..Default::default()
+            };
+            let got = parse_zone_with_options(&output, &zone_options)
+                .unwrap_or_else(|err| panic!("failed to parse:\n{}\n{}", output, err));
+
+            assert_eq!(got, records, "incorrect round trip for:\n{}", output);
+        }
+    }
+}
@@ -0,0 +1,699 @@
+// Builds a complete, validated, Zone from individual records.
+
+use crate::resource::write_name;
+use crate::resource::SOA;
+use crate::zones::lint::duplicate_key;
+use crate::zones::lint::is_subdomain;
+use crate::zones::validate::find_soa;
+use crate::zones::validate_zone;
+use crate::zones::Location;
+use crate::zones::ValidationError;
+use crate::Record;
+use crate::Class;
+use crate::Resource;
+use crate::Type;
+
+/// A record's name, type, class, TTL (in seconds) and RDATA, in the wire
+/// format, as returned by [`Zone::to_wire_records`].
+pub type WireRecord = (Vec<u8>, Type, Class, u32, Vec<u8>);
+
+/// A complete zone, with exactly one SOA record at its origin, as built by
+/// [`ZoneBuilder`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct Zone {
+    origin: String,
+    records: Vec<Record>,
+}
+
+impl Zone {
+    /// Returns the origin (or apex) of the zone, without the trailing dot.
+    pub fn origin(&self) -> &str {
+        &self.origin
+    }
+
+    /// Returns the zone's SOA.
+    pub fn soa(&self) -> &SOA {
+        self.records
+            .iter()
+            .find_map(|record| match &record.resource {
+                Resource::SOA(soa) => Some(soa),
+                _ => None,
+            })
+            .expect("zone has no SOA")
+    }
+
+    /// Returns all the records, in the order they were added.
+    pub fn records(&self) -> &[Record] {
+        &self.records
+    }
+
+    /// Returns the records of the given type, in the order they were added.
+    pub fn records_of_type(&self, r#type: Type) -> impl Iterator<Item = &Record> {
+        self.records
+            .iter()
+            .filter(move |record| record.r#type() == r#type)
+    }
+
+    /// Same as [`records_of_type`](Zone::records_of_type), iterating over
+    /// the records of the given type without allocating.
+    pub fn iter_type(&self, r#type: Type) -> impl Iterator<Item = &Record> {
+        self.records_of_type(r#type)
+    }
+
+    /// Returns each record's name, type, class, TTL (in seconds) and RDATA,
+    /// in the wire format, ready to be packed into a DNS message, such as for
+    /// a zone transfer. The name is uncompressed, and the RDATA is in the
+    /// canonical form (see [`Resource::canonical_rdata`]).
+    pub fn to_wire_records(&self) -> Vec<WireRecord> {
+        self.records
+            .iter()
+            .map(|record| {
+                let mut name = Vec::new();
+                write_name(&mut name, &record.name, false);
+
+                (
+                    name,
+                    record.r#type(),
+                    record.class,
+                    record.ttl.as_secs() as u32,
+                    record.resource.canonical_rdata(),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the resources of the given type at the name, as used to
+    /// answer a query. Names are compared ignoring case, and may, or may
+    /// not, end with a dot. [`Type::ANY`] returns the resources of every type.
+    ///
+    /// If the name does not exist, a wildcard such as "*.example.com" is
+    /// used instead, following [rfc4592 section 3.3.1]. That is the wildcard
+    /// of the nearest existing ancestor, so "*.example.com" does not match
+    /// "a.b.example.com" if "b.example.com" exists.
+    ///
+    /// [rfc4592 section 3.3.1]: https://datatracker.ietf.org/doc/html/rfc4592#section-3.3.1
+    pub fn lookup(&self, name: &str, r#type: Type) -> Vec<&Resource> {
+        let name = name.trim_end_matches('.').to_ascii_lowercase();
+        if self.exists(&name) {
+            return self.resources(&name, r#type);
+        }
+
+        let mut parent = name.as_str();
+        while let Some((_, rest)) = parent.split_once('.') {
+            parent = rest;
+
+            let wildcard = format!("*.{}", parent);
+            if self.exists(&wildcard) {
+                return self.resources(&wildcard, r#type);
+            }
+
+            // The closest encloser has no wildcard, so there is no match.
+            if self.exists(parent) {
+                break;
+            }
+        }
+
+        Vec::new()
+    }
+
+    // Returns true if the (lowercase) name has records, or is a empty
+    // non-terminal, that is only its subdomains have records.
+    fn exists(&self, name: &str) -> bool {
+        self.records
+            .iter()
+            .any(|record| is_subdomain(&record.name.to_ascii_lowercase(), name))
+    }
+
+    fn resources(&self, name: &str, r#type: Type) -> Vec<&Resource> {
+        self.records
+            .iter()
+            .filter(|record| record.name.eq_ignore_ascii_case(name))
+            .filter(|record| r#type == Type::ANY || record.r#type() == r#type)
+            .map(|record| &record.resource)
+            .collect()
+    }
+
+    /// Consumes the zone, returning all the records, in the order they were
+    /// added.
+    pub fn into_records(self) -> Vec<Record> {
+        self.records
+    }
+
+    /// Adds the record to the zone, and increments the SOA serial. The name
+    /// must be absolute. Returns false, leaving the zone unchanged, if the
+    /// record was already in the zone.
+    ///
+    /// The zone is unchanged if it would no longer be valid, for example if
+    /// the record is a second SOA, or a CNAME at a name with other records.
+    /// The line of any [`Location`] in the error is the position (starting
+    /// from 1) of the record, with the new record last.
+    pub fn add_record(&mut self, record: Record) -> Result<bool, ValidationError> {
+        let key = duplicate_key(&record);
+        if self.records.iter().any(|r| duplicate_key(r) == key) {
+            return Ok(false);
+        }
+
+        let mut records = self.records.clone();
+        records.push(record);
+        self.update(records)?;
+
+        Ok(true)
+    }
+
+    /// Removes the record from the zone, and increments the SOA serial.
+    /// Returns false, leaving the zone unchanged, if the record was not in
+    /// the zone. As with [`add_record`](Zone::add_record), names are compared
+    /// ignoring case.
+    ///
+    /// The SOA can't be removed, only replaced with
+    /// [`replace_rrset`](Zone::replace_rrset).
+    pub fn remove_record(&mut self, record: &Record) -> Result<bool, ValidationError> {
+        let key = duplicate_key(record);
+        let records: Vec<Record> = self
+            .records
+            .iter()
+            .filter(|r| duplicate_key(r) != key)
+            .cloned()
+            .collect();
+
+        if records.len() == self.records.len() {
+            return Ok(false);
+        }
+
+        self.update(records)?;
+        Ok(true)
+    }
+
+    /// Replaces all the records of the type at the name (the RRset) with
+    /// the given records, and increments the SOA serial. An empty list
+    /// removes the RRset.
+    ///
+    /// The serial is not incremented if the SOA itself is replaced, so the
+    /// new SOA is kept as given.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the records has a different name or type.
+    pub fn replace_rrset<I>(
+        &mut self,
+        name: &str,
+        r#type: Type,
+        records: I,
+    ) -> Result<(), ValidationError>
+    where
+        I: IntoIterator<Item = Record>,
+    {
+        let name = name.trim_end_matches('.');
+        let in_rrset = |record: &Record| {
+            record.r#type() == r#type
+                && record.name.trim_end_matches('.').eq_ignore_ascii_case(name)
+        };
+
+        let mut updated: Vec<Record> = self
+            .records
+            .iter()
+            .filter(|record| !in_rrset(record))
+            .cloned()
+            .collect();
+
+        for record in records {
+            assert!(
+                in_rrset(&record),
+                "record '{}' {} is not in the RRset '{}' {}",
+                record.name,
+                record.r#type(),
+                name,
+                r#type
+            );
+            updated.push(record);
+        }
+
+        self.update(updated)
+    }
+
+    // Replaces the records, if they are still a valid zone, incrementing the
+    // SOA serial unless the SOA was changed.
+    fn update(&mut self, mut records: Vec<Record>) -> Result<(), ValidationError> {
+        check(&self.origin, &records)?;
+
+        let old_soa = find_soa(self.records.iter())?;
+        if find_soa(records.iter())? == old_soa {
+            for record in &mut records {
+                if let Resource::SOA(soa) = &mut record.resource {
+                    soa.serial = soa.serial.wrapping_add(1);
+                }
+            }
+        }
+
+        self.records = records;
+        Ok(())
+    }
+}
+
+/// Builds a [`Zone`], checking it is valid to serve.
+///
+/// ```
+/// use rustdns::zones::{parse_record, ZoneBuilder, ZoneOptions};
+/// use rustdns::Type;
+/// use std::time::Duration;
+///
+/// let options = ZoneOptions {
+///     origin: Some("example.com.".to_string()),
+///     default_ttl: Some(Duration::new(3600, 0)),
+///     ..Default::default()
+/// };
+///
+/// let zone = ZoneBuilder::new()
+///     .record(parse_record("@ IN SOA ns admin 1 7200 3600 1209600 3600", &options).unwrap())
+///     .record(parse_record("@ IN NS ns", &options).unwrap())
+///     .record(parse_record("ns IN A 192.0.2.1", &options).unwrap())
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(zone.origin(), "example.com");
+/// assert_eq!(zone.soa().serial, 1);
+/// assert_eq!(zone.records_of_type(Type::NS).count(), 1);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ZoneBuilder {
+    origin: Option<String>,
+    records: Vec<Record>,
+}
+
+impl ZoneBuilder {
+    pub fn new() -> ZoneBuilder {
+        ZoneBuilder::default()
+    }
+
+    /// Sets the origin of the zone, for example "example.com.". If not set,
+    /// the name of the SOA record is used.
+    pub fn origin(mut self, origin: &str) -> ZoneBuilder {
+        self.origin = Some(origin.trim_end_matches('.').to_string());
+        self
+    }
+
+    /// Adds a record to the zone. The name must be absolute, as returned by
+    /// [`parse_zone`](crate::zones::parse_zone).
+    pub fn record(mut self, record: Record) -> ZoneBuilder {
+        self.records.push(record);
+        self
+    }
+
+    /// Adds all the records to the zone.
+    pub fn records<I: IntoIterator<Item = Record>>(mut self, records: I) -> ZoneBuilder {
+        self.records.extend(records);
+        self
+    }
+
+    /// Returns the Zone, or a error if it has no SOA, more than one SOA, the
+    /// SOA is not at the origin, a record is outside the zone, or it fails
+    /// [`validate_zone`].
+    ///
+    /// As the records did not come from a file, the line of any [`Location`]
+    /// in the error is the position (starting from 1) the record was added.
+    pub fn build(self) -> Result<Zone, ValidationError> {
+        let soa = find_soa(self.records.iter())?;
+
+        let origin = self
+            .origin
+            .unwrap_or_else(|| soa.name.trim_end_matches('.').to_string());
+        check(&origin, &self.records)?;
+
+        Ok(Zone {
+            origin,
+            records: self.records,
+        })
+    }
+}
+
+// Checks the records are a valid zone at the origin, using the position of
+// each record as the line of its Location.
+fn check(origin: &str, records: &[Record]) -> Result<(), ValidationError> {
+    let soa = find_soa(records.iter())?;
+
+    let soa_name = soa.name.trim_end_matches('.');
+    if !soa_name.eq_ignore_ascii_case(origin) {
+        return Err(ValidationError::SoaNotAtOrigin {
+            name: soa.name.to_owned(),
+            origin: origin.to_owned(),
+        });
+    }
+
+    let located: Vec<(Record, Location)> = records
+        .iter()
+        .enumerate()
+        .map(|(i, record)| {
+            (
+                record.clone(),
+                Location {
+                    line: i + 1,
+                    column: 1,
+                },
+            )
+        })
+        .collect();
+    validate_zone(&located)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::zones::parse_zone;
+    use crate::zones::ValidationError;
+    use crate::zones::ZoneBuilder;
+    use crate::Class;
+    use crate::Type;
+    use pretty_assertions::assert_eq;
+
+    static INPUT: &str = "$ORIGIN example.com.
+        $TTL 3600
+        @     IN  SOA  ns admin ( 2020091025 7200 3600 1209600 3600 )
+        @     IN  NS   ns
+        @     IN  NS   ns.other.example.
+        @     IN  MX   10 mail
+        ns    IN  A    192.0.2.1
+        mail  IN  A    192.0.2.2";
+
+    #[test]
+    fn test_zone_builder() {
+        let records = parse_zone(INPUT).expect("failed to parse");
+
+        let zone = ZoneBuilder::new()
+            .records(records.clone())
+            .build()
+            .expect("failed to build");
+
+        assert_eq!(zone.origin(), "example.com");
+        assert_eq!(zone.soa().serial, 2020091025);
+        assert_eq!(zone.soa().mname, "ns.example.com");
+        assert_eq!(zone.records(), records.as_slice());
+        assert_eq!(
+            zone.records_of_type(Type::NS)
+                .map(|record| record.resource.to_string())
+                .collect::<Vec<String>>(),
+            vec!["ns.example.com", "ns.other.example"]
+        );
+        assert_eq!(zone.records_of_type(Type::AAAA).count(), 0);
+
+        // The origin may also be given, with or without the trailing dot.
+        let zone = ZoneBuilder::new()
+            .origin("Example.COM.")
+            .records(records)
+            .build()
+            .expect("failed to build");
+        assert_eq!(zone.origin(), "Example.COM");
+    }
+
+    #[test]
+    fn test_zone_to_wire_records() {
+        let records = parse_zone(INPUT).expect("failed to parse");
+        let zone = ZoneBuilder::new()
+            .records(records)
+            .build()
+            .expect("failed to build");
+
+        let got = zone.to_wire_records();
+        assert_eq!(got.len(), zone.records().len());
+
+        let name = b"\x07example\x03com\x00".to_vec();
+        assert_eq!(
+            got[3],
+            (
+                name.clone(),
+                Type::MX,
+                Class::Internet,
+                3600,
+                b"\x00\x0a\x04mail\x07example\x03com\x00".to_vec()
+            )
+        );
+        assert_eq!(
+            got[4],
+            (
+                b"\x02ns\x07example\x03com\x00".to_vec(),
+                Type::A,
+                Class::Internet,
+                3600,
+                vec![192, 0, 2, 1]
+            )
+        );
+        assert_eq!((&got[0].0, got[0].1), (&name, Type::SOA));
+    }
+
+    #[test]
+    fn test_zone_records_of_type() {
+        // Example from https://datatracker.ietf.org/doc/html/rfc1035#section-5.3
+        // with a $TTL added, as the original relies on the TTL being implied.
+        let records = parse_zone(
+            "$ORIGIN ISI.EDU.
+            $TTL 3600
+            @   IN  SOA     VENERA      Action\\.domains ( 20 7200 600 3600000 60 )
+                    NS      A.ISI.EDU.
+                    NS      VENERA
+                    NS      VAXA
+                    MX      10      VENERA
+                    MX      20      VAXA
+            A       A       26.3.0.103
+            VENERA  A       10.1.0.52
+                    A       128.9.0.32
+            VAXA    A       10.2.0.27
+                    A       128.9.0.33",
+        )
+        .expect("failed to parse");
+
+        let zone = ZoneBuilder::new()
+            .records(records)
+            .build()
+            .expect("failed to build");
+
+        assert_eq!(zone.records_of_type(Type::NS).count(), 3);
+        assert_eq!(zone.records_of_type(Type::MX).count(), 2);
+        assert_eq!(zone.records_of_type(Type::A).count(), 5);
+        assert_eq!(zone.records_of_type(Type::SOA).count(), 1);
+
+        assert_eq!(zone.iter_type(Type::NS).count(), 3);
+        assert!(zone.iter_type(Type::MX).eq(zone.records_of_type(Type::MX)));
+    }
+
+    #[test]
+    fn test_zone_builder_errors() {
+        let records = parse_zone(INPUT).expect("failed to parse");
+        let extra = parse_zone(
+            "$ORIGIN example.com.
+            $TTL 3600
+            @    IN  SOA    ns admin ( 1 7200 3600 1209600 3600 )
+            www  IN  A      192.0.2.3
+            www  IN  CNAME  ns
+            www.example.org.  IN  A  192.0.2.4",
+        )
+        .expect("failed to parse");
+
+        let tests = vec![
+            (ZoneBuilder::new(), ValidationError::MissingSoa),
+            (
+                ZoneBuilder::new().records(records[1..].to_vec()),
+                ValidationError::MissingSoa,
+            ),
+            (
+                ZoneBuilder::new()
+                    .records(records.clone())
+                    .record(extra[0].clone()),
+                ValidationError::MultipleSoa(2),
+            ),
+            (
+                ZoneBuilder::new()
+                    .origin("other.example.")
+                    .records(records.clone()),
+                ValidationError::SoaNotAtOrigin {
+                    name: "example.com".to_string(),
+                    origin: "other.example".to_string(),
+                },
+            ),
+            (
+                ZoneBuilder::new()
+                    .records(records.clone())
+                    .record(extra[3].clone()),
+                ValidationError::OutsideZone {
+                    name: "www.example.org".to_string(),
+                    origin: "example.com".to_string(),
+                },
+            ),
+        ];
+
+        for (builder, want) in tests {
+            assert_eq!(
+                builder.clone().build(),
+                Err(want),
+                "incorrect result for {:?}",
+                builder
+            );
+        }
+
+        // The validation errors refer to the order the records were added.
+        let err = ZoneBuilder::new()
+            .records(records)
+            .record(extra[1].clone())
+            .record(extra[2].clone())
+            .build()
+            .expect_err("incorrectly built");
+        assert_eq!(
+            err.to_string(),
+            "'www.example.com' has a CNAME (line 8) and a A record (line 7)"
+        );
+    }
+
+    #[test]
+    fn test_zone_update() {
+        let records = parse_zone(INPUT).expect("failed to parse");
+        let mut zone = ZoneBuilder::new()
+            .records(records)
+            .build()
+            .expect("failed to build");
+
+        let extra = parse_zone(
+            "$ORIGIN example.com.
+            $TTL 3600
+            @    IN  SOA    ns admin ( 1 7200 3600 1209600 3600 )
+            www  IN  A      192.0.2.3
+            www  IN  A      192.0.2.4
+            www  IN  CNAME  ns
+            WWW  IN  A      192.0.2.3
+            www.example.org.  IN  A  192.0.2.5",
+        )
+        .expect("failed to parse");
+
+        // Each change increments the serial.
+        assert_eq!(zone.add_record(extra[1].clone()), Ok(true));
+        assert_eq!(zone.soa().serial, 2020091026);
+        assert_eq!(zone.lookup("www.example.com", Type::A).len(), 1);
+
+        // Adding the same record again (ignoring case) changes nothing.
+        assert_eq!(zone.add_record(extra[4].clone()), Ok(false));
+        assert_eq!(zone.soa().serial, 2020091026);
+
+        assert_eq!(zone.add_record(extra[2].clone()), Ok(true));
+        assert_eq!(zone.soa().serial, 2020091027);
+        assert_eq!(zone.lookup("www.example.com", Type::A).len(), 2);
+
+        assert_eq!(zone.remove_record(&extra[1]), Ok(true));
+        assert_eq!(zone.remove_record(&extra[1]), Ok(false));
+        assert_eq!(zone.soa().serial, 2020091028);
+        assert_eq!(
+            zone.lookup("www.example.com", Type::A)[0].to_string(),
+            "192.0.2.4"
+        );
+
+        // Invalid changes are rejected, leaving the zone unchanged.
+        let before = zone.clone();
+        assert_eq!(
+            zone.add_record(extra[0].clone()),
+            Err(ValidationError::MultipleSoa(2))
+        );
+        let soa = zone.records_of_type(Type::SOA).next().unwrap().clone();
+        assert_eq!(zone.remove_record(&soa), Err(ValidationError::MissingSoa));
+        assert_eq!(
+            zone.add_record(extra[5].clone()),
+            Err(ValidationError::OutsideZone {
+                name: "www.example.org".to_string(),
+                origin: "example.com".to_string(),
+            })
+        );
+        assert_eq!(
+            zone.add_record(extra[3].clone())
+                .expect_err("incorrectly added")
+                .to_string(),
+            "'www.example.com' has a CNAME (line 8) and a A record (line 7)"
+        );
+        assert_eq!(zone, before);
+
+        // Replacing the A records with a CNAME keeps the zone valid.
+        zone.replace_rrset("www.example.com.", Type::A, vec![])
+            .expect("failed to remove");
+        zone.replace_rrset("WWW.example.com", Type::CNAME, vec![extra[3].clone()])
+            .expect("failed to replace");
+        assert_eq!(zone.soa().serial, 2020091030);
+        assert_eq!(
+            zone.lookup("www.example.com", Type::ANY)
+                .iter()
+                .map(|resource| resource.to_string())
+                .collect::<Vec<String>>(),
+            vec!["ns.example.com"]
+        );
+
+        // The SOA may be replaced, and is kept as given.
+        zone.replace_rrset("example.com", Type::SOA, vec![extra[0].clone()])
+            .expect("failed to replace");
+        assert_eq!(zone.soa().serial, 1);
+        assert_eq!(zone.records_of_type(Type::SOA).count(), 1);
+    }
+
+    #[test]
+    fn test_zone_lookup() {
+        let records = parse_zone(
+            "$ORIGIN example.com.
+            $TTL 3600
+            @        IN  SOA  ns admin ( 1 7200 3600 1209600 3600 )
+            @        IN  NS   ns
+            ns       IN  A    192.0.2.1
+            www      IN  A    192.0.2.2
+            www      IN  A    192.0.2.3
+            www      IN  TXT  \"hello\"
+            *        IN  A    192.0.2.4
+            x.sub    IN  A    192.0.2.5
+            *.other  IN  MX   10 mail",
+        )
+        .expect("failed to parse");
+
+        let zone = ZoneBuilder::new()
+            .records(records)
+            .build()
+            .expect("failed to build");
+
+        let lookup = |name, r#type| -> Vec<String> {
+            zone.lookup(name, r#type)
+                .iter()
+                .map(|resource| resource.to_string())
+                .collect()
+        };
+
+        let tests = vec![
+            // Exact matches, ignoring case and the trailing dot.
+            ("www.example.com.", Type::A, vec!["192.0.2.2", "192.0.2.3"]),
+            ("WWW.Example.COM", Type::A, vec!["192.0.2.2", "192.0.2.3"]),
+            ("www.example.com.", Type::TXT, vec!["\"hello\""]),
+            (
+                "www.example.com.",
+                Type::ANY,
+                vec!["192.0.2.2", "192.0.2.3", "\"hello\""],
+            ),
+            ("example.com.", Type::NS, vec!["ns.example.com"]),
+            // Wildcard matches, as the name does not exist.
+            ("foo.example.com.", Type::A, vec!["192.0.2.4"]),
+            ("a.b.example.com.", Type::A, vec!["192.0.2.4"]),
+            (
+                "mail.other.example.com.",
+                Type::MX,
+                vec!["10 mail.example.com"],
+            ),
+            // The name exists, so the wildcard is not used.
+            ("www.example.com.", Type::AAAA, vec![]),
+            ("ns.example.com.", Type::MX, vec![]),
+            // "sub" exists (with no records), so is the closest encloser,
+            // which has no wildcard.
+            ("sub.example.com.", Type::A, vec![]),
+            ("y.sub.example.com.", Type::A, vec![]),
+            // The wildcard exists, but has no records of that type.
+            ("foo.example.com.", Type::TXT, vec![]),
+            // Outside the zone.
+            ("www.example.org.", Type::A, vec![]),
+        ];
+
+        for (name, r#type, want) in tests {
+            assert_eq!(
+                lookup(name, r#type),
+                want,
+                "incorrect result for {} {}",
+                name,
+                r#type
+            );
+        }
+    }
+}